@@ -2,6 +2,8 @@
 
 mod parser;
 
+use std::ops::Range;
+
 use parser::parse;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -56,6 +58,109 @@ impl<'a> Text<'a> {
     }
 }
 
+/// ANSIエスケープシーケンスを取り除いた文字列を返す
+pub fn strip_ansi(s: &str) -> String {
+    s.ansi_parse()
+        .filter(|text| text.ty == AnsiEscapeSequence::Chars)
+        .map(|text| text.chars)
+        .collect()
+}
+
+/// ANSIエスケープシーケンスを取り除いた文字列上の範囲をSGRで装飾する
+///
+/// rangesはエスケープシーケンスを取り除いた文字列のバイト位置とSGRのパラメータ（例: `30;43`）で、
+/// 開始位置の昇順かつ重複しないこと。
+/// 装飾の終了時はリセットした後にそれまでのSGRを再適用し、元の装飾を復元する。
+/// 解析できないエスケープシーケンスを含む場合は装飾せずにそのまま返す
+pub fn highlight(s: &str, ranges: &[(Range<usize>, &str)]) -> String {
+    let mut buf = String::with_capacity(s.len());
+
+    let mut sgr_history = String::new();
+
+    let mut ranges = ranges
+        .iter()
+        .filter(|(range, _)| !range.is_empty())
+        .peekable();
+
+    let mut active: Option<&(Range<usize>, &str)> = None;
+
+    let mut consumed = 0;
+
+    let mut offset = 0;
+
+    for text in s.ansi_parse() {
+        consumed += text.len();
+
+        if text.ty != AnsiEscapeSequence::Chars {
+            buf.push_str(text.chars);
+
+            if let AnsiEscapeSequence::SelectGraphicRendition(_) = text.ty {
+                sgr_history.push_str(text.chars);
+
+                // 装飾中に元のSGRで上書きされないよう再適用する
+                if let Some((_, sgr)) = active {
+                    buf.push_str(&format!("\x1b[{}m", sgr));
+                }
+            }
+
+            continue;
+        }
+
+        let chars = text.chars;
+
+        let start = offset;
+        let end = offset + chars.len();
+
+        let mut pos = start;
+
+        while pos < end {
+            if let Some((range, _)) = active {
+                let stop = range.end.min(end);
+
+                buf.push_str(&chars[pos - start..stop - start]);
+
+                pos = stop;
+
+                if pos == range.end {
+                    buf.push_str("\x1b[0m");
+                    buf.push_str(&sgr_history);
+
+                    active = None;
+                }
+            } else if let Some(next) = ranges.next_if(|(range, _)| range.start < end) {
+                let (range, sgr) = next;
+
+                let stop = range.start.max(pos);
+
+                buf.push_str(&chars[pos - start..stop - start]);
+
+                pos = stop;
+
+                buf.push_str(&format!("\x1b[{}m", sgr));
+
+                active = Some(next);
+            } else {
+                buf.push_str(&chars[pos - start..]);
+
+                pos = end;
+            }
+        }
+
+        offset = end;
+    }
+
+    if consumed != s.len() {
+        return s.to_string();
+    }
+
+    if active.is_some() {
+        buf.push_str("\x1b[0m");
+        buf.push_str(&sgr_history);
+    }
+
+    buf
+}
+
 pub trait TextParser {
     fn ansi_parse(&self) -> TextIterator;
 }
@@ -188,6 +293,59 @@ mod parse_test {
         assert_eq!(iter.next(), Some(Text::new("\x1b[1B", CursorDown(1))));
     }
 
+    #[test]
+    fn strip_ansi_text() {
+        assert_eq!(
+            strip_ansi("\x1b[92m[pod\x1b[39m \x1b[32mcontainer\x1b[39m] text\x1b[0m"),
+            "[pod container] text"
+        );
+    }
+
+    #[test]
+    fn highlight_plain_text() {
+        assert_eq!(
+            highlight(
+                "error: failed to connect",
+                &[(0..5, "30;41"), (14..16, "30;43")]
+            ),
+            "\x1b[30;41merror\x1b[0m: failed \x1b[30;43mto\x1b[0m connect"
+        );
+    }
+
+    #[test]
+    fn highlight_restores_sgr() {
+        // [pod] のSGR以降に装飾を終了した場合は、それまでのSGRを再適用する
+        assert_eq!(
+            highlight(
+                "\x1b[92m[pod]\x1b[39m level=\x1b[33mwarn\x1b[39m",
+                &[(3..11, "30;43")]
+            ),
+            [
+                "\x1b[92m[po",
+                "\x1b[30;43md]",
+                "\x1b[39m\x1b[30;43m level",
+                "\x1b[0m\x1b[92m\x1b[39m=",
+                "\x1b[33mwarn\x1b[39m",
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn highlight_range_to_end() {
+        assert_eq!(
+            highlight("abc", &[(1..3, "30;43")]),
+            "a\x1b[30;43mbc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn highlight_unsupported_sequence() {
+        let s = "text\x1b]6;1;bg;green;brightness;26\x07";
+
+        assert_eq!(highlight(s, &[(0..4, "30;43")]), s);
+    }
+
     mod not_support {
         use super::*;
         use pretty_assertions::assert_eq;
@@ -9,15 +9,27 @@ use crossbeam::channel::{bounded, Receiver, Sender};
 use crate::{
     cmd::Command,
     message::Message,
-    workers::{KubeWorker, Render, Tick, UserInput},
+    workers::{
+        kube::KubeWorkerConfig, InputPause, KubeWorker, Render, RenderConfig, Tick, UserInput,
+    },
 };
 
 pub struct App;
 
 impl App {
     pub fn run(cmd: Command) -> Result<()> {
-        let split_direction = cmd.split_direction();
-        let kube_worker_config = cmd.kube_worker_config();
+        let config = cmd.config()?;
+        let write_guard = cmd.write_guard(&config)?;
+
+        let render_config = RenderConfig {
+            write_guard: write_guard.clone(),
+            ..cmd.render_config()
+        };
+        let kube_worker_config = KubeWorkerConfig {
+            write_guard,
+            poll_intervals: cmd.poll_intervals(&config)?,
+            ..cmd.kube_worker_config()
+        };
 
         let (tx_input, rx_main): (Sender<Message>, Receiver<Message>) = bounded(128);
         let (tx_main, rx_kube): (Sender<Message>, Receiver<Message>) = bounded(256);
@@ -26,7 +38,10 @@ impl App {
 
         let is_terminated = Arc::new(AtomicBool::new(false));
 
-        let user_input = UserInput::new(tx_input.clone(), is_terminated.clone());
+        let input_pause = InputPause::default();
+
+        let user_input =
+            UserInput::new(tx_input.clone(), is_terminated.clone(), input_pause.clone());
 
         let kube = KubeWorker::new(
             tx_kube.clone(),
@@ -45,7 +60,8 @@ impl App {
             tx_main.clone(),
             rx_main.clone(),
             is_terminated.clone(),
-            split_direction,
+            input_pause,
+            render_config,
         );
 
         thread::scope(|s| {
@@ -1,8 +1,13 @@
+use anyhow::{bail, Result};
 use clap::Parser;
 use ratatui::layout::Direction;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use crate::workers::kube::KubeWorkerConfig;
+use crate::{
+    config::{Config, PollingConfig},
+    kube::{guard::WriteGuard, polling::PollIntervals},
+    workers::{kube::KubeWorkerConfig, RenderConfig},
+};
 
 use super::args::{AllNamespaces, SplitDirection};
 
@@ -65,6 +70,40 @@ pub struct Command {
     /// Logging
     #[arg(short = 'l', long, display_order = 1000)]
     pub logging: bool,
+
+    /// Maximum number of lines kept in the log view. Older lines are dropped (0 = unlimited)
+    #[arg(
+        long,
+        value_name = "LINES",
+        default_value_t = 100_000,
+        display_order = 1000
+    )]
+    pub log_max_lines: usize,
+
+    /// Image used for ephemeral debug containers
+    #[arg(
+        long,
+        value_name = "IMAGE",
+        default_value = "busybox",
+        display_order = 1000
+    )]
+    pub debug_image: String,
+
+    /// Disable all write operations (delete, edit, scale, exec, ...)
+    #[arg(long, display_order = 1000)]
+    pub readonly: bool,
+
+    /// Config file path [default: $XDG_CONFIG_HOME/kubetui/config.yaml]
+    #[arg(long, value_name = "PATH", display_order = 1000)]
+    pub config: Option<PathBuf>,
+
+    /// Polling interval in seconds for the visible tab [default: 1]
+    #[arg(long, value_name = "SECONDS", display_order = 1000)]
+    pub poll_interval: Option<u64>,
+
+    /// Polling interval in seconds for hidden tabs (0 = pause) [default: 10]
+    #[arg(long, value_name = "SECONDS", display_order = 1000)]
+    pub background_poll_interval: Option<u64>,
 }
 
 impl Command {
@@ -76,6 +115,15 @@ impl Command {
         self.split_direction.to_direction()
     }
 
+    pub fn render_config(&self) -> RenderConfig {
+        RenderConfig {
+            split_direction: self.split_direction(),
+            log_max_lines: (0 < self.log_max_lines).then_some(self.log_max_lines),
+            debug_image: self.debug_image.clone(),
+            write_guard: WriteGuard::default(),
+        }
+    }
+
     pub fn kube_worker_config(&self) -> KubeWorkerConfig {
         let Self {
             namespaces,
@@ -90,7 +138,48 @@ impl Command {
             target_namespaces: namespaces,
             context,
             all_namespaces: all_namespaces.into(),
+            write_guard: WriteGuard::default(),
+            poll_intervals: PollIntervals::default(),
+        }
+    }
+
+    pub fn config(&self) -> Result<Config> {
+        Config::load(self.config.as_deref())
+    }
+
+    pub fn write_guard(&self, config: &Config) -> Result<WriteGuard> {
+        WriteGuard::new(self.readonly, &config.contexts)
+    }
+
+    /// ポーリング間隔を返す
+    ///
+    /// コマンドライン引数、設定ファイル、デフォルト値の順に優先する
+    pub fn poll_intervals(&self, config: &Config) -> Result<PollIntervals> {
+        let PollingConfig {
+            interval,
+            description_interval,
+            background_interval,
+        } = config.polling;
+
+        let interval = self.poll_interval.or(interval);
+        let background_interval = self.background_poll_interval.or(background_interval);
+
+        if interval == Some(0) || description_interval == Some(0) {
+            bail!("Polling interval must be greater than 0");
         }
+
+        let default = PollIntervals::default();
+
+        Ok(PollIntervals {
+            interval: interval.map_or(default.interval, Duration::from_secs),
+            description_interval: description_interval
+                .map_or(default.description_interval, Duration::from_secs),
+            background_interval: match background_interval {
+                Some(0) => None,
+                Some(secs) => Some(Duration::from_secs(secs)),
+                None => default.background_interval,
+            },
+        })
     }
 }
 
@@ -117,6 +206,115 @@ mod tests {
         }
     }
 
+    mod log_max_lines {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn 値を設定しないとき100000行を上限とする() {
+            let cmd = Command::try_parse_from(["kubetui"]).unwrap();
+            assert_eq!(cmd.render_config().log_max_lines, Some(100_000))
+        }
+
+        #[test]
+        fn 設定した値を上限とする() {
+            let cmd = Command::try_parse_from(["kubetui", "--log-max-lines", "500"]).unwrap();
+            assert_eq!(cmd.render_config().log_max_lines, Some(500))
+        }
+
+        #[test]
+        fn ゼロを設定したとき上限なしとする() {
+            let cmd = Command::try_parse_from(["kubetui", "--log-max-lines", "0"]).unwrap();
+            assert_eq!(cmd.render_config().log_max_lines, None)
+        }
+    }
+
+    mod debug_image {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn 値を設定しないときbusyboxを使う() {
+            let cmd = Command::try_parse_from(["kubetui"]).unwrap();
+            assert_eq!(cmd.render_config().debug_image, "busybox")
+        }
+
+        #[test]
+        fn 設定したイメージを使う() {
+            let cmd =
+                Command::try_parse_from(["kubetui", "--debug-image", "nicolaka/netshoot"]).unwrap();
+            assert_eq!(cmd.render_config().debug_image, "nicolaka/netshoot")
+        }
+    }
+
+    mod readonly {
+        use super::*;
+
+        #[test]
+        fn 値を設定しないとき書き込み操作を許可する() {
+            let cmd = Command::try_parse_from(["kubetui"]).unwrap();
+            let guard = cmd.write_guard(&Config::default()).unwrap();
+            assert!(!guard.is_readonly("kind-kind"))
+        }
+
+        #[test]
+        fn 設定したときすべてのコンテキストで書き込み操作を無効にする() {
+            let cmd = Command::try_parse_from(["kubetui", "--readonly"]).unwrap();
+            let guard = cmd.write_guard(&Config::default()).unwrap();
+            assert!(guard.is_readonly("kind-kind"))
+        }
+    }
+
+    mod poll_intervals {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        fn config(interval: Option<u64>, background_interval: Option<u64>) -> Config {
+            Config {
+                polling: PollingConfig {
+                    interval,
+                    background_interval,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn 値を設定しないときデフォルトの間隔を使う() {
+            let cmd = Command::try_parse_from(["kubetui"]).unwrap();
+            assert_eq!(
+                cmd.poll_intervals(&Config::default()).unwrap(),
+                PollIntervals::default()
+            )
+        }
+
+        #[test]
+        fn 引数を設定ファイルより優先する() {
+            let cmd = Command::try_parse_from(["kubetui", "--poll-interval", "5"]).unwrap();
+            let intervals = cmd.poll_intervals(&config(Some(2), Some(30))).unwrap();
+            assert_eq!(intervals.interval, Duration::from_secs(5));
+            assert_eq!(intervals.background_interval, Some(Duration::from_secs(30)))
+        }
+
+        #[test]
+        fn バックグラウンドの間隔にゼロを設定したとき停止する() {
+            let cmd =
+                Command::try_parse_from(["kubetui", "--background-poll-interval", "0"]).unwrap();
+            let intervals = cmd.poll_intervals(&Config::default()).unwrap();
+            assert_eq!(intervals.background_interval, None)
+        }
+
+        #[test]
+        fn 表示中のタブの間隔にゼロを設定したときエラーを返す() {
+            let cmd = Command::try_parse_from(["kubetui", "--poll-interval", "0"]).unwrap();
+            assert!(cmd.poll_intervals(&Config::default()).is_err())
+        }
+    }
+
     mod namespace {
         use clap::error::ErrorKind;
         use pretty_assertions::assert_eq;
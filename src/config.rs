@@ -0,0 +1,146 @@
+//! 設定ファイル
+//!
+//! `--config` で指定したファイル、または `$XDG_CONFIG_HOME/kubetui/config.yaml`
+//! （未設定の場合は `~/.config/kubetui/config.yaml`）を読み込む
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub contexts: ContextsConfig,
+    #[serde(default)]
+    pub polling: PollingConfig,
+}
+
+/// コンテキストごとの設定
+///
+/// いずれもコンテキスト名に一致する正規表現
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContextsConfig {
+    /// 書き込み操作を無効にするコンテキスト
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// `deny` に一致しても書き込み操作を許可するコンテキスト
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// ヘッダーにPRODバッジを表示するコンテキスト
+    #[serde(default)]
+    pub production: Vec<String>,
+}
+
+/// ポーリング間隔の設定（秒）
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PollingConfig {
+    /// 表示中のタブの一覧を更新する間隔
+    pub interval: Option<u64>,
+    /// 表示中のタブの詳細（YAMLなど）を更新する間隔
+    pub description_interval: Option<u64>,
+    /// 表示していないタブを更新する間隔（0の場合は停止する）
+    pub background_interval: Option<u64>,
+}
+
+impl Config {
+    /// 設定ファイルを読み込む
+    ///
+    /// パスを指定しなかった場合、デフォルトのパスにファイルがなければデフォルトの設定を返す
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match default_path().filter(|path| path.exists()) {
+                Some(path) => path,
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+        Self::parse(&content)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        // 空のファイルはnullとして解釈される
+        let config: Option<Self> = serde_yaml::from_str(content)?;
+
+        Ok(config.unwrap_or_default())
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("kubetui").join("config.yaml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn コンテキストの設定を読み込む() {
+        let actual = Config::parse(indoc! {r#"
+            contexts:
+              deny:
+                - "^prod-"
+              allow:
+                - "^prod-sandbox$"
+              production:
+                - "^prod-"
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            actual,
+            Config {
+                contexts: ContextsConfig {
+                    deny: vec!["^prod-".to_string()],
+                    allow: vec!["^prod-sandbox$".to_string()],
+                    production: vec!["^prod-".to_string()],
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn ポーリング間隔の設定を読み込む() {
+        let actual = Config::parse(indoc! {r#"
+            polling:
+              interval: 2
+              background_interval: 0
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            actual.polling,
+            PollingConfig {
+                interval: Some(2),
+                description_interval: None,
+                background_interval: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn 空のファイルはデフォルトの設定とする() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn 未知のキーはエラーとする() {
+        assert!(Config::parse("contexts:\n  readonly: []\n").is_err());
+    }
+}
@@ -2,10 +2,16 @@ pub mod api_resources;
 pub mod component_id;
 pub mod config;
 pub mod context;
+pub mod delete;
+pub mod dry_run;
+pub mod edit;
 pub mod event;
 pub mod get;
 pub mod help;
 pub mod namespace;
 pub mod network;
+pub mod node;
 pub mod pod;
+pub mod port_forward;
+pub mod workload;
 pub mod yaml;
@@ -12,12 +12,13 @@ use serde_json::Value as JsonValue;
 use tokio::{sync::RwLock, time::Instant};
 
 use crate::{
-    features::api_resources::message::ApiResponse,
+    features::{api_resources::message::ApiResponse, component_id::LIST_TAB_ID},
     kube::{
         apis::{
             metrics::{NodeMetricsList, PodMetricsList},
             v1_table::{Table, TableColumnDefinition, Value},
         },
+        informer::{ResourcePath, TableInformers},
         table::insert_ns,
         KubeClient, KubeClientRequest as _,
     },
@@ -29,6 +30,9 @@ use crate::{
 
 pub type SharedApiResources = Arc<RwLock<ApiResources>>;
 
+/// メトリクスAPIはwatchできないため、キャッシュを使わずに都度取得する
+const METRICS_GROUP: &str = "metrics.k8s.io";
+
 /// kubectl api-resources の結果を保持
 /// Network一覧機能のために順番が重要なためVecで保持
 #[derive(Debug, Default, Clone)]
@@ -258,6 +262,9 @@ impl Worker for ApiPoller {
                     tx,
                     shared_target_namespaces,
                     kube_client,
+                    informers,
+                    polling,
+                    health,
                 },
             shared_target_api_resources,
             shared_api_resources,
@@ -274,7 +281,7 @@ impl Worker for ApiPoller {
             }
         }
 
-        let mut interval = tokio::time::interval(time::Duration::from_millis(1000));
+        let mut changes = informers.changes(polling.ticker(LIST_TAB_ID));
 
         let mut last_tick = Instant::now();
         let tick_rate = time::Duration::from_secs(10);
@@ -282,12 +289,17 @@ impl Worker for ApiPoller {
         let mut is_error = false;
 
         while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
-            interval.tick().await;
+            changes.wait().await;
+            health.wait_backoff().await;
 
             if tick_rate < last_tick.elapsed() {
                 last_tick = Instant::now();
 
-                match fetch_api_resources(kube_client).await {
+                let fetched = fetch_api_resources(kube_client).await;
+
+                health.record(&fetched);
+
+                match fetched {
                     Ok(fetched) => {
                         let mut api_resources = shared_api_resources.write().await;
                         *api_resources = fetched;
@@ -317,6 +329,7 @@ impl Worker for ApiPoller {
 
             let result = FetchTargetApiResources::new(
                 kube_client,
+                changes.informers(),
                 &target_api_resources,
                 &target_namespaces,
             )
@@ -414,7 +427,16 @@ fn merge_tables(fetch_data: Vec<FetchData>, insert_ns: bool) -> Table {
     base_table
 }
 
-async fn try_fetch_table(client: &KubeClient, path: &str) -> Result<Table> {
+async fn try_fetch_table(
+    client: &KubeClient,
+    informers: &TableInformers,
+    api_resource: &ApiResource,
+    path: &str,
+) -> Result<Table> {
+    if api_resource.group() != METRICS_GROUP {
+        return informers.table(path).await;
+    }
+
     let table = client.table_request::<Table>(path).await;
 
     if let Ok(t) = table {
@@ -439,10 +461,13 @@ struct FetchData {
 
 async fn fetch_table_per_namespace(
     client: &KubeClient,
-    path: String,
+    informers: &TableInformers,
+    api_resource: &ApiResource,
     ns: &str,
 ) -> Result<FetchData> {
-    let table = try_fetch_table(client, &path).await?;
+    let path = api_resource.api_url_with_namespace(ns);
+
+    let table = try_fetch_table(client, informers, api_resource, &path).await?;
 
     Ok(FetchData {
         namespace: ns.to_string(),
@@ -453,14 +478,33 @@ async fn fetch_table_per_namespace(
 #[inline]
 async fn get_table_namespaced_resource(
     client: &KubeClient,
+    informers: &TableInformers,
     api_resource: &ApiResource,
     namespaces: &[String],
 ) -> Result<Table> {
-    let jobs =
-        try_join_all(namespaces.iter().map(|ns| {
-            fetch_table_per_namespace(client, api_resource.api_url_with_namespace(ns), ns)
-        }))
-        .await?;
+    if api_resource.group() != METRICS_GROUP {
+        let resource = ResourcePath::new(
+            api_resource.group(),
+            api_resource.version(),
+            api_resource.name(),
+        );
+
+        let result = informers
+            .tables(&resource, namespaces)
+            .await?
+            .into_iter()
+            .map(|(namespace, table)| FetchData { namespace, table })
+            .collect();
+
+        return Ok(merge_tables(result, insert_ns(namespaces)));
+    }
+
+    let jobs = try_join_all(
+        namespaces
+            .iter()
+            .map(|ns| fetch_table_per_namespace(client, informers, api_resource, ns)),
+    )
+    .await?;
 
     let result: Vec<FetchData> = jobs.into_iter().collect();
 
@@ -468,12 +512,17 @@ async fn get_table_namespaced_resource(
 }
 
 #[inline]
-async fn get_table_cluster_resource(client: &KubeClient, path: &str) -> Result<Table> {
-    try_fetch_table(client, path).await
+async fn get_table_cluster_resource(
+    client: &KubeClient,
+    informers: &TableInformers,
+    api_resource: &ApiResource,
+) -> Result<Table> {
+    try_fetch_table(client, informers, api_resource, &api_resource.api_url()).await
 }
 
 struct FetchTargetApiResources<'a> {
     client: &'a KubeClient,
+    informers: &'a TableInformers,
     target_api_resources: &'a TargetApiResources,
     target_namespace: &'a TargetNamespaces,
 }
@@ -481,11 +530,13 @@ struct FetchTargetApiResources<'a> {
 impl<'a> FetchTargetApiResources<'a> {
     fn new(
         client: &'a KubeClient,
+        informers: &'a TableInformers,
         target_api_resources: &'a TargetApiResources,
         target_namespace: &'a TargetNamespaces,
     ) -> Self {
         Self {
             client,
+            informers,
             target_api_resources,
             target_namespace,
         }
@@ -495,10 +546,15 @@ impl<'a> FetchTargetApiResources<'a> {
         let mut ret = Vec::new();
         for api_resource in self.target_api_resources {
             let table = if api_resource.is_namespaced() {
-                get_table_namespaced_resource(self.client, api_resource, self.target_namespace)
-                    .await
+                get_table_namespaced_resource(
+                    self.client,
+                    self.informers,
+                    api_resource,
+                    self.target_namespace,
+                )
+                .await
             } else {
-                get_table_cluster_resource(self.client, &api_resource.api_url()).await
+                get_table_cluster_resource(self.client, self.informers, api_resource).await
             }?;
 
             let data = if table.rows.is_empty() {
@@ -18,9 +18,13 @@ component_id!(
     list_tab,
     network_tab,
     yaml_tab,
+    workload_tab,
+    port_forward_tab,
+    node_tab,
     // widgets
     pod_widget,
     pod_log_widget,
+    pod_log_pane_widget,
     pod_log_query_widget,
     config_widget,
     config_raw_data_widget,
@@ -29,9 +33,24 @@ component_id!(
     event_widget,
     list_widget,
     yaml_widget,
+    workload_widget,
+    port_forward_widget,
+    node_widget,
+    node_drain_widget,
     // popups
     pod_log_query_help_popup,
+    pod_log_save_popup,
+    pod_log_save_status_popup,
+    pod_exec_popup,
+    pod_debug_popup,
+    pod_debug_status_popup,
     context_popup,
+    delete_popup,
+    edit_popup,
+    workload_scale_popup,
+    workload_action_popup,
+    port_forward_popup,
+    node_action_popup,
     single_namespace_popup,
     multiple_namespaces_popup,
     list_popup,
@@ -1,11 +1,11 @@
-use std::{collections::BTreeMap, time};
+use std::collections::BTreeMap;
 
 use crate::{
-    features::config::message::ConfigResponse,
+    features::{component_id::CONFIG_TAB_ID, config::message::ConfigResponse},
     kube::{
         apis::v1_table::TableRow,
-        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow},
-        KubeClient,
+        informer::{ResourcePath, TableInformers},
+        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow, ResourceRows},
     },
     workers::kube::{
         WorkerResult, {PollerBase, Worker},
@@ -32,24 +32,28 @@ impl Worker for ConfigPoller {
     type Output = WorkerResult;
 
     async fn run(&self) -> Self::Output {
-        let mut interval = tokio::time::interval(time::Duration::from_secs(1));
-
         let Self {
             base:
                 PollerBase {
                     is_terminated,
                     tx,
                     shared_target_namespaces,
-                    kube_client,
+                    informers,
+                    polling,
+                    health,
+                    ..
                 },
         } = self;
 
+        let mut changes = informers.changes(polling.ticker(CONFIG_TAB_ID));
+
         while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
-            interval.tick().await;
+            changes.wait().await;
+            health.wait_backoff().await;
 
             let target_namespaces = shared_target_namespaces.read().await;
 
-            let table = fetch_configs(kube_client, &target_namespaces).await;
+            let table = fetch_configs(changes.informers(), &target_namespaces).await;
 
             tx.send(ConfigResponse::Table(table).into())
                 .expect("Failed to send ConfigResponse::Table");
@@ -82,46 +86,44 @@ impl Configs {
 }
 
 async fn fetch_configs_per_namespace(
-    client: &KubeClient,
+    informers: &TableInformers,
     namespaces: &[String],
     ty: Configs,
-) -> Result<Vec<KubeTableRow>> {
+) -> Result<ResourceRows> {
     let insert_ns = insert_ns(namespaces);
-    let jobs = try_join_all(namespaces.iter().map(|ns| {
-        get_resource_per_namespace(
-            client,
-            format!("api/v1/namespaces/{}/{}", ns, ty.kind()),
-            &["Name", r#"Data"#, "Age"],
-            move |row: &TableRow, indexes: &[usize]| {
-                let mut row = vec![
-                    ty.resource().to_string(),
-                    row.cells[indexes[0]].to_string(),
-                    row.cells[indexes[1]].to_string(),
-                    row.cells[indexes[2]].to_string(),
-                ];
-
-                let kind = row[0].clone();
-                let name = row[1].clone();
-
-                if insert_ns {
-                    row.insert(0, ns.to_string())
-                }
-
-                KubeTableRow {
-                    namespace: ns.to_string(),
-                    name,
-                    row,
-                    metadata: Some(BTreeMap::from([("kind".to_string(), kind)])),
-                }
-            },
-        )
-    }))
-    .await?;
 
-    Ok(jobs.into_iter().flatten().collect())
+    get_resource_per_namespace(
+        informers,
+        &ResourcePath::new("", "v1", ty.kind()),
+        namespaces,
+        &["Name", r#"Data"#, "Age"],
+        |ns: &str, row: &TableRow, indexes: &[usize]| {
+            let mut row = vec![
+                ty.resource().to_string(),
+                row.cells[indexes[0]].to_string(),
+                row.cells[indexes[1]].to_string(),
+                row.cells[indexes[2]].to_string(),
+            ];
+
+            let kind = row[0].clone();
+            let name = row[1].clone();
+
+            if insert_ns {
+                row.insert(0, ns.to_string())
+            }
+
+            KubeTableRow {
+                namespace: ns.to_string(),
+                name,
+                row,
+                metadata: Some(BTreeMap::from([("kind".to_string(), kind)])),
+            }
+        },
+    )
+    .await
 }
 
-async fn fetch_configs(client: &KubeClient, namespaces: &[String]) -> Result<KubeTable> {
+async fn fetch_configs(informers: &TableInformers, namespaces: &[String]) -> Result<KubeTable> {
     let mut table = KubeTable {
         header: if namespaces.len() == 1 {
             ["KIND", "NAME", "DATA", "AGE"]
@@ -138,12 +140,12 @@ async fn fetch_configs(client: &KubeClient, namespaces: &[String]) -> Result<Kub
     };
 
     let jobs = try_join_all([
-        fetch_configs_per_namespace(client, namespaces, Configs::ConfigMap),
-        fetch_configs_per_namespace(client, namespaces, Configs::Secret),
+        fetch_configs_per_namespace(informers, namespaces, Configs::ConfigMap),
+        fetch_configs_per_namespace(informers, namespaces, Configs::Secret),
     ])
     .await?;
 
-    table.update_rows(jobs.into_iter().flatten().collect());
+    table.update_resource_rows(jobs.into_iter().collect());
 
     Ok(table)
 }
@@ -8,8 +8,11 @@ use async_trait::async_trait;
 use crossbeam::channel::Sender;
 
 use crate::{
-    features::config::message::{ConfigData, ConfigRequest, ConfigResponse, RequestData},
-    kube::KubeClient,
+    features::{
+        component_id::CONFIG_TAB_ID,
+        config::message::{ConfigData, ConfigRequest, ConfigResponse, RequestData},
+    },
+    kube::{polling::Polling, KubeClient},
     message::Message,
     workers::kube::AbortWorker,
 };
@@ -22,6 +25,7 @@ pub struct ConfigsDataWorker {
     tx: Sender<Message>,
     client: KubeClient,
     req: ConfigRequest,
+    polling: Polling,
 }
 
 impl ConfigsDataWorker {
@@ -30,12 +34,14 @@ impl ConfigsDataWorker {
         tx: Sender<Message>,
         client: KubeClient,
         req: ConfigRequest,
+        polling: Polling,
     ) -> Self {
         Self {
             is_terminated,
             tx,
             client,
             req,
+            polling,
         }
     }
 }
@@ -63,14 +69,12 @@ trait Fetch<'a> {
     async fn fetch(&self) -> Result<ConfigData>;
 }
 
-const INTERVAL: u64 = 3;
-
 impl ConfigsDataWorker {
     async fn fetch_description<'a, Worker>(&'a self) -> Result<()>
     where
         Worker: Fetch<'a>,
     {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(INTERVAL));
+        let mut ticker = self.polling.description_ticker(CONFIG_TAB_ID);
 
         let RequestData { name, namespace } = self.req.data();
 
@@ -80,7 +84,7 @@ impl ConfigsDataWorker {
             .is_terminated
             .load(std::sync::atomic::Ordering::Relaxed)
         {
-            interval.tick().await;
+            ticker.tick().await;
 
             let fetched_data = worker.fetch().await;
 
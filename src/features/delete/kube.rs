@@ -0,0 +1,234 @@
+use anyhow::Result;
+use kube::api::DeleteParams;
+
+use crate::{
+    features::{
+        api_resources::kube::ApiResource, get::message::GetYamlKind,
+        yaml::kube::resource_json_to_yaml,
+    },
+    kube::KubeClientRequest,
+    logger,
+};
+
+use super::message::DeleteRequest;
+
+/// 依存リソース（Pod等）を持つリソースの種類
+const CASCADING_KINDS: [&str; 7] = [
+    "deployments",
+    "replicasets",
+    "statefulsets",
+    "daemonsets",
+    "jobs",
+    "cronjobs",
+    "replicationcontrollers",
+];
+
+/// 削除対象のリソース
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteTarget {
+    /// リソースの種類（例: pods）
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    /// リソースのURLパス
+    pub path: String,
+}
+
+impl DeleteTarget {
+    /// テーブルで選択したリソースから生成する
+    pub fn from_get_yaml_kind(kind: &GetYamlKind, namespace: &str, name: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            namespace: Some(namespace.to_string()),
+            name: name.to_string(),
+            path: format!("{}/{}", kind.url_path(namespace), name),
+        }
+    }
+
+    /// Yamlタブで選択したリソースから生成する
+    pub fn from_api_resource(api: &ApiResource, namespace: &str, name: &str) -> Self {
+        let (namespace, path) = if api.is_namespaced() {
+            (
+                Some(namespace.to_string()),
+                format!("{}/{}", api.api_url_with_namespace(namespace), name),
+            )
+        } else {
+            (None, format!("{}/{}", api.api_url(), name))
+        };
+
+        Self {
+            kind: api.name().to_string(),
+            namespace,
+            name: name.to_string(),
+            path,
+        }
+    }
+
+    /// 削除時に依存リソースの扱いを指定できるかどうか
+    pub fn is_cascading(&self) -> bool {
+        CASCADING_KINDS.contains(&self.kind.as_str())
+    }
+}
+
+impl std::fmt::Display for DeleteTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.namespace {
+            Some(ns) => write!(f, "{}/{} (namespace: {})", self.kind, self.name, ns),
+            None => write!(f, "{}/{}", self.kind, self.name),
+        }
+    }
+}
+
+pub async fn delete_resource<C: KubeClientRequest>(client: &C, req: &DeleteRequest) -> Result<()> {
+    let DeleteRequest {
+        target,
+        propagation_policy,
+    } = req;
+
+    logger!(
+        info,
+        "Deleting resource [{}] propagation_policy={:?}",
+        target,
+        propagation_policy
+    );
+
+    let params = DeleteParams {
+        propagation_policy: propagation_policy.clone(),
+        ..Default::default()
+    };
+
+    client.delete(&target.path, &params).await?;
+
+    Ok(())
+}
+
+/// 削除をサーバーサイドのdry-runで実行し、削除されるリソースをyamlで返す
+pub async fn dry_run_delete<C: KubeClientRequest>(
+    client: &C,
+    req: &DeleteRequest,
+) -> Result<String> {
+    let DeleteRequest {
+        target,
+        propagation_policy,
+    } = req;
+
+    let params = DeleteParams {
+        dry_run: true,
+        propagation_policy: propagation_policy.clone(),
+        ..Default::default()
+    };
+
+    let json = client.delete(&target.path, &params).await?;
+
+    resource_json_to_yaml(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{delete::message::PropagationPolicy, network::message::GatewayVersion},
+        kube::mock::MockTestKubeClient,
+    };
+    use anyhow::bail;
+    use kube::discovery::Scope;
+    use mockall::predicate::{always, eq};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn テーブルのリソースからパスを生成する() {
+        let actual = DeleteTarget::from_get_yaml_kind(
+            &GetYamlKind::Gateway(GatewayVersion::V1),
+            "default",
+            "gateway",
+        );
+
+        assert_eq!(
+            actual,
+            DeleteTarget {
+                kind: "gateways".to_string(),
+                namespace: Some("default".to_string()),
+                name: "gateway".to_string(),
+                path: "/apis/gateway.networking.k8s.io/v1/namespaces/default/gateways/gateway"
+                    .to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn namespaceに属さないリソースのパスにはnamespaceを含めない() {
+        let api = ApiResource::Api {
+            name: "persistentvolumes".to_string(),
+            version: "v1".to_string(),
+            scope: Scope::Cluster,
+        };
+
+        let actual = DeleteTarget::from_api_resource(&api, "default", "pv");
+
+        assert_eq!(actual.namespace, None);
+        assert_eq!(actual.path, "api/v1/persistentvolumes/pv");
+    }
+
+    #[test]
+    fn 依存リソースを持つ種類を判定する() {
+        let api = |name: &str| ApiResource::Apis {
+            name: name.to_string(),
+            group: "apps".to_string(),
+            version: "v1".to_string(),
+            preferred_version: true,
+            scope: Scope::Namespaced,
+        };
+
+        assert!(DeleteTarget::from_api_resource(&api("deployments"), "ns", "app").is_cascading());
+        assert!(!DeleteTarget::from_get_yaml_kind(&GetYamlKind::Pod, "ns", "app").is_cascading());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn 削除リクエストを送信する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_delete()
+            .with(eq("/api/v1/namespaces/default/pods/app-0"), always())
+            .returning(|_, params| {
+                if params.propagation_policy != Some(PropagationPolicy::Foreground) {
+                    bail!("unexpected params: {:?}", params);
+                }
+
+                Ok(String::default())
+            });
+
+        let req = DeleteRequest {
+            target: DeleteTarget::from_get_yaml_kind(&GetYamlKind::Pod, "default", "app-0"),
+            propagation_policy: Some(PropagationPolicy::Foreground),
+        };
+
+        assert!(delete_resource(&client, &req).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dry_runで削除されるリソースをyamlで返す() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_delete()
+            .with(eq("/api/v1/namespaces/default/pods/app-0"), always())
+            .returning(|_, params| {
+                if !params.dry_run {
+                    bail!("unexpected params: {:?}", params);
+                }
+
+                Ok(r#"{"kind":"Pod","metadata":{"name":"app-0","managedFields":[{"manager":"kubectl"}]}}"#.to_string())
+            });
+
+        let req = DeleteRequest {
+            target: DeleteTarget::from_get_yaml_kind(&GetYamlKind::Pod, "default", "app-0"),
+            propagation_policy: None,
+        };
+
+        assert_eq!(
+            dry_run_delete(&client, &req).await.unwrap(),
+            "kind: Pod\nmetadata:\n  name: app-0\n"
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use crate::{message::Message, workers::kube::message::Kube};
+
+pub use kube::api::PropagationPolicy;
+
+pub use super::kube::DeleteTarget;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteRequest {
+    pub target: DeleteTarget,
+    /// 依存リソースの削除方法（Noneの場合はAPIサーバーのデフォルト）
+    pub propagation_policy: Option<PropagationPolicy>,
+}
+
+#[derive(Debug)]
+pub struct DeleteResponse {
+    pub target: DeleteTarget,
+    pub result: Result<()>,
+}
+
+#[derive(Debug)]
+pub enum DeleteMessage {
+    Request(DeleteRequest),
+    /// 削除をサーバーサイドのdry-runで実行する
+    DryRun(DeleteRequest),
+    Response(DeleteResponse),
+    /// dry-runで削除されるリソース（yaml）
+    DryRunResponse(DeleteRequest, Result<String>),
+}
+
+impl From<DeleteMessage> for Message {
+    fn from(m: DeleteMessage) -> Self {
+        Self::Kube(Kube::Delete(m))
+    }
+}
+
+impl From<DeleteRequest> for Message {
+    fn from(req: DeleteRequest) -> Self {
+        DeleteMessage::Request(req).into()
+    }
+}
+
+impl From<DeleteResponse> for Message {
+    fn from(res: DeleteResponse) -> Self {
+        DeleteMessage::Response(res).into()
+    }
+}
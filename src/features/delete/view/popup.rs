@@ -0,0 +1,318 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::DELETE_POPUP_ID,
+        delete::message::{
+            DeleteMessage, DeleteRequest, DeleteResponse, DeleteTarget, PropagationPolicy,
+        },
+    },
+    logger,
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, Item, LiteralItem, Text, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+/// 確認待ちの削除リクエスト
+pub type SharedDeleteState = Rc<RefCell<Option<DeleteRequest>>>;
+
+pub struct DeletePopup {
+    pub popup: Widget<'static>,
+}
+
+impl DeletePopup {
+    pub fn new(tx: &Sender<Message>, state: SharedDeleteState) -> Self {
+        Self {
+            popup: popup(tx.clone(), state),
+        }
+    }
+}
+
+fn popup(tx: Sender<Message>, state: SharedDeleteState) -> Widget<'static> {
+    Text::builder()
+        .id(DELETE_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Delete").build())
+        .wrap()
+        .action('y', confirm(tx.clone(), state.clone()))
+        .action('p', toggle_propagation_policy(tx, state.clone()))
+        .action('n', cancel(state.clone()))
+        .action(UserEvent::from(KeyCode::Enter), cancel(state))
+        .build()
+        .into()
+}
+
+/// 削除の確認ポップアップを開く
+///
+/// 依存リソースを持つリソースはBackgroundで削除する。
+/// 削除をサーバーサイドのdry-runで実行し、削除されるリソースを [`show_delete_dry_run_result`] で表示する
+pub fn open_delete_popup(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    state: &SharedDeleteState,
+    target: DeleteTarget,
+) {
+    let propagation_policy = target
+        .is_cascading()
+        .then_some(PropagationPolicy::Background);
+
+    let req = DeleteRequest {
+        target,
+        propagation_policy,
+    };
+
+    request_dry_run(w, tx, state, req);
+
+    w.open_popup(DELETE_POPUP_ID);
+}
+
+fn request_dry_run(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    state: &SharedDeleteState,
+    req: DeleteRequest,
+) {
+    update_popup(
+        w,
+        confirm_lines(
+            &req,
+            vec!["\x1b[90mRunning server-side dry-run ...\x1b[39m".to_string()],
+        ),
+    );
+
+    *state.borrow_mut() = Some(req.clone());
+
+    tx.send(DeleteMessage::DryRun(req).into())
+        .expect("Failed to send DeleteMessage::DryRun");
+}
+
+/// dry-runで削除されるリソースを確認ポップアップに表示する
+///
+/// 確認待ちの削除リクエストが変わっていた場合は何もしない
+pub fn show_delete_dry_run_result(
+    w: &mut Window,
+    state: &SharedDeleteState,
+    req: DeleteRequest,
+    result: Result<String>,
+) {
+    if state.borrow().as_ref() != Some(&req) {
+        return;
+    }
+
+    let body = match result {
+        Ok(yaml) => ["The following object will be removed:".to_string()]
+            .into_iter()
+            .chain(yaml.lines().map(|line| format!("  {}", line)))
+            .collect(),
+        Err(err) => {
+            logger!(
+                error,
+                "Dry-run failed for deleting {}: {:?}",
+                req.target,
+                err
+            );
+
+            ["Dry-run failed:".to_string()]
+                .into_iter()
+                .chain(format!("{:?}", err).lines().map(String::from))
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    update_popup(w, confirm_lines(&req, body));
+}
+
+/// 削除結果をポップアップに表示する
+pub fn show_delete_result(w: &mut Window, res: DeleteResponse) {
+    let DeleteResponse { target, result } = res;
+
+    let lines = match result {
+        Ok(_) => vec![format!("Deleted {}", target)],
+        Err(err) => {
+            logger!(error, "Failed to delete {}: {:?}", target, err);
+
+            std::iter::once(format!("Failed to delete {}", target))
+                .chain(format!("{:?}", err).lines().map(ToString::to_string))
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    let lines = lines
+        .into_iter()
+        .chain([String::default(), close_hint()])
+        .collect();
+
+    update_popup(w, lines);
+
+    w.open_popup(DELETE_POPUP_ID);
+}
+
+fn update_popup(w: &mut Window, lines: Vec<String>) {
+    let items = lines
+        .into_iter()
+        .map(|line| LiteralItem::new(line, None))
+        .collect();
+
+    w.find_widget_mut(DELETE_POPUP_ID)
+        .update_widget_item(Item::Array(items));
+}
+
+fn confirm_lines(req: &DeleteRequest, body: Vec<String>) -> Vec<String> {
+    let DeleteRequest {
+        target,
+        propagation_policy,
+    } = req;
+
+    let mut lines = vec![
+        "Delete the following resource?".to_string(),
+        String::default(),
+        format!("  Kind:      {}", target.kind),
+    ];
+
+    if let Some(ns) = &target.namespace {
+        lines.push(format!("  Namespace: {}", ns));
+    }
+
+    lines.push(format!("  Name:      {}", target.name));
+
+    if let Some(policy) = propagation_policy {
+        lines.push(format!(
+            "  Propagation policy: {} (press \x1b[1mp\x1b[0m to change)",
+            propagation_policy_name(policy)
+        ));
+    }
+
+    lines.push(String::default());
+
+    lines.extend(body);
+
+    lines.extend([
+        String::default(),
+        "Press \x1b[1my\x1b[0m to delete, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to cancel."
+            .to_string(),
+    ]);
+
+    lines
+}
+
+fn close_hint() -> String {
+    "Press \x1b[1mEnter\x1b[0m or \x1b[1mEsc\x1b[0m to close.".to_string()
+}
+
+fn propagation_policy_name(policy: &PropagationPolicy) -> &'static str {
+    match policy {
+        PropagationPolicy::Background => "Background",
+        PropagationPolicy::Foreground => "Foreground",
+        PropagationPolicy::Orphan => "Orphan",
+    }
+}
+
+fn next_propagation_policy(policy: &PropagationPolicy) -> PropagationPolicy {
+    match policy {
+        PropagationPolicy::Background => PropagationPolicy::Foreground,
+        PropagationPolicy::Foreground => PropagationPolicy::Orphan,
+        PropagationPolicy::Orphan => PropagationPolicy::Background,
+    }
+}
+
+fn confirm(tx: Sender<Message>, state: SharedDeleteState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(req) = state.borrow_mut().take() else {
+            return EventResult::Nop;
+        };
+
+        update_popup(w, vec![format!("Deleting {} ...", req.target)]);
+
+        tx.send(req.into())
+            .expect("Failed to send DeleteMessage::Request");
+
+        EventResult::Nop
+    }
+}
+
+/// 削除方法を切り替え、切り替えた削除方法でdry-runをやり直す
+fn toggle_propagation_policy(
+    tx: Sender<Message>,
+    state: SharedDeleteState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(mut req) = state.borrow().clone() else {
+            return EventResult::Nop;
+        };
+
+        let Some(policy) = &req.propagation_policy else {
+            return EventResult::Nop;
+        };
+
+        req.propagation_policy = Some(next_propagation_policy(policy));
+
+        request_dry_run(w, &tx, &state, req);
+
+        EventResult::Nop
+    }
+}
+
+fn cancel(state: SharedDeleteState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        state.borrow_mut().take();
+
+        w.close_popup();
+
+        EventResult::Nop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 依存リソースを持つリソースは削除方法を表示する() {
+        let req = DeleteRequest {
+            target: DeleteTarget {
+                kind: "deployments".to_string(),
+                namespace: Some("default".to_string()),
+                name: "app".to_string(),
+                path: "apis/apps/v1/namespaces/default/deployments/app".to_string(),
+            },
+            propagation_policy: Some(PropagationPolicy::Background),
+        };
+
+        assert_eq!(
+            confirm_lines(&req, Vec::new())[2..6],
+            [
+                "  Kind:      deployments",
+                "  Namespace: default",
+                "  Name:      app",
+                "  Propagation policy: Background (press \x1b[1mp\x1b[0m to change)",
+            ]
+        );
+    }
+
+    #[test]
+    fn namespaceに属さないリソースはnamespaceを表示しない() {
+        let req = DeleteRequest {
+            target: DeleteTarget {
+                kind: "persistentvolumes".to_string(),
+                namespace: None,
+                name: "pv".to_string(),
+                path: "api/v1/persistentvolumes/pv".to_string(),
+            },
+            propagation_policy: None,
+        };
+
+        assert_eq!(
+            confirm_lines(&req, Vec::new())[2..5],
+            ["  Kind:      persistentvolumes", "  Name:      pv", ""]
+        );
+    }
+}
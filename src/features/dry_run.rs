@@ -0,0 +1,2 @@
+pub mod kube;
+pub mod view;
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::features::yaml::kube::resource_json_to_yaml;
+
+/// サーバーサイドのdry-run（`dryRun=All`）で得た変更前後のリソース
+///
+/// どちらもmanagedFieldsを除いたyaml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunDiff {
+    /// 現在のリソース
+    pub live: String,
+    /// dry-runの結果のリソース
+    pub dry_run: String,
+}
+
+impl DryRunDiff {
+    /// 現在のリソースとdry-runの結果のjsonから生成する
+    pub fn from_json(live: &str, dry_run: &str) -> Result<Self> {
+        Ok(Self {
+            live: resource_json_to_yaml(live)?,
+            dry_run: resource_json_to_yaml(dry_run)?,
+        })
+    }
+
+    /// 現在のリソースとdry-runの結果から生成する
+    pub fn from_resources<K: Serialize>(live: &K, dry_run: &K) -> Result<Self> {
+        Self::from_json(
+            &serde_json::to_string(live)?,
+            &serde_json::to_string(dry_run)?,
+        )
+    }
+
+    /// dry-runで新規作成したリソースから生成する
+    pub fn from_created<K: Serialize>(dry_run: &K) -> Result<Self> {
+        Ok(Self {
+            live: String::default(),
+            dry_run: resource_json_to_yaml(&serde_json::to_string(dry_run)?)?,
+        })
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.live != self.dry_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn managed_fieldsを除いて比較する() {
+        let actual = DryRunDiff::from_json(
+            r#"{"metadata":{"name":"app","managedFields":[{"manager":"kubectl"}]},"spec":{"replicas":1}}"#,
+            r#"{"metadata":{"name":"app","managedFields":[{"manager":"kubetui"}]},"spec":{"replicas":1}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual.live,
+            indoc! {"
+                metadata:
+                  name: app
+                spec:
+                  replicas: 1
+            "}
+        );
+        assert!(!actual.is_changed());
+    }
+}
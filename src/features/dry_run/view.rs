@@ -0,0 +1,3 @@
+mod diff;
+
+pub use diff::diff_lines;
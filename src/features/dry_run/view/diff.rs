@@ -0,0 +1,247 @@
+use std::ops::Range;
+
+use crate::ansi::highlight;
+
+/// 変更行の前後に表示する行数
+const CONTEXT_LINES: usize = 3;
+
+/// 行内で変更された部分のSGR（反転）
+const CHANGED_SGR: &str = "7";
+
+/// 行単位の差分を生成する
+///
+/// 削除行は赤、追加行は緑で表示し、変更から離れた行は `...` にまとめる。
+/// 削除行と追加行が同じ数だけ続く場合は、対応する行の変更された部分を反転表示する
+pub fn diff_lines(original: &str, modified: &str) -> Vec<String> {
+    let original: Vec<&str> = original.lines().collect();
+    let modified: Vec<&str> = modified.lines().collect();
+
+    let diff = diff::slice(&original, &modified);
+
+    let is_changed: Vec<bool> = diff
+        .iter()
+        .map(|line| !matches!(line, diff::Result::Both(..)))
+        .collect();
+
+    let mut lines = Vec::new();
+    let mut last_index: Option<usize> = None;
+
+    for (index, line) in render(&diff).into_iter().enumerate() {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES).min(diff.len() - 1);
+
+        if !is_changed[start..=end].contains(&true) {
+            continue;
+        }
+
+        let is_skipped = match last_index {
+            Some(last_index) => last_index + 1 != index,
+            None => index != 0,
+        };
+
+        if is_skipped {
+            lines.push(omitted());
+        }
+
+        lines.push(line);
+
+        last_index = Some(index);
+    }
+
+    if last_index.is_some_and(|last_index| last_index + 1 != diff.len()) {
+        lines.push(omitted());
+    }
+
+    lines
+}
+
+/// 差分の各行を色付きの文字列にする
+fn render(diff: &[diff::Result<&&str>]) -> Vec<String> {
+    let mut rendered = Vec::with_capacity(diff.len());
+
+    let mut index = 0;
+
+    while index < diff.len() {
+        let removed: Vec<&str> = diff[index..]
+            .iter()
+            .map_while(|line| match line {
+                diff::Result::Left(line) => Some(**line),
+                _ => None,
+            })
+            .collect();
+
+        let added: Vec<&str> = diff[index + removed.len()..]
+            .iter()
+            .map_while(|line| match line {
+                diff::Result::Right(line) => Some(**line),
+                _ => None,
+            })
+            .collect();
+
+        if removed.is_empty() && added.is_empty() {
+            if let diff::Result::Both(line, _) = diff[index] {
+                rendered.push(format!(" {}", line));
+            }
+
+            index += 1;
+
+            continue;
+        }
+
+        let ranges: Vec<Option<(Range<usize>, Range<usize>)>> = if removed.len() == added.len() {
+            removed
+                .iter()
+                .zip(&added)
+                .map(|(removed, added)| changed_ranges(removed, added))
+                .collect()
+        } else {
+            vec![None; removed.len().max(added.len())]
+        };
+
+        rendered.extend(
+            removed
+                .iter()
+                .enumerate()
+                .map(|(i, line)| colorize('-', "31", line, ranges[i].as_ref().map(|(r, _)| r))),
+        );
+
+        rendered.extend(
+            added
+                .iter()
+                .enumerate()
+                .map(|(i, line)| colorize('+', "32", line, ranges[i].as_ref().map(|(_, r)| r))),
+        );
+
+        index += removed.len() + added.len();
+    }
+
+    rendered
+}
+
+fn colorize(sign: char, sgr: &str, line: &str, changed: Option<&Range<usize>>) -> String {
+    let line = format!("\x1b[{}m{}{}\x1b[39m", sgr, sign, line);
+
+    match changed {
+        // 先頭の記号の分だけずらす
+        Some(range) => highlight(&line, &[(range.start + 1..range.end + 1, CHANGED_SGR)]),
+        None => line,
+    }
+}
+
+/// 変更前後の行で共通する先頭と末尾を除いた範囲を返す
+///
+/// 共通する部分がなければ行全体が変更されているため `None` を返す
+fn changed_ranges(removed: &str, added: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let prefix: usize = removed
+        .chars()
+        .zip(added.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+
+    let suffix: usize = removed[prefix..]
+        .chars()
+        .rev()
+        .zip(added[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+
+    if prefix == 0 && suffix == 0 {
+        return None;
+    }
+
+    Some((prefix..removed.len() - suffix, prefix..added.len() - suffix))
+}
+
+fn omitted() -> String {
+    "\x1b[90m...\x1b[39m".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 変更の前後の行だけを表示する() {
+        let original = indoc! {"
+            a
+            b
+            c
+            d
+            e
+            f
+            g
+            h
+            i
+        "};
+
+        let edited = indoc! {"
+            a
+            b
+            c
+            d
+            E
+            f
+            g
+            h
+            i
+        "};
+
+        assert_eq!(
+            diff_lines(original, edited),
+            vec![
+                "\x1b[90m...\x1b[39m",
+                " b",
+                " c",
+                " d",
+                "\x1b[31m-e\x1b[39m",
+                "\x1b[32m+E\x1b[39m",
+                " f",
+                " g",
+                " h",
+                "\x1b[90m...\x1b[39m",
+            ]
+        );
+    }
+
+    #[test]
+    fn 先頭と末尾の変更は省略記号を付けない() {
+        assert_eq!(
+            diff_lines("a\nb\n", "A\nb\nc\n"),
+            vec![
+                "\x1b[31m-a\x1b[39m",
+                "\x1b[32m+A\x1b[39m",
+                " b",
+                "\x1b[32m+c\x1b[39m",
+            ]
+        );
+    }
+
+    #[test]
+    fn 対応する行の変更された部分を反転表示する() {
+        assert_eq!(
+            diff_lines("replicas: 1\nname: app\n", "replicas: 3\nname: app\n"),
+            vec![
+                "\x1b[31m-replicas: \x1b[7m1\x1b[0m\x1b[31m\x1b[39m",
+                "\x1b[32m+replicas: \x1b[7m3\x1b[0m\x1b[32m\x1b[39m",
+                " name: app",
+            ]
+        );
+    }
+
+    #[test]
+    fn 追加のみの部分は反転表示しない() {
+        assert_eq!(
+            diff_lines("a: 1\n", "a: 1\nb: 2\nc: 3\n"),
+            vec![" a: 1", "\x1b[32m+b: 2\x1b[39m", "\x1b[32m+c: 3\x1b[39m"]
+        );
+    }
+
+    #[test]
+    fn 変更がなければ空になる() {
+        assert!(diff_lines("a\nb\n", "a\nb\n").is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+use anyhow::{anyhow, Context as _, Result};
+use kube::api::PostParams;
+
+use crate::{
+    features::{
+        api_resources::kube::ApiResource, dry_run::kube::DryRunDiff, get::message::GetYamlKind,
+        yaml::kube::resource_json_to_yaml,
+    },
+    kube::KubeClientRequest,
+    logger,
+};
+
+use super::message::{ApplyError, EditSession};
+
+const FIELD_MANAGER: &str = "kubetui";
+
+/// 編集対象のリソース
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditTarget {
+    /// リソースの種類（例: pods）
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    /// リソースのURLパス
+    pub path: String,
+}
+
+impl EditTarget {
+    /// yamlポップアップで表示しているリソースから生成する
+    pub fn from_get_yaml_kind(kind: &GetYamlKind, namespace: &str, name: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            namespace: Some(namespace.to_string()),
+            name: name.to_string(),
+            path: format!("{}/{}", kind.url_path(namespace), name),
+        }
+    }
+
+    /// Yamlタブで選択したリソースから生成する
+    pub fn from_api_resource(api: &ApiResource, namespace: &str, name: &str) -> Self {
+        let (namespace, path) = if api.is_namespaced() {
+            (
+                Some(namespace.to_string()),
+                format!("{}/{}", api.api_url_with_namespace(namespace), name),
+            )
+        } else {
+            (None, format!("{}/{}", api.api_url(), name))
+        };
+
+        Self {
+            kind: api.name().to_string(),
+            namespace,
+            name: name.to_string(),
+            path,
+        }
+    }
+}
+
+impl std::fmt::Display for EditTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.namespace {
+            Some(ns) => write!(f, "{}/{} (namespace: {})", self.kind, self.name, ns),
+            None => write!(f, "{}/{}", self.kind, self.name),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApplyError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast_ref::<kube::Error>() {
+            Some(kube::Error::Api(res)) if res.code == 409 => Self::Conflict(res.message.clone()),
+            Some(kube::Error::Api(res)) => {
+                Self::Other(anyhow!("{} ({}): {}", res.reason, res.code, res.message))
+            }
+            _ => Self::Other(err),
+        }
+    }
+}
+
+/// 編集するリソースをmanagedFieldsを除いたyamlで取得する
+pub async fn fetch_edit_yaml<C: KubeClientRequest>(
+    client: &C,
+    target: &EditTarget,
+) -> Result<String> {
+    logger!(info, "Fetching resource for edit [{}]", target);
+
+    let json = client.request_text(&target.path).await?;
+
+    resource_json_to_yaml(&json)
+}
+
+/// 編集したリソースをサーバーサイドのdry-runで置き換え、取得時のリソースとの差分を返す
+pub async fn dry_run_edit<C: KubeClientRequest>(
+    client: &C,
+    session: &EditSession,
+) -> Result<DryRunDiff, ApplyError> {
+    logger!(info, "Dry-run edited resource [{}]", session.target);
+
+    let json = replace(client, session, true).await?;
+
+    Ok(DryRunDiff {
+        live: session.original.clone(),
+        dry_run: resource_json_to_yaml(&json)?,
+    })
+}
+
+/// 編集したリソースをPUTで置き換える
+///
+/// metadata.resourceVersionが取得時のままのため、他の更新と競合した場合は409が返る
+pub async fn apply_edit<C: KubeClientRequest>(
+    client: &C,
+    session: &EditSession,
+) -> Result<(), ApplyError> {
+    logger!(info, "Applying edited resource [{}]", session.target);
+
+    replace(client, session, false).await?;
+
+    Ok(())
+}
+
+async fn replace<C: KubeClientRequest>(
+    client: &C,
+    session: &EditSession,
+    dry_run: bool,
+) -> Result<String, ApplyError> {
+    let value: serde_json::Value =
+        serde_yaml::from_str(&session.edited).context("Failed to parse the edited yaml")?;
+
+    let data = serde_json::to_vec(&value).map_err(anyhow::Error::from)?;
+
+    let params = PostParams {
+        dry_run,
+        field_manager: Some(FIELD_MANAGER.to_string()),
+    };
+
+    client
+        .replace(&session.target.path, &params, data)
+        .await
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kube::mock::MockTestKubeClient;
+    use indoc::indoc;
+    use kube::core::ErrorResponse;
+    use mockall::predicate::{always, eq};
+    use pretty_assertions::assert_eq;
+
+    fn target() -> EditTarget {
+        EditTarget::from_get_yaml_kind(&GetYamlKind::ConfigMap, "default", "app")
+    }
+
+    fn session(edited: &str) -> EditSession {
+        EditSession {
+            target: target(),
+            original: String::default(),
+            edited: edited.to_string(),
+        }
+    }
+
+    fn api_error(code: u16, reason: &str, message: &str) -> anyhow::Error {
+        kube::Error::Api(ErrorResponse {
+            status: "Failure".to_string(),
+            message: message.to_string(),
+            reason: reason.to_string(),
+            code,
+        })
+        .into()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn managed_fieldsを除いたyamlを取得する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_request_text()
+            .with(eq("/api/v1/namespaces/default/configmaps/app"))
+            .returning(|_| {
+                Ok(r#"{"kind":"ConfigMap","metadata":{"name":"app","managedFields":[{}]},"data":{"key":"value"}}"#.to_string())
+            });
+
+        let actual = fetch_edit_yaml(&client, &target()).await.unwrap();
+
+        assert_eq!(
+            actual,
+            indoc! {"
+                kind: ConfigMap
+                metadata:
+                  name: app
+                data:
+                  key: value
+            "}
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn 編集したyamlをjsonで送信する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_replace()
+            .with(
+                eq("/api/v1/namespaces/default/configmaps/app"),
+                always(),
+                always(),
+            )
+            .returning(|_, params, data| {
+                assert_eq!(params.field_manager.as_deref(), Some("kubetui"));
+                assert!(!params.dry_run);
+                assert_eq!(
+                    serde_json::from_slice::<serde_json::Value>(&data).unwrap(),
+                    serde_json::json!({"kind": "ConfigMap", "data": {"key": "edited"}})
+                );
+
+                Ok(String::default())
+            });
+
+        let result = apply_edit(&client, &session("kind: ConfigMap\ndata:\n  key: edited\n")).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dry_runの結果と取得時のyamlを比較する() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_replace()
+            .withf(|_, params, _| params.dry_run)
+            .returning(|_, _, _| {
+                Ok(r#"{"kind":"ConfigMap","metadata":{"managedFields":[{}]},"data":{"key":"edited"}}"#.to_string())
+            });
+
+        let session = EditSession {
+            original: "kind: ConfigMap\ndata:\n  key: value\n".to_string(),
+            ..session("kind: ConfigMap\ndata:\n  key: edited\n")
+        };
+
+        let actual = dry_run_edit(&client, &session).await.unwrap();
+
+        assert_eq!(
+            actual,
+            DryRunDiff {
+                live: "kind: ConfigMap\ndata:\n  key: value\n".to_string(),
+                dry_run: indoc! {"
+                    kind: ConfigMap
+                    metadata: {}
+                    data:
+                      key: edited
+                "}
+                .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn 競合した場合はconflictを返す() {
+        let mut client = MockTestKubeClient::new();
+
+        client
+            .expect_replace()
+            .returning(|_, _, _| Err(api_error(409, "Conflict", "the object has been modified")));
+
+        let result = apply_edit(&client, &session("kind: ConfigMap\n")).await;
+
+        assert!(
+            matches!(result, Err(ApplyError::Conflict(ref message)) if message == "the object has been modified")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn 不正なyamlは送信しない() {
+        let mut client = MockTestKubeClient::new();
+
+        client.expect_replace().never();
+
+        let result = apply_edit(&client, &session("kind: [ConfigMap\n")).await;
+
+        assert!(matches!(result, Err(ApplyError::Other(_))));
+    }
+
+    #[test]
+    fn apiのエラーを理由とメッセージで表示する() {
+        let ApplyError::Other(err) =
+            ApplyError::from(api_error(422, "Invalid", "spec.replicas: Invalid value"))
+        else {
+            panic!("unexpected conflict");
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid (422): spec.replicas: Invalid value"
+        );
+    }
+}
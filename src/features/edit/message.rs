@@ -0,0 +1,90 @@
+use anyhow::Result;
+
+use crate::{
+    features::dry_run::kube::DryRunDiff, message::Message, ui::WindowAction,
+    workers::kube::message::Kube,
+};
+
+pub use super::kube::EditTarget;
+
+/// エディタで編集中のリソース
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditSession {
+    pub target: EditTarget,
+    /// 取得したyaml
+    pub original: String,
+    /// 編集後のyaml
+    pub edited: String,
+}
+
+/// 画面を一時停止してリソースをエディタで編集する
+impl From<EditSession> for WindowAction {
+    fn from(session: EditSession) -> WindowAction {
+        WindowAction::Suspend(Box::new(session))
+    }
+}
+
+impl EditSession {
+    pub fn new(target: EditTarget, yaml: String) -> Self {
+        Self {
+            target,
+            original: yaml.clone(),
+            edited: yaml,
+        }
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.original != self.edited
+    }
+}
+
+#[derive(Debug)]
+pub enum ApplyError {
+    /// 取得してから適用するまでの間にリソースが更新された（409 Conflict）
+    Conflict(String),
+    Other(anyhow::Error),
+}
+
+#[derive(Debug)]
+pub enum EditRequest {
+    /// 編集するリソースを取得する
+    Fetch(EditTarget),
+    /// 編集したリソースをサーバーサイドのdry-runで検証する
+    DryRun(EditSession),
+    /// 編集したリソースを適用する
+    Apply(EditSession),
+}
+
+#[derive(Debug)]
+pub enum EditResponse {
+    Fetched {
+        target: EditTarget,
+        yaml: Result<String>,
+    },
+    DryRun {
+        session: EditSession,
+        result: Result<DryRunDiff, ApplyError>,
+    },
+    Applied {
+        session: EditSession,
+        result: Result<(), ApplyError>,
+    },
+}
+
+#[derive(Debug)]
+pub enum EditMessage {
+    Request(EditRequest),
+    Response(EditResponse),
+}
+
+impl From<EditRequest> for Message {
+    fn from(req: EditRequest) -> Self {
+        Self::Kube(Kube::Edit(EditMessage::Request(req)))
+    }
+}
+
+impl From<EditResponse> for Message {
+    fn from(res: EditResponse) -> Self {
+        Self::Kube(Kube::Edit(EditMessage::Response(res)))
+    }
+}
@@ -0,0 +1,342 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::EDIT_POPUP_ID,
+        dry_run::{kube::DryRunDiff, view::diff_lines},
+        edit::message::{ApplyError, EditRequest, EditSession, EditTarget},
+    },
+    logger,
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, Item, LiteralItem, Text, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+/// 編集の状態
+#[derive(Debug, Default)]
+pub enum EditState {
+    #[default]
+    Idle,
+    /// サーバーサイドのdry-runの結果を待っている
+    DryRun,
+    /// 差分を表示して適用の確認を待っている
+    Confirm(EditSession),
+    /// 適用に失敗した
+    Failed(EditSession),
+}
+
+pub type SharedEditState = Rc<RefCell<EditState>>;
+
+pub struct EditPopup {
+    pub popup: Widget<'static>,
+}
+
+impl EditPopup {
+    pub fn new(tx: &Sender<Message>, state: SharedEditState) -> Self {
+        Self {
+            popup: popup(tx.clone(), state),
+        }
+    }
+}
+
+fn popup(tx: Sender<Message>, state: SharedEditState) -> Widget<'static> {
+    Text::builder()
+        .id(EDIT_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Edit").build())
+        .wrap()
+        .action('y', apply(tx.clone(), state.clone()))
+        .action('e', edit_again(state.clone()))
+        .action('r', reload(tx, state.clone()))
+        .action('n', cancel(state.clone()))
+        .action(UserEvent::from(KeyCode::Enter), cancel(state))
+        .build()
+        .into()
+}
+
+/// 編集するリソースを取得する
+///
+/// 取得後にエディタが開く
+pub fn request_edit(w: &mut Window, tx: &Sender<Message>, target: EditTarget) {
+    update_popup(w, &target, vec![format!("Fetching {} ...", target)]);
+
+    tx.send(EditRequest::Fetch(target).into())
+        .expect("Failed to send EditRequest::Fetch");
+
+    w.open_popup(EDIT_POPUP_ID);
+}
+
+/// エディタで編集した結果をサーバーサイドのdry-runで検証する
+///
+/// 結果は [`show_dry_run_result`] で差分として表示する
+pub fn request_dry_run(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    state: &SharedEditState,
+    session: EditSession,
+) {
+    let target = session.target.clone();
+
+    if session.edited.trim().is_empty() {
+        cancel_edit(w, state, &target, "Edit cancelled, the file is empty.");
+        return;
+    }
+
+    if !session.is_changed() {
+        cancel_edit(w, state, &target, "Edit cancelled, no changes made.");
+        return;
+    }
+
+    update_popup(
+        w,
+        &target,
+        vec![format!("Running server-side dry-run for {} ...", target)],
+    );
+
+    *state.borrow_mut() = EditState::DryRun;
+
+    tx.send(EditRequest::DryRun(session).into())
+        .expect("Failed to send EditRequest::DryRun");
+
+    w.open_popup(EDIT_POPUP_ID);
+}
+
+/// 現在のリソースとdry-runの結果の差分を表示し、適用の確認を待つ
+///
+/// dry-runの結果を待つ間にキャンセルされていた場合は何もしない
+pub fn show_dry_run_result(
+    w: &mut Window,
+    state: &SharedEditState,
+    session: EditSession,
+    result: Result<DryRunDiff, ApplyError>,
+) {
+    if !matches!(*state.borrow(), EditState::DryRun) {
+        return;
+    }
+
+    let target = session.target.clone();
+
+    let lines = match result {
+        Ok(diff) if !diff.is_changed() => {
+            *state.borrow_mut() = EditState::Idle;
+
+            vec![
+                "The server reports no changes to apply.".to_string(),
+                String::default(),
+                close_hint(),
+            ]
+        }
+        Ok(diff) => {
+            *state.borrow_mut() = EditState::Confirm(session);
+
+            [
+                "\x1b[90mDiff between the live object and the server-side dry-run result\x1b[39m"
+                    .to_string(),
+                String::default(),
+            ]
+            .into_iter()
+            .chain(diff_lines(&diff.live, &diff.dry_run))
+            .chain([
+                String::default(),
+                "Press \x1b[1my\x1b[0m to apply, \x1b[1me\x1b[0m to edit again, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to cancel."
+                    .to_string(),
+            ])
+            .collect()
+        }
+        Err(err) => {
+            logger!(error, "Dry-run failed for {}: {:?}", target, err);
+
+            failed_lines(state, session, err)
+        }
+    };
+
+    update_popup(w, &target, lines);
+
+    w.open_popup(EDIT_POPUP_ID);
+}
+
+/// 適用の結果を表示する
+///
+/// 失敗した場合は編集内容を保持し、再編集できるようにする
+pub fn show_edit_result(
+    w: &mut Window,
+    state: &SharedEditState,
+    session: EditSession,
+    result: Result<(), ApplyError>,
+) {
+    let target = session.target.clone();
+
+    let lines = match result {
+        Ok(()) => {
+            logger!(info, "Applied {}", target);
+
+            *state.borrow_mut() = EditState::Idle;
+
+            vec![
+                format!("Applied {}", target),
+                String::default(),
+                close_hint(),
+            ]
+        }
+        Err(err) => {
+            logger!(error, "Failed to apply {}: {:?}", target, err);
+
+            failed_lines(state, session, err)
+        }
+    };
+
+    update_popup(w, &target, lines);
+
+    w.open_popup(EDIT_POPUP_ID);
+}
+
+/// リソースの取得やエディタの起動に失敗した場合のエラーを表示する
+pub fn show_edit_error(
+    w: &mut Window,
+    state: &SharedEditState,
+    target: &EditTarget,
+    err: anyhow::Error,
+) {
+    logger!(error, "Failed to edit {}: {:?}", target, err);
+
+    *state.borrow_mut() = EditState::Idle;
+
+    let lines = error_lines(&err)
+        .into_iter()
+        .chain([String::default(), close_hint()])
+        .collect();
+
+    update_popup(w, target, lines);
+
+    w.open_popup(EDIT_POPUP_ID);
+}
+
+fn cancel_edit(w: &mut Window, state: &SharedEditState, target: &EditTarget, message: &str) {
+    *state.borrow_mut() = EditState::Idle;
+
+    update_popup(
+        w,
+        target,
+        vec![message.to_string(), String::default(), close_hint()],
+    );
+
+    w.open_popup(EDIT_POPUP_ID);
+}
+
+/// dry-runや適用に失敗した場合に表示する行
+///
+/// 編集内容を保持し、競合した場合は最新のリソースを再取得できるようにする
+fn failed_lines(state: &SharedEditState, session: EditSession, err: ApplyError) -> Vec<String> {
+    *state.borrow_mut() = EditState::Failed(session);
+
+    match err {
+        ApplyError::Conflict(message) => vec![
+            format!("\x1b[31mConflict: {}\x1b[39m", message),
+            String::default(),
+            "The resource was modified after it was fetched.".to_string(),
+            "Press \x1b[1mr\x1b[0m to discard your changes and edit the latest version, \x1b[1me\x1b[0m to edit again, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to close."
+                .to_string(),
+        ],
+        ApplyError::Other(err) => error_lines(&err)
+            .into_iter()
+            .chain([
+                String::default(),
+                "Press \x1b[1me\x1b[0m to edit again, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to close."
+                    .to_string(),
+            ])
+            .collect(),
+    }
+}
+
+fn update_popup(w: &mut Window, target: &EditTarget, lines: Vec<String>) {
+    let items = lines
+        .into_iter()
+        .map(|line| LiteralItem::new(line, None))
+        .collect();
+
+    let widget = w.find_widget_mut(EDIT_POPUP_ID);
+
+    *widget.widget_config_mut().append_title_mut() = Some(format!(" : {}", target).into());
+
+    widget.update_widget_item(Item::Array(items));
+}
+
+fn error_lines(err: &anyhow::Error) -> Vec<String> {
+    format!("{:?}", err)
+        .lines()
+        .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+        .collect()
+}
+
+fn close_hint() -> String {
+    "Press \x1b[1mEnter\x1b[0m or \x1b[1mEsc\x1b[0m to close.".to_string()
+}
+
+fn apply(tx: Sender<Message>, state: SharedEditState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let current = std::mem::take(&mut *state.borrow_mut());
+
+        let session = match current {
+            EditState::Confirm(session) => session,
+            other => {
+                *state.borrow_mut() = other;
+                return EventResult::Nop;
+            }
+        };
+
+        update_popup(
+            w,
+            &session.target,
+            vec![format!("Applying {} ...", session.target)],
+        );
+
+        tx.send(EditRequest::Apply(session).into())
+            .expect("Failed to send EditRequest::Apply");
+
+        EventResult::Nop
+    }
+}
+
+/// 編集内容を保持したままエディタを開き直す
+fn edit_again(state: SharedEditState) -> impl Fn(&mut Window) -> EventResult {
+    move |_: &mut Window| match &*state.borrow() {
+        EditState::Confirm(session) | EditState::Failed(session) => {
+            EventResult::WindowAction(session.clone().into())
+        }
+        EditState::Idle | EditState::DryRun => EventResult::Nop,
+    }
+}
+
+/// 編集内容を破棄して最新のリソースを編集する
+fn reload(tx: Sender<Message>, state: SharedEditState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let current = std::mem::take(&mut *state.borrow_mut());
+
+        let session = match current {
+            EditState::Failed(session) => session,
+            other => {
+                *state.borrow_mut() = other;
+                return EventResult::Nop;
+            }
+        };
+
+        request_edit(w, &tx, session.target);
+
+        EventResult::Nop
+    }
+}
+
+fn cancel(state: SharedEditState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        *state.borrow_mut() = EditState::Idle;
+
+        w.close_popup();
+
+        EventResult::Nop
+    }
+}
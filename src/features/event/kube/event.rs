@@ -1,14 +1,14 @@
-use std::{sync::atomic::Ordering, time};
+use std::sync::atomic::Ordering;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use futures::future::try_join_all;
 
 use crate::{
+    features::component_id::EVENT_TAB_ID,
     kube::{
         apis::v1_table::{TableRow, ToTime as _},
+        informer::{ResourcePath, TableInformers},
         table::{get_resource_per_namespace, insert_ns, KubeTableRow},
-        KubeClient,
     },
     message::Message,
     workers::kube::{message::Kube, PollerBase, Worker, WorkerResult},
@@ -35,16 +35,21 @@ impl Worker for EventPoller {
                     is_terminated,
                     tx,
                     shared_target_namespaces,
-                    kube_client,
+                    informers,
+                    polling,
+                    health,
+                    ..
                 },
         } = self;
 
-        let mut interval = tokio::time::interval(time::Duration::from_millis(1000));
+        let mut changes = informers.changes(polling.ticker(EVENT_TAB_ID));
         while !is_terminated.load(Ordering::Relaxed) {
-            interval.tick().await;
+            changes.wait().await;
+            health.wait_backoff().await;
+
             let target_namespaces = shared_target_namespaces.read().await;
 
-            let event_list = get_event_table(kube_client, &target_namespaces).await;
+            let event_list = get_event_table(changes.informers(), &target_namespaces).await;
 
             tx.send(Message::Kube(Kube::Event(event_list)))
                 .expect("Failed to send Kube::Event");
@@ -57,36 +62,33 @@ impl Worker for EventPoller {
 const TARGET_LEN: usize = 4;
 const TARGET: [&str; TARGET_LEN] = ["Last Seen", "Object", "Reason", "Message"];
 
-async fn get_event_table(client: &KubeClient, namespaces: &[String]) -> Result<Vec<String>> {
+async fn get_event_table(informers: &TableInformers, namespaces: &[String]) -> Result<Vec<String>> {
     let insert_ns = insert_ns(namespaces);
 
-    let jobs = try_join_all(namespaces.iter().map(|ns| {
-        get_resource_per_namespace(
-            client,
-            format!("api/v1/namespaces/{}/{}", ns, "events"),
-            &TARGET,
-            move |row: &TableRow, indexes: &[usize]| {
-                let mut row: Vec<String> =
-                    indexes.iter().map(|i| row.cells[*i].to_string()).collect();
-
-                let name = row[0].clone();
-
-                if insert_ns {
-                    row.insert(1, ns.to_string())
-                }
-
-                KubeTableRow {
-                    namespace: ns.to_string(),
-                    name,
-                    row,
-                    ..Default::default()
-                }
-            },
-        )
-    }))
-    .await?;
-
-    let mut ok_only: Vec<KubeTableRow> = jobs.into_iter().flatten().collect();
+    let mut ok_only: Vec<KubeTableRow> = get_resource_per_namespace(
+        informers,
+        &ResourcePath::new("", "v1", "events"),
+        namespaces,
+        &TARGET,
+        |ns: &str, row: &TableRow, indexes: &[usize]| {
+            let mut row: Vec<String> = indexes.iter().map(|i| row.cells[*i].to_string()).collect();
+
+            let name = row[0].clone();
+
+            if insert_ns {
+                row.insert(1, ns.to_string())
+            }
+
+            KubeTableRow {
+                namespace: ns.to_string(),
+                name,
+                row,
+                ..Default::default()
+            }
+        },
+    )
+    .await?
+    .rows;
 
     ok_only.sort_by_key(|row| row.row[0].to_time());
 
@@ -9,7 +9,7 @@ use k8s_openapi::{
     },
     NamespaceResourceScope, Resource as _,
 };
-use kube::Api;
+use kube::{Api, Resource as _};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
@@ -59,6 +59,30 @@ impl std::fmt::Display for GetYamlKind {
     }
 }
 
+impl GetYamlKind {
+    /// 指定したnamespaceのリソース一覧のURLパス
+    pub fn url_path(&self, namespace: &str) -> String {
+        let ns = Some(namespace);
+
+        match self {
+            Self::Pod => Pod::url_path(&(), ns),
+            Self::ConfigMap => ConfigMap::url_path(&(), ns),
+            Self::Secret => Secret::url_path(&(), ns),
+            Self::Ingress => Ingress::url_path(&(), ns),
+            Self::Service => Service::url_path(&(), ns),
+            Self::NetworkPolicy => NetworkPolicy::url_path(&(), ns),
+            Self::Gateway(version) => match version {
+                GatewayVersion::V1 => v1::Gateway::url_path(&(), ns),
+                GatewayVersion::V1Beta1 => v1beta1::Gateway::url_path(&(), ns),
+            },
+            Self::HTTPRoute(version) => match version {
+                HTTPRouteVersion::V1 => v1::HTTPRoute::url_path(&(), ns),
+                HTTPRouteVersion::V1Beta1 => v1beta1::HTTPRoute::url_path(&(), ns),
+            },
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GetYamlWorker {
     is_terminated: Arc<AtomicBool>,
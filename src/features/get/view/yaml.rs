@@ -1,9 +1,19 @@
 use std::{cell::RefCell, rc::Rc};
 
+use crossbeam::channel::Sender;
+
 use crate::{
     clipboard::Clipboard,
-    features::component_id::YAML_POPUP_ID,
-    ui::widget::{config::WidgetConfig, Text, Widget, WidgetTrait},
+    features::{
+        component_id::YAML_POPUP_ID,
+        edit::{message::EditTarget, view::request_edit},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, Text, Widget, WidgetTrait},
+        Window,
+    },
 };
 
 pub struct YamlPopup {
@@ -11,14 +21,22 @@ pub struct YamlPopup {
 }
 
 impl YamlPopup {
-    pub fn new(clipboard: &Option<Rc<RefCell<Clipboard>>>) -> Self {
+    pub fn new(
+        tx: &Sender<Message>,
+        clipboard: &Option<Rc<RefCell<Clipboard>>>,
+        target: Rc<RefCell<Option<EditTarget>>>,
+    ) -> Self {
         Self {
-            popup: popup(clipboard),
+            popup: popup(tx, clipboard, target),
         }
     }
 }
 
-pub fn popup(clipboard: &Option<Rc<RefCell<Clipboard>>>) -> Widget<'static> {
+pub fn popup(
+    tx: &Sender<Message>,
+    clipboard: &Option<Rc<RefCell<Clipboard>>>,
+    target: Rc<RefCell<Option<EditTarget>>>,
+) -> Widget<'static> {
     let mut builder = Text::builder()
         .id(YAML_POPUP_ID)
         .widget_config(&WidgetConfig::builder().title("Yaml").build())
@@ -31,6 +49,7 @@ pub fn popup(clipboard: &Option<Rc<RefCell<Clipboard>>>) -> Widget<'static> {
 
             config.render_block(text.can_activate() && is_active, is_mouse_over)
         })
+        .action('e', open_edit(tx.clone(), target))
         .wrap();
 
     if let Some(clipboard) = clipboard {
@@ -39,3 +58,19 @@ pub fn popup(clipboard: &Option<Rc<RefCell<Clipboard>>>) -> Widget<'static> {
 
     builder.build().into()
 }
+
+/// 表示中のリソースをエディタで編集する
+fn open_edit(
+    tx: Sender<Message>,
+    target: Rc<RefCell<Option<EditTarget>>>,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(target) = target.borrow().clone() else {
+            return EventResult::Ignore;
+        };
+
+        request_edit(w, &tx, target);
+
+        EventResult::Nop
+    }
+}
@@ -11,7 +11,7 @@ const LEFT_HELP_TEXT: &[HelpBlock] = &[
         title: "General",
         bindings: &[
             KeyBindings {
-                keys: &["0~6"],
+                keys: &["0~9"],
                 desc: "switch tab",
             },
             KeyBindings {
@@ -38,6 +38,10 @@ const LEFT_HELP_TEXT: &[HelpBlock] = &[
                 keys: &["y"],
                 desc: "open yaml popup",
             },
+            KeyBindings {
+                keys: &["d"],
+                desc: "delete the selected resource",
+            },
             KeyBindings {
                 keys: &["q", "Esc"],
                 desc: "quit",
@@ -154,6 +158,107 @@ const RIGHT_HELP_TEXT: &[HelpBlock] = &[
             },
         ],
     },
+    HelpBlock {
+        title: "Pod Tab (Only pod view)",
+        bindings: &[
+            KeyBindings {
+                keys: &["e"],
+                desc: "exec into a container",
+            },
+            KeyBindings {
+                keys: &["D"],
+                desc: "attach to an ephemeral debug container",
+            },
+            KeyBindings {
+                keys: &["f"],
+                desc: "port-forward",
+            },
+        ],
+    },
+    HelpBlock {
+        title: "Pod Tab (Only log view)",
+        bindings: &[
+            KeyBindings {
+                keys: &["s"],
+                desc: "save log to file (without colors)",
+            },
+            KeyBindings {
+                keys: &["Ctrl-s"],
+                desc: "save log to file (raw)",
+            },
+            KeyBindings {
+                keys: &["t"],
+                desc: "toggle timestamp (none / rfc3339 / local / delta)",
+            },
+            KeyBindings {
+                keys: &["p"],
+                desc: "toggle split panes per pod",
+            },
+            KeyBindings {
+                keys: &["x"],
+                desc: "close the focused pane (split panes)",
+            },
+        ],
+    },
+    HelpBlock {
+        title: "Workload Tab",
+        bindings: &[
+            KeyBindings {
+                keys: &["r"],
+                desc: "rollout restart",
+            },
+            KeyBindings {
+                keys: &["s"],
+                desc: "scale replicas",
+            },
+            KeyBindings {
+                keys: &["p"],
+                desc: "pause / resume rollout (Deployment), suspend / resume (CronJob)",
+            },
+            KeyBindings {
+                keys: &["t"],
+                desc: "create a Job from the CronJob and show its logs",
+            },
+        ],
+    },
+    HelpBlock {
+        title: "Network Tab (Only service)",
+        bindings: &[KeyBindings {
+            keys: &["f"],
+            desc: "port-forward",
+        }],
+    },
+    HelpBlock {
+        title: "Port Forward Tab",
+        bindings: &[KeyBindings {
+            keys: &["d"],
+            desc: "stop port-forward",
+        }],
+    },
+    HelpBlock {
+        title: "Node Tab",
+        bindings: &[
+            KeyBindings {
+                keys: &["c"],
+                desc: "cordon",
+            },
+            KeyBindings {
+                keys: &["u"],
+                desc: "uncordon",
+            },
+            KeyBindings {
+                keys: &["d"],
+                desc: "drain (evict pods respecting PodDisruptionBudgets)",
+            },
+        ],
+    },
+    HelpBlock {
+        title: "Yaml Tab / Yaml Popup",
+        bindings: &[KeyBindings {
+            keys: &["e"],
+            desc: "edit in $KUBE_EDITOR or $EDITOR, review the dry-run diff and apply",
+        }],
+    },
     HelpBlock {
         title: "List / Yaml Tab",
         bindings: &[KeyBindings {
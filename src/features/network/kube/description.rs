@@ -14,9 +14,10 @@ use std::sync::{atomic::AtomicBool, Arc};
 use crate::{
     features::{
         api_resources::kube::SharedApiResources,
+        component_id::NETWORK_TAB_ID,
         network::message::{NetworkRequest, NetworkRequestTargetParams, NetworkResponse},
     },
-    kube::KubeClientRequest,
+    kube::{polling::Polling, KubeClientRequest},
     message::Message,
     workers::kube::AbortWorker,
 };
@@ -31,8 +32,6 @@ use anyhow::Result;
 use async_trait::async_trait;
 use crossbeam::channel::Sender;
 
-const INTERVAL: u64 = 3;
-
 type FetchedData = Vec<String>;
 
 #[async_trait]
@@ -57,6 +56,7 @@ where
     client: C,
     req: NetworkRequest,
     api_resources: SharedApiResources,
+    polling: Polling,
 }
 
 impl<C> NetworkDescriptionWorker<C>
@@ -69,6 +69,7 @@ where
         client: C,
         req: NetworkRequest,
         api_resources: SharedApiResources,
+        polling: Polling,
     ) -> Self {
         Self {
             is_terminated,
@@ -76,6 +77,7 @@ where
             client,
             req,
             api_resources,
+            polling,
         }
     }
 }
@@ -126,7 +128,7 @@ where
     where
         Worker: Fetch<'a, C>,
     {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(INTERVAL));
+        let mut ticker = self.polling.description_ticker(NETWORK_TAB_ID);
 
         let worker = Worker::new(
             &self.client,
@@ -138,7 +140,7 @@ where
             .is_terminated
             .load(std::sync::atomic::Ordering::Relaxed)
         {
-            interval.tick().await;
+            ticker.tick().await;
 
             let fetched_data = worker.fetch().await;
 
@@ -183,6 +185,13 @@ mod tests {
         serde_yaml::from_str(yaml).unwrap()
     }
 
+    /// ネットワークタブを表示中にする
+    fn polling() -> Polling {
+        let polling = Polling::new(Default::default());
+        polling.activate(NETWORK_TAB_ID);
+        polling
+    }
+
     mod run {
 
         use crate::{
@@ -250,6 +259,7 @@ mod tests {
                 client,
                 req,
                 ApiResources::shared(),
+                polling(),
             );
 
             let handle = tokio::spawn(async move { worker.run().await });
@@ -299,6 +309,7 @@ mod tests {
                 client,
                 req,
                 ApiResources::shared(),
+                polling(),
             );
 
             let handle = tokio::spawn(async move { worker.run().await });
@@ -390,6 +401,7 @@ mod tests {
                 client,
                 req,
                 ApiResources::shared(),
+                polling(),
             );
 
             let handle = tokio::spawn(async move {
@@ -457,6 +469,7 @@ mod tests {
                 client,
                 req,
                 ApiResources::shared(),
+                polling(),
             );
 
             let handle = tokio::spawn(async move {
@@ -515,6 +528,7 @@ mod tests {
                 client,
                 req,
                 ApiResources::shared(),
+                polling(),
             );
 
             let handle = tokio::spawn(async move {
@@ -1,8 +1,8 @@
-use std::{collections::BTreeMap, time};
+use std::collections::BTreeMap;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use futures::future::{join_all, try_join_all};
+use futures::future::join_all;
 use k8s_openapi::{
     api::{
         core::v1::{Pod, Service},
@@ -14,6 +14,7 @@ use k8s_openapi::{
 use crate::{
     features::{
         api_resources::kube::{ApiResource, ApiResources, SharedApiResources},
+        component_id::NETWORK_TAB_ID,
         network::message::{GatewayVersion, HTTPRouteVersion, NetworkResponse},
     },
     kube::{
@@ -21,8 +22,8 @@ use crate::{
             networking::gateway::{v1, v1beta1},
             v1_table::Table,
         },
+        informer::{ResourcePath, TableInformers},
         table::{insert_ns, KubeTable, KubeTableRow},
-        KubeClient, KubeClientRequest,
     },
     logger,
     workers::kube::{PollerBase, Worker, WorkerResult},
@@ -102,6 +103,7 @@ impl NetworkTable {
         KubeTable {
             header: self.header(),
             rows: self.to_kube_table_rows(),
+            ..Default::default()
         }
     }
 }
@@ -147,31 +149,35 @@ impl TargetResource {
         }
     }
 
-    async fn fetch_table(&self, client: &KubeClient, ns: &str) -> Result<Table> {
+    fn resource_path(&self) -> ResourcePath {
         match self {
-            Self::Ingress => client.table_namespaced::<Ingress>(ns).await,
-            Self::Service => client.table_namespaced::<Service>(ns).await,
-            Self::Pod => client.table_namespaced::<Pod>(ns).await,
-            Self::NetworkPolicy => client.table_namespaced::<NetworkPolicy>(ns).await,
-            Self::Gateway(GatewayVersion::V1) => client.table_namespaced::<v1::Gateway>(ns).await,
-            Self::Gateway(GatewayVersion::V1Beta1) => {
-                client.table_namespaced::<v1beta1::Gateway>(ns).await
-            }
-            Self::HTTPRoute(HTTPRouteVersion::V1) => {
-                client.table_namespaced::<v1::HTTPRoute>(ns).await
-            }
-            Self::HTTPRoute(HTTPRouteVersion::V1Beta1) => {
-                client.table_namespaced::<v1beta1::HTTPRoute>(ns).await
-            }
+            Self::Ingress => ResourcePath::of::<Ingress>(),
+            Self::Service => ResourcePath::of::<Service>(),
+            Self::Pod => ResourcePath::of::<Pod>(),
+            Self::NetworkPolicy => ResourcePath::of::<NetworkPolicy>(),
+            Self::Gateway(GatewayVersion::V1) => ResourcePath::of::<v1::Gateway>(),
+            Self::Gateway(GatewayVersion::V1Beta1) => ResourcePath::of::<v1beta1::Gateway>(),
+            Self::HTTPRoute(HTTPRouteVersion::V1) => ResourcePath::of::<v1::HTTPRoute>(),
+            Self::HTTPRoute(HTTPRouteVersion::V1Beta1) => ResourcePath::of::<v1beta1::HTTPRoute>(),
         }
-        .with_context(|| {
-            format!(
-                "Failed to fetch table: kind={} ({}) namespace={}",
-                self.as_str(),
-                self.version(),
-                ns
-            )
-        })
+    }
+
+    async fn fetch_tables(
+        &self,
+        informers: &TableInformers,
+        namespaces: &[String],
+    ) -> Result<Vec<(String, Table)>> {
+        informers
+            .tables(&self.resource_path(), namespaces)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch table: kind={} ({}) namespaces={:?}",
+                    self.as_str(),
+                    self.version(),
+                    namespaces
+                )
+            })
     }
 }
 
@@ -254,20 +260,25 @@ impl Worker for NetworkPoller {
     type Output = WorkerResult;
 
     async fn run(&self) -> Self::Output {
-        let mut interval = tokio::time::interval(time::Duration::from_secs(1));
+        let mut changes = self
+            .base
+            .informers
+            .changes(self.base.polling.ticker(NETWORK_TAB_ID));
 
         let is_terminated = &self.base.is_terminated;
         let tx = &self.base.tx;
+        let health = &self.base.health;
 
         while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
-            interval.tick().await;
+            changes.wait().await;
+            health.wait_backoff().await;
 
             let target_resources = {
                 let apis = self.api_resources.read().await;
                 target_resources(&apis)
             };
 
-            let table = self.polling(&target_resources).await;
+            let table = self.polling(changes.informers(), &target_resources).await;
 
             tx.send(NetworkResponse::List(table).into())
                 .expect("Failed to send NetworkResponse::List");
@@ -280,13 +291,17 @@ impl Worker for NetworkPoller {
 const TARGET_COLUMNS: [&str; 2] = ["Name", "Age"];
 
 impl NetworkPoller {
-    async fn polling(&self, target_resources: &[TargetResource]) -> Result<KubeTable> {
+    async fn polling(
+        &self,
+        informers: &TableInformers,
+        target_resources: &[TargetResource],
+    ) -> Result<KubeTable> {
         let target_namespaces = self.base.shared_target_namespaces.read().await;
 
         let rows: Vec<_> = join_all(
             target_resources
                 .iter()
-                .map(|kind| self.fetch_resource(kind, &target_namespaces)),
+                .map(|kind| self.fetch_resource(informers, kind, &target_namespaces)),
         )
         .await
         .into_iter()
@@ -308,33 +323,28 @@ impl NetworkPoller {
 
     async fn fetch_resource(
         &self,
+        informers: &TableInformers,
         kind: &TargetResource,
         namespaces: &[String],
     ) -> Result<Vec<NetworkTableRow>> {
-        let client = &self.base.kube_client;
-
-        let jobs = try_join_all(
-            namespaces
-                .iter()
-                .map(|ns| fetch_resource_per_namespace(client, kind, ns, &TARGET_COLUMNS)),
-        )
-        .await?;
+        let tables = kind.fetch_tables(informers, namespaces).await?;
 
-        Ok(jobs.into_iter().flatten().collect())
+        Ok(tables
+            .iter()
+            .flat_map(|(ns, table)| table_to_rows(kind, ns, table, &TARGET_COLUMNS))
+            .collect())
     }
 }
 
-async fn fetch_resource_per_namespace(
-    client: &KubeClient,
+fn table_to_rows(
     kind: &TargetResource,
     ns: &str,
+    table: &Table,
     target_columns: &[&str],
-) -> Result<Vec<NetworkTableRow>> {
-    let table = kind.fetch_table(client, ns).await?;
-
+) -> Vec<NetworkTableRow> {
     let indexes = table.find_indexes(target_columns);
 
-    let rows = table
+    table
         .rows
         .iter()
         .map(|row| NetworkTableRow {
@@ -344,9 +354,7 @@ async fn fetch_resource_per_namespace(
             name: row.cells[indexes[0]].to_string(),
             age: row.cells[indexes[1]].to_string(),
         })
-        .collect();
-
-    Ok(rows)
+        .collect()
 }
 
 /// groupとkindが一致するAPIリソースを取得する
@@ -8,6 +8,7 @@ use crate::{
     features::{
         component_id::NETWORK_TAB_ID,
         network::view::widgets::{description_widget, network_widget},
+        port_forward::view::SharedPortForwardState,
     },
     message::Message,
     ui::{
@@ -26,8 +27,9 @@ impl NetworkTab {
         tx: &Sender<Message>,
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
         split_direction: Direction,
+        port_forward_state: &SharedPortForwardState,
     ) -> Self {
-        let network_widget = network_widget(tx);
+        let network_widget = network_widget(tx, port_forward_state.clone());
         let description_widget = description_widget(clipboard);
 
         let layout = TabLayout::new(layout, split_direction);
@@ -11,6 +11,7 @@ use crate::{
     features::{
         component_id::{NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID},
         network::message::{NetworkRequest, NetworkRequestTargetParams},
+        port_forward::view::{request_port_forward, SharedPortForwardState},
     },
     kube::apis::networking::gateway::v1::{Gateway, HTTPRoute},
     message::Message,
@@ -21,7 +22,10 @@ use crate::{
     },
 };
 
-pub fn network_widget(tx: &Sender<Message>) -> Widget<'static> {
+pub fn network_widget(
+    tx: &Sender<Message>,
+    port_forward_state: SharedPortForwardState,
+) -> Widget<'static> {
     let tx = tx.clone();
 
     Table::builder()
@@ -30,6 +34,7 @@ pub fn network_widget(tx: &Sender<Message>) -> Widget<'static> {
         .filtered_key("NAME")
         .block_injection(block_injection())
         .on_select(on_select(tx))
+        .action('f', request_port_forward(port_forward_state))
         .build()
         .into()
 }
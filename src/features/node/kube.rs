@@ -0,0 +1,8 @@
+mod action;
+mod drain;
+mod node;
+mod quantity;
+
+pub use action::*;
+pub use drain::*;
+pub use node::*;
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use k8s_openapi::api::core::v1::Node;
+use kube::{
+    api::{Patch, PatchParams},
+    Api,
+};
+use serde_json::json;
+
+use crate::{
+    features::{
+        dry_run::kube::DryRunDiff,
+        node::message::{NodeDryRun, NodeRequest},
+    },
+    kube::KubeClient,
+    logger,
+};
+
+use super::drain::{list_node_pods, plan_drain, pod_ref};
+
+const FIELD_MANAGER: &str = "kubetui";
+
+/// ノードをcordon・uncordonし、成功時は結果のメッセージを返す
+///
+/// drainは進捗を送信するため [`super::DrainWorker`] で実行する
+pub async fn cordon_node(client: &KubeClient, node: &str, unschedulable: bool) -> Result<String> {
+    logger!(info, "Set node/{} unschedulable={}", node, unschedulable);
+
+    set_unschedulable(client, node, unschedulable, false).await?;
+
+    if unschedulable {
+        Ok(format!("node/{} cordoned", node))
+    } else {
+        Ok(format!("node/{} uncordoned", node))
+    }
+}
+
+/// 操作をサーバーサイドのdry-runで実行し、ノードの差分とdrainで退避させるPodを返す
+pub async fn dry_run_node_request(client: &KubeClient, req: &NodeRequest) -> Result<NodeDryRun> {
+    logger!(info, "Dry-run node request {:?}", req);
+
+    let node = req.node();
+
+    let api: Api<Node> = Api::all(client.to_client());
+
+    let live = api.get(node).await?;
+
+    let unschedulable = !matches!(req, NodeRequest::Uncordon(_));
+
+    let dry_run = set_unschedulable(client, node, unschedulable, true).await?;
+
+    let diff = DryRunDiff::from_resources(&live, &dry_run)?;
+
+    if !matches!(req, NodeRequest::Drain(_)) {
+        return Ok(NodeDryRun {
+            diff,
+            evictions: Vec::new(),
+            emptydir_evictions: Vec::new(),
+            skipped: Vec::new(),
+        });
+    }
+
+    let plan = plan_drain(list_node_pods(client, node).await?);
+
+    Ok(NodeDryRun {
+        diff,
+        evictions: plan.evictions.iter().map(pod_ref).collect(),
+        emptydir_evictions: plan.emptydir_evictions.iter().map(pod_ref).collect(),
+        skipped: plan
+            .skipped
+            .iter()
+            .map(|(pod, reason)| (pod_ref(pod), reason.to_string()))
+            .collect(),
+    })
+}
+
+pub(super) async fn set_unschedulable(
+    client: &KubeClient,
+    node: &str,
+    unschedulable: bool,
+    dry_run: bool,
+) -> Result<Node> {
+    let api: Api<Node> = Api::all(client.to_client());
+
+    let params = PatchParams {
+        dry_run,
+        field_manager: Some(FIELD_MANAGER.to_string()),
+        ..Default::default()
+    };
+
+    let node = api
+        .patch(
+            node,
+            &params,
+            &Patch::Merge(unschedulable_patch(unschedulable)),
+        )
+        .await?;
+
+    Ok(node)
+}
+
+/// `kubectl cordon` と同様にspec.unschedulableを更新する
+///
+/// uncordonではフィールドを削除する
+fn unschedulable_patch(unschedulable: bool) -> serde_json::Value {
+    if unschedulable {
+        json!({ "spec": { "unschedulable": true } })
+    } else {
+        json!({ "spec": { "unschedulable": null } })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn uncordonではunschedulableを削除する() {
+        assert_eq!(
+            unschedulable_patch(true),
+            json!({ "spec": { "unschedulable": true } })
+        );
+        assert_eq!(
+            unschedulable_patch(false),
+            json!({ "spec": { "unschedulable": null } })
+        );
+    }
+}
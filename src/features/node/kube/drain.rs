@@ -0,0 +1,374 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use crossbeam::channel::Sender;
+use futures::future::try_join_all;
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::{EvictParams, ListParams},
+    Api, ResourceExt as _,
+};
+
+use crate::{
+    features::node::message::DrainEvent, kube::KubeClient, logger, message::Message,
+    workers::kube::AbortWorker,
+};
+
+use super::action::set_unschedulable;
+
+/// 静的Podのミラーに付与されるアノテーション
+const MIRROR_POD_ANNOTATION: &str = "kubernetes.io/config.mirror";
+
+/// PodDisruptionBudgetによってevictionが拒否された場合に再試行するまでの時間
+const EVICTION_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Podの削除を確認する間隔
+const DELETION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// drain全体のタイムアウト
+///
+/// PodDisruptionBudgetを満たせない場合に待ち続けないようにする
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// drainで退避させるPodと退避させないPod
+#[derive(Debug, Default)]
+pub struct DrainPlan {
+    pub evictions: Vec<Pod>,
+    /// 退避させるとemptyDirのデータが失われるPod
+    pub emptydir_evictions: Vec<Pod>,
+    /// 退避させないPodと理由
+    pub skipped: Vec<(Pod, &'static str)>,
+}
+
+/// `kubectl drain --ignore-daemonsets --delete-emptydir-data` と同様に退避させるPodを選ぶ
+///
+/// コントローラーに管理されていないPodは退避させると再作成されないため対象外にする。
+/// ただし終了済みのPodは失うものがないため退避させる。
+/// emptyDirを使うPodはデータが失われることを確認画面で示すため分けて返す
+pub fn plan_drain(pods: Vec<Pod>) -> DrainPlan {
+    let mut plan = DrainPlan::default();
+
+    for pod in pods {
+        let controller = pod
+            .owner_references()
+            .iter()
+            .find(|owner| owner.controller.unwrap_or_default());
+
+        let finished = is_finished(&pod);
+
+        let skip_reason = if pod.annotations().contains_key(MIRROR_POD_ANNOTATION) {
+            Some("mirror pod")
+        } else {
+            match controller {
+                Some(owner) if owner.kind == "DaemonSet" => Some("DaemonSet-managed pod"),
+                Some(_) => None,
+                None if finished => None,
+                None => Some("not managed by a controller, delete it manually"),
+            }
+        };
+
+        match skip_reason {
+            Some(reason) => plan.skipped.push((pod, reason)),
+            None if !finished && has_emptydir(&pod) => plan.emptydir_evictions.push(pod),
+            None => plan.evictions.push(pod),
+        }
+    }
+
+    plan
+}
+
+fn is_finished(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.phase.as_deref())
+        .is_some_and(|phase| phase == "Succeeded" || phase == "Failed")
+}
+
+fn has_emptydir(pod: &Pod) -> bool {
+    pod.spec
+        .as_ref()
+        .and_then(|spec| spec.volumes.as_ref())
+        .is_some_and(|volumes| volumes.iter().any(|volume| volume.empty_dir.is_some()))
+}
+
+/// ノード上のPodを取得する
+pub async fn list_node_pods(client: &KubeClient, node: &str) -> Result<Vec<Pod>> {
+    let api: Api<Pod> = Api::all(client.to_client());
+
+    let pods = api
+        .list(&ListParams::default().fields(&format!("spec.nodeName={}", node)))
+        .await?;
+
+    Ok(pods.items)
+}
+
+pub fn pod_ref(pod: &Pod) -> String {
+    format!("{}/{}", pod.namespace().unwrap_or_default(), pod.name_any())
+}
+
+#[derive(Clone)]
+pub struct DrainWorker {
+    tx: Sender<Message>,
+    client: KubeClient,
+    node: String,
+}
+
+impl DrainWorker {
+    pub fn new(tx: Sender<Message>, client: KubeClient, node: String) -> Self {
+        Self { tx, client, node }
+    }
+
+    fn progress(&self, message: String) {
+        self.tx
+            .send(DrainEvent::Progress(message).into())
+            .expect("Failed to send DrainEvent::Progress");
+    }
+
+    async fn drain(&self) -> Result<String> {
+        set_unschedulable(&self.client, &self.node, true, false).await?;
+
+        self.progress(format!("node/{} cordoned", self.node));
+
+        let plan = plan_drain(list_node_pods(&self.client, &self.node).await?);
+
+        for (pod, reason) in &plan.skipped {
+            self.progress(format!(
+                "\x1b[33mWARNING: skipping pod {}: {}\x1b[39m",
+                pod_ref(pod),
+                reason
+            ));
+        }
+
+        try_join_all(
+            plan.evictions
+                .iter()
+                .chain(&plan.emptydir_evictions)
+                .map(|pod| self.evict(pod)),
+        )
+        .await?;
+
+        Ok(format!("node/{} drained", self.node))
+    }
+
+    /// Podを退避させ、削除されるまで待つ
+    async fn evict(&self, pod: &Pod) -> Result<()> {
+        let api: Api<Pod> = Api::namespaced(
+            self.client.to_client(),
+            &pod.namespace().unwrap_or_default(),
+        );
+
+        let name = pod.name_any();
+
+        let pod_ref = pod_ref(pod);
+
+        loop {
+            match api.evict(&name, &EvictParams::default()).await {
+                Ok(_) => break,
+                Err(kube::Error::Api(err)) if err.code == 404 => return Ok(()),
+                Err(kube::Error::Api(err)) if err.code == 429 => {
+                    self.progress(format!(
+                        "error when evicting pod {} (will retry after {}s): {}",
+                        pod_ref,
+                        EVICTION_RETRY_INTERVAL.as_secs(),
+                        err.message
+                    ));
+
+                    tokio::time::sleep(EVICTION_RETRY_INTERVAL).await;
+                }
+                Err(err) => {
+                    return Err(anyhow!("Failed to evict pod {}: {}", pod_ref, err));
+                }
+            }
+        }
+
+        self.progress(format!("evicting pod {}", pod_ref));
+
+        loop {
+            tokio::time::sleep(DELETION_POLL_INTERVAL).await;
+
+            let current = api.get_opt(&name).await?;
+
+            // 同名のPodが再作成された場合もuidが変わるため、削除されたものとみなす
+            if current.is_none_or(|current| current.uid() != pod.uid()) {
+                break;
+            }
+        }
+
+        self.progress(format!("pod {} evicted", pod_ref));
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl AbortWorker for DrainWorker {
+    async fn run(&self) {
+        logger!(info, "Draining node {}", self.node);
+
+        self.tx
+            .send(DrainEvent::Started(self.node.clone()).into())
+            .expect("Failed to send DrainEvent::Started");
+
+        let result = match tokio::time::timeout(DRAIN_TIMEOUT, self.drain()).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!(
+                "Timed out after {}s. node/{} stays cordoned",
+                DRAIN_TIMEOUT.as_secs(),
+                self.node
+            )),
+        };
+
+        if let Err(err) = &result {
+            logger!(error, "Failed to drain node {}: {:?}", self.node, err);
+        }
+
+        self.tx
+            .send(DrainEvent::Finished(result).into())
+            .expect("Failed to send DrainEvent::Finished");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn pod(name: &str, metadata: serde_json::Value) -> Pod {
+        let mut value = json!({
+            "metadata": { "name": name, "namespace": "default" },
+        });
+
+        value["metadata"]
+            .as_object_mut()
+            .unwrap()
+            .extend(metadata.as_object().unwrap().clone());
+
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn pod_with(name: &str, metadata: serde_json::Value, extra: serde_json::Value) -> Pod {
+        let mut value = serde_json::to_value(pod(name, metadata)).unwrap();
+
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn emptydir() -> serde_json::Value {
+        json!({
+            "containers": [{ "name": "app" }],
+            "volumes": [{ "name": "cache", "emptyDir": {} }],
+        })
+    }
+
+    fn owner(kind: &str) -> serde_json::Value {
+        json!({
+            "ownerReferences": [{
+                "apiVersion": "apps/v1",
+                "kind": kind,
+                "name": "owner",
+                "uid": "uid",
+                "controller": true,
+            }],
+        })
+    }
+
+    #[test]
+    fn daemonsetとミラーpodと管理されていないpodは退避させない() {
+        let pods = vec![
+            pod("app", owner("ReplicaSet")),
+            pod("agent", owner("DaemonSet")),
+            pod(
+                "kube-apiserver",
+                json!({ "annotations": { MIRROR_POD_ANNOTATION: "hash" } }),
+            ),
+            pod("standalone", json!({})),
+        ];
+
+        let plan = plan_drain(pods);
+
+        assert_eq!(
+            plan.evictions.iter().map(pod_ref).collect::<Vec<_>>(),
+            vec!["default/app"]
+        );
+        assert_eq!(
+            plan.skipped
+                .iter()
+                .map(|(pod, reason)| (pod_ref(pod), *reason))
+                .collect::<Vec<_>>(),
+            vec![
+                ("default/agent".to_string(), "DaemonSet-managed pod"),
+                ("default/kube-apiserver".to_string(), "mirror pod"),
+                (
+                    "default/standalone".to_string(),
+                    "not managed by a controller, delete it manually"
+                ),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("Succeeded")]
+    #[case("Failed")]
+    fn 終了済みのpodはコントローラーに管理されていなくても退避させる(
+        #[case] phase: &str,
+    ) {
+        let pods = vec![
+            pod_with("job", json!({}), json!({ "status": { "phase": phase } })),
+            pod_with(
+                "standalone",
+                json!({}),
+                json!({ "status": { "phase": "Running" } }),
+            ),
+        ];
+
+        let plan = plan_drain(pods);
+
+        assert_eq!(
+            plan.evictions.iter().map(pod_ref).collect::<Vec<_>>(),
+            vec!["default/job"]
+        );
+        assert_eq!(
+            plan.skipped
+                .iter()
+                .map(|(pod, _)| pod_ref(pod))
+                .collect::<Vec<_>>(),
+            vec!["default/standalone"]
+        );
+    }
+
+    #[rstest]
+    #[case::実行中("Running", vec![], vec!["default/cache"])]
+    #[case::終了済み("Succeeded", vec!["default/cache"], vec![])]
+    fn emptydirを使う実行中のpodは分けて返す(
+        #[case] phase: &str,
+        #[case] evictions: Vec<&str>,
+        #[case] emptydir_evictions: Vec<&str>,
+    ) {
+        let pods = vec![pod_with(
+            "cache",
+            owner("ReplicaSet"),
+            json!({ "spec": emptydir(), "status": { "phase": phase } }),
+        )];
+
+        let plan = plan_drain(pods);
+
+        assert_eq!(
+            plan.evictions.iter().map(pod_ref).collect::<Vec<_>>(),
+            evictions
+        );
+        assert_eq!(
+            plan.emptydir_evictions
+                .iter()
+                .map(pod_ref)
+                .collect::<Vec<_>>(),
+            emptydir_evictions
+        );
+        assert!(plan.skipped.is_empty());
+    }
+}
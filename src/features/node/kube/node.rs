@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use k8s_openapi::api::core::v1::Node;
+
+use crate::{
+    features::{component_id::NODE_TAB_ID, node::message::NodeResponse},
+    kube::{
+        apis::{
+            metrics::NodeMetricsList,
+            v1_table::{Table, TableRow},
+        },
+        table::{KubeTable, KubeTableRow},
+        KubeClient, KubeClientRequest as _,
+    },
+    logger,
+    workers::kube::{PollerBase, Worker, WorkerResult},
+};
+
+use super::quantity::{format_cpu, format_memory, format_usage, parse_quantity};
+
+/// サーバーサイドのテーブルから取得する列
+const COLUMNS: [&str; 4] = ["Name", "Status", "Roles", "Age"];
+
+const HEADER: [&str; 10] = [
+    "NAME",
+    "STATUS",
+    "ROLES",
+    "VERSION",
+    "TAINTS",
+    "CPU",
+    "MEMORY",
+    "CPU USAGE",
+    "MEMORY USAGE",
+    "AGE",
+];
+
+/// メトリクスを取得できない場合の表示
+const UNKNOWN: &str = "<unknown>";
+
+#[derive(Clone)]
+pub struct NodePoller {
+    base: PollerBase,
+}
+
+impl NodePoller {
+    pub fn new(base: PollerBase) -> Self {
+        Self { base }
+    }
+}
+
+#[async_trait]
+impl Worker for NodePoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let PollerBase {
+            is_terminated,
+            tx,
+            kube_client,
+            polling,
+            health,
+            ..
+        } = &self.base;
+
+        let mut ticker = polling.ticker(NODE_TAB_ID);
+
+        while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
+            ticker.tick().await;
+            health.wait_backoff().await;
+
+            let table = fetch_nodes(kube_client).await;
+
+            health.record(&table);
+
+            tx.send(NodeResponse::Table(table).into())
+                .expect("Failed to send NodeResponse::Table");
+        }
+
+        WorkerResult::Terminated
+    }
+}
+
+/// ノードの一覧をメトリクスとあわせて取得する
+///
+/// metrics-serverがない場合も一覧は表示し、使用量は `<unknown>` とする
+async fn fetch_nodes(client: &KubeClient) -> Result<KubeTable> {
+    let table: Table = client
+        .table_request("api/v1/nodes?includeObject=Object")
+        .await?;
+
+    let usages = match client
+        .request::<NodeMetricsList>("apis/metrics.k8s.io/v1beta1/nodes")
+        .await
+    {
+        Ok(metrics) => metrics
+            .items
+            .into_iter()
+            .filter_map(|m| Some((m.metadata?.name?, m.usage)))
+            .collect(),
+        Err(err) => {
+            logger!(debug, "Failed to fetch node metrics: {:?}", err);
+            HashMap::new()
+        }
+    };
+
+    let indexes = table.find_indexes(&COLUMNS);
+
+    let mut kube_table = KubeTable {
+        header: HEADER.iter().map(ToString::to_string).collect(),
+        ..Default::default()
+    };
+
+    kube_table.update_rows(
+        table
+            .rows
+            .iter()
+            .map(|row| node_row(row, &indexes, &usages))
+            .collect(),
+    );
+
+    Ok(kube_table)
+}
+
+fn node_row(
+    row: &TableRow,
+    indexes: &[usize],
+    usages: &HashMap<String, HashMap<String, String>>,
+) -> KubeTableRow {
+    let name = row.cells[indexes[0]].to_string();
+
+    let node: Option<Node> = row
+        .object
+        .as_ref()
+        .and_then(|object| serde_json::from_value(object.0.clone()).ok());
+
+    let version = node
+        .as_ref()
+        .and_then(|node| node.status.as_ref()?.node_info.as_ref())
+        .map(|info| info.kubelet_version.clone())
+        .unwrap_or_default();
+
+    let taints = node.as_ref().map(taints).unwrap_or_default();
+
+    let allocatable = |key: &str| {
+        node.as_ref()
+            .and_then(|node| node.status.as_ref()?.allocatable.as_ref()?.get(key))
+            .map(|quantity| quantity.0.clone())
+    };
+
+    let allocatable_cpu = allocatable("cpu");
+    let allocatable_memory = allocatable("memory");
+
+    let usage = |key: &str| {
+        usages
+            .get(&name)
+            .and_then(|usage| usage.get(key))
+            .and_then(|quantity| parse_quantity(quantity))
+    };
+
+    let cpu_usage = usage("cpu").map_or(UNKNOWN.to_string(), |cpu| {
+        format_usage(
+            cpu,
+            allocatable_cpu.as_deref().and_then(parse_quantity),
+            format_cpu,
+        )
+    });
+
+    let memory_usage = usage("memory").map_or(UNKNOWN.to_string(), |memory| {
+        format_usage(
+            memory,
+            allocatable_memory.as_deref().and_then(parse_quantity),
+            format_memory,
+        )
+    });
+
+    let unschedulable = node
+        .as_ref()
+        .and_then(|node| node.spec.as_ref()?.unschedulable)
+        .unwrap_or_default();
+
+    KubeTableRow {
+        namespace: String::default(),
+        name: name.clone(),
+        metadata: Some(
+            [("unschedulable".to_string(), unschedulable.to_string())]
+                .into_iter()
+                .collect(),
+        ),
+        row: vec![
+            name,
+            row.cells[indexes[1]].to_string(),
+            row.cells[indexes[2]].to_string(),
+            version,
+            taints,
+            allocatable_cpu.unwrap_or_default(),
+            allocatable_memory
+                .as_deref()
+                .and_then(parse_quantity)
+                .map(format_memory)
+                .unwrap_or_default(),
+            cpu_usage,
+            memory_usage,
+            row.cells[indexes[3]].to_string(),
+        ],
+    }
+}
+
+/// taintを `key=value:effect` 形式で列挙する
+fn taints(node: &Node) -> String {
+    let Some(taints) = node.spec.as_ref().and_then(|spec| spec.taints.as_ref()) else {
+        return "<none>".to_string();
+    };
+
+    if taints.is_empty() {
+        return "<none>".to_string();
+    }
+
+    taints
+        .iter()
+        .map(|taint| match &taint.value {
+            Some(value) => format!("{}={}:{}", taint.key, value, taint.effect),
+            None => format!("{}:{}", taint.key, taint.effect),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn row(object: serde_json::Value) -> TableRow {
+        serde_json::from_value(json!({
+            "cells": ["node-1", "Ready,SchedulingDisabled", "control-plane", "10d"],
+            "object": object,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn ノードの情報とメトリクスを表示する() {
+        let row = row(json!({
+            "metadata": { "name": "node-1" },
+            "spec": {
+                "unschedulable": true,
+                "taints": [
+                    { "key": "node-role.kubernetes.io/control-plane", "effect": "NoSchedule" },
+                    { "key": "dedicated", "value": "db", "effect": "NoExecute" },
+                ],
+            },
+            "status": {
+                "allocatable": { "cpu": "4", "memory": "8Gi" },
+                "nodeInfo": {
+                    "architecture": "amd64",
+                    "bootID": "",
+                    "containerRuntimeVersion": "containerd://1.7.0",
+                    "kernelVersion": "",
+                    "kubeProxyVersion": "",
+                    "kubeletVersion": "v1.30.0",
+                    "machineID": "",
+                    "operatingSystem": "linux",
+                    "osImage": "",
+                    "systemUUID": "",
+                },
+            },
+        }));
+
+        let usages = HashMap::from([(
+            "node-1".to_string(),
+            HashMap::from([
+                ("cpu".to_string(), "1000000000n".to_string()),
+                ("memory".to_string(), "2Gi".to_string()),
+            ]),
+        )]);
+
+        let actual = node_row(&row, &[0, 1, 2, 3], &usages);
+
+        assert_eq!(
+            actual.row,
+            vec![
+                "node-1",
+                "Ready,SchedulingDisabled",
+                "control-plane",
+                "v1.30.0",
+                "node-role.kubernetes.io/control-plane:NoSchedule,dedicated=db:NoExecute",
+                "4",
+                "8.0Gi",
+                "1000m (25%)",
+                "2.0Gi (25%)",
+                "10d",
+            ]
+        );
+        assert_eq!(
+            actual
+                .metadata
+                .unwrap()
+                .get("unschedulable")
+                .map(String::as_str),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn メトリクスがなければunknownを表示する() {
+        let row = row(json!({ "metadata": { "name": "node-1" } }));
+
+        let actual = node_row(&row, &[0, 1, 2, 3], &HashMap::new());
+
+        assert_eq!(actual.row[4], "<none>");
+        assert_eq!(actual.row[7], UNKNOWN);
+        assert_eq!(actual.row[8], UNKNOWN);
+    }
+}
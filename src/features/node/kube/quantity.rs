@@ -0,0 +1,106 @@
+//! リソースの量（Quantity）の解析と表示
+
+const BINARY_SUFFIXES: [(&str, f64); 6] = [
+    ("Ki", 1024.0),
+    ("Mi", 1024.0 * 1024.0),
+    ("Gi", 1024.0 * 1024.0 * 1024.0),
+    ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    ("Pi", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    ("Ei", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+];
+
+const DECIMAL_SUFFIXES: [(&str, f64); 9] = [
+    ("n", 1e-9),
+    ("u", 1e-6),
+    ("m", 1e-3),
+    ("k", 1e3),
+    ("M", 1e6),
+    ("G", 1e9),
+    ("T", 1e12),
+    ("P", 1e15),
+    ("E", 1e18),
+];
+
+/// Quantityを数値に変換する（例: `250m` -> 0.25、`1Ki` -> 1024）
+pub fn parse_quantity(s: &str) -> Option<f64> {
+    let s = s.trim();
+
+    let (number, multiplier) = BINARY_SUFFIXES
+        .iter()
+        .chain(DECIMAL_SUFFIXES.iter())
+        .find_map(|(suffix, multiplier)| s.strip_suffix(suffix).map(|number| (number, *multiplier)))
+        .unwrap_or((s, 1.0));
+
+    number.parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// CPUのコア数をミリコアで表示する
+pub fn format_cpu(cores: f64) -> String {
+    format!("{}m", (cores * 1000.0).round())
+}
+
+/// バイト数を2の累乗の単位で表示する
+pub fn format_memory(bytes: f64) -> String {
+    let Some((suffix, multiplier)) = BINARY_SUFFIXES
+        .iter()
+        .rev()
+        .find(|(_, multiplier)| *multiplier <= bytes)
+    else {
+        return format!("{}", bytes.round());
+    };
+
+    let value = bytes / multiplier;
+
+    if value < 10.0 {
+        format!("{:.1}{}", value, suffix)
+    } else {
+        format!("{}{}", value.round(), suffix)
+    }
+}
+
+/// 使用量を割り当て可能な量に対する割合とあわせて表示する
+pub fn format_usage(usage: f64, allocatable: Option<f64>, format: fn(f64) -> String) -> String {
+    match allocatable.filter(|allocatable| 0.0 < *allocatable) {
+        Some(allocatable) => format!(
+            "{} ({}%)",
+            format(usage),
+            (usage / allocatable * 100.0).round()
+        ),
+        None => format(usage),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("4", Some(4.0))]
+    #[case("250m", Some(0.25))]
+    #[case("1500000000n", Some(1.5))]
+    #[case("2Ki", Some(2048.0))]
+    #[case("1Gi", Some(1073741824.0))]
+    #[case("1G", Some(1e9))]
+    #[case("0.5", Some(0.5))]
+    #[case("abc", None)]
+    fn quantityを数値に変換する(#[case] input: &str, #[case] expected: Option<f64>) {
+        assert_eq!(parse_quantity(input), expected);
+    }
+
+    #[rstest]
+    #[case(512.0, "512")]
+    #[case(1536.0, "1.5Ki")]
+    #[case(16331640.0 * 1024.0, "16Gi")]
+    #[case(3.5 * 1024.0 * 1024.0 * 1024.0, "3.5Gi")]
+    fn メモリを単位付きで表示する(#[case] bytes: f64, #[case] expected: &str) {
+        assert_eq!(format_memory(bytes), expected);
+    }
+
+    #[test]
+    fn 使用量を割合とあわせて表示する() {
+        assert_eq!(format_usage(0.25, Some(4.0), format_cpu), "250m (6%)");
+        assert_eq!(format_usage(0.25, None, format_cpu), "250m");
+    }
+}
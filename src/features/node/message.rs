@@ -0,0 +1,87 @@
+use anyhow::Result;
+
+use crate::{
+    features::dry_run::kube::DryRunDiff, kube::table::KubeTable, message::Message,
+    workers::kube::message::Kube,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeRequest {
+    /// ノードをスケジュール不可にする
+    Cordon(String),
+    /// ノードをスケジュール可能に戻す
+    Uncordon(String),
+    /// ノードをcordonし、Podをeviction APIで退避させる
+    Drain(String),
+}
+
+impl NodeRequest {
+    pub fn node(&self) -> &str {
+        match self {
+            Self::Cordon(node) | Self::Uncordon(node) | Self::Drain(node) => node,
+        }
+    }
+}
+
+/// 操作をサーバーサイドのdry-runで実行した結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeDryRun {
+    /// ノードの差分
+    pub diff: DryRunDiff,
+    /// drainで退避させるPod（`namespace/name`）
+    pub evictions: Vec<String>,
+    /// drainで退避させるとemptyDirのデータが失われるPod（`namespace/name`）
+    pub emptydir_evictions: Vec<String>,
+    /// drainで退避させないPodと理由
+    pub skipped: Vec<(String, String)>,
+}
+
+/// drainの進捗
+#[derive(Debug)]
+pub enum DrainEvent {
+    Started(String),
+    Progress(String),
+    /// 終了時は成功時のメッセージ、またはエラーを返す
+    Finished(Result<String>),
+}
+
+#[derive(Debug)]
+pub enum NodeResponse {
+    Table(Result<KubeTable>),
+    /// cordon・uncordonの結果（成功時は表示するメッセージ）
+    Action(Result<String>),
+    DryRun(NodeRequest, Result<NodeDryRun>),
+    Drain(DrainEvent),
+}
+
+#[derive(Debug)]
+pub enum NodeMessage {
+    Request(NodeRequest),
+    /// 操作をサーバーサイドのdry-runで実行する
+    DryRun(NodeRequest),
+    Response(NodeResponse),
+}
+
+impl From<NodeMessage> for Message {
+    fn from(m: NodeMessage) -> Self {
+        Self::Kube(Kube::Node(m))
+    }
+}
+
+impl From<NodeRequest> for Message {
+    fn from(req: NodeRequest) -> Self {
+        NodeMessage::Request(req).into()
+    }
+}
+
+impl From<NodeResponse> for Message {
+    fn from(res: NodeResponse) -> Self {
+        NodeMessage::Response(res).into()
+    }
+}
+
+impl From<DrainEvent> for Message {
+    fn from(ev: DrainEvent) -> Self {
+        NodeResponse::Drain(ev).into()
+    }
+}
@@ -0,0 +1,7 @@
+mod tab;
+mod widgets;
+
+pub use tab::*;
+pub use widgets::{
+    show_drain_event, show_node_action_result, show_node_dry_run_result, SharedNodeActionState,
+};
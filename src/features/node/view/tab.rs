@@ -0,0 +1,51 @@
+use crossbeam::channel::Sender;
+use ratatui::layout::{Constraint, Direction};
+
+use crate::{
+    features::component_id::NODE_TAB_ID,
+    message::Message,
+    ui::{
+        tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
+        widget::Widget,
+        Tab,
+    },
+};
+
+use super::widgets::{action_popup_widget, drain_widget, node_widget, SharedNodeActionState};
+
+pub struct NodeTab {
+    pub tab: Tab<'static>,
+    pub action_popup: Widget<'static>,
+}
+
+impl NodeTab {
+    pub fn new(title: &'static str, tx: &Sender<Message>, state: SharedNodeActionState) -> Self {
+        let node_widget = node_widget(tx, state.clone());
+        let drain_widget = drain_widget();
+        let action_popup = action_popup_widget(tx, state);
+
+        // テーブルの列が多いため、分割方向の設定によらず上下に並べる
+        let layout = TabLayout::new(
+            |_| {
+                NestedWidgetLayout::default()
+                    .direction(Direction::Vertical)
+                    .nested_widget_layout([
+                        NestedLayoutElement(
+                            Constraint::Percentage(60),
+                            LayoutElement::WidgetIndex(0),
+                        ),
+                        NestedLayoutElement(
+                            Constraint::Percentage(40),
+                            LayoutElement::WidgetIndex(1),
+                        ),
+                    ])
+            },
+            Default::default(),
+        );
+
+        Self {
+            tab: Tab::new(NODE_TAB_ID, title, [node_widget, drain_widget], layout),
+            action_popup,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod action;
+mod drain;
+mod node;
+
+pub(super) use action::action_popup_widget;
+pub use action::{show_node_action_result, show_node_dry_run_result, SharedNodeActionState};
+pub(super) use drain::drain_widget;
+pub use drain::show_drain_event;
+pub(super) use node::node_widget;
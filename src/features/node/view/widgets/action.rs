@@ -0,0 +1,253 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::{NODE_ACTION_POPUP_ID, NODE_DRAIN_WIDGET_ID},
+        dry_run::view::diff_lines,
+        node::message::{NodeDryRun, NodeMessage, NodeRequest},
+    },
+    logger,
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, Item, LiteralItem, Text, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+/// 確認待ちの操作
+#[derive(Debug, Default)]
+pub struct NodeActionState {
+    pub confirm: Option<NodeRequest>,
+}
+
+pub type SharedNodeActionState = Rc<RefCell<NodeActionState>>;
+
+pub fn action_popup_widget(tx: &Sender<Message>, state: SharedNodeActionState) -> Widget<'static> {
+    Text::builder()
+        .id(NODE_ACTION_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Node").build())
+        .wrap()
+        .action('y', confirm(tx.clone(), state.clone()))
+        .action('n', cancel(state.clone()))
+        .action(UserEvent::from(KeyCode::Enter), cancel(state))
+        .build()
+        .into()
+}
+
+/// 操作の確認ポップアップを開く
+///
+/// 操作をサーバーサイドのdry-runで実行し、結果を [`show_node_dry_run_result`] で表示する
+pub fn open_confirm_popup(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    state: &SharedNodeActionState,
+    req: NodeRequest,
+) {
+    update_popup(
+        w,
+        confirm_lines(
+            &req,
+            vec!["\x1b[90mRunning server-side dry-run ...\x1b[39m".to_string()],
+        ),
+    );
+
+    state.borrow_mut().confirm = Some(req.clone());
+
+    tx.send(NodeMessage::DryRun(req).into())
+        .expect("Failed to send NodeMessage::DryRun");
+
+    w.open_popup(NODE_ACTION_POPUP_ID);
+}
+
+/// dry-runの結果を確認ポップアップに表示する
+///
+/// 確認待ちの操作が変わっていた場合は何もしない
+pub fn show_node_dry_run_result(
+    w: &mut Window,
+    state: &SharedNodeActionState,
+    req: NodeRequest,
+    result: Result<NodeDryRun>,
+) {
+    if state.borrow().confirm.as_ref() != Some(&req) {
+        return;
+    }
+
+    let body = match result {
+        Ok(dry_run) => dry_run_lines(&req, dry_run),
+        Err(err) => {
+            logger!(error, "Dry-run failed for {:?}: {:?}", req, err);
+
+            ["Dry-run failed:".to_string()]
+                .into_iter()
+                .chain(format!("{:?}", err).lines().map(String::from))
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    update_popup(w, confirm_lines(&req, body));
+}
+
+/// 操作できないノードを選択した場合などのメッセージを表示する
+pub fn show_message(w: &mut Window, message: String) {
+    update_popup(w, vec![message, String::default(), close_hint()]);
+
+    w.open_popup(NODE_ACTION_POPUP_ID);
+}
+
+/// cordon・uncordonの結果をポップアップに表示する
+pub fn show_node_action_result(w: &mut Window, result: Result<String>) {
+    let lines = match result {
+        Ok(message) => vec![message],
+        Err(err) => {
+            logger!(error, "Node request failed: {:?}", err);
+
+            format!("{:?}", err)
+                .lines()
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    update_popup(
+        w,
+        lines
+            .into_iter()
+            .chain([String::default(), close_hint()])
+            .collect(),
+    );
+
+    w.open_popup(NODE_ACTION_POPUP_ID);
+}
+
+fn dry_run_lines(req: &NodeRequest, dry_run: NodeDryRun) -> Vec<String> {
+    let mut lines = if dry_run.diff.is_changed() {
+        diff_lines(&dry_run.diff.live, &dry_run.diff.dry_run)
+    } else {
+        vec!["The server reports no changes to the node.".to_string()]
+    };
+
+    if !matches!(req, NodeRequest::Drain(_)) {
+        return lines;
+    }
+
+    lines.push(String::default());
+
+    if dry_run.evictions.is_empty() && dry_run.emptydir_evictions.is_empty() {
+        lines.push("No pods to evict.".to_string());
+    }
+
+    if !dry_run.evictions.is_empty() {
+        lines.push(format!("Pods to evict ({}):", dry_run.evictions.len()));
+        lines.extend(dry_run.evictions.iter().map(|pod| format!("  {}", pod)));
+    }
+
+    if !dry_run.emptydir_evictions.is_empty() {
+        if !dry_run.evictions.is_empty() {
+            lines.push(String::default());
+        }
+
+        lines.push(format!(
+            "Pods to evict, deleting their emptyDir data ({}):",
+            dry_run.emptydir_evictions.len()
+        ));
+        lines.extend(
+            dry_run
+                .emptydir_evictions
+                .iter()
+                .map(|pod| format!("  \x1b[31m{}\x1b[39m", pod)),
+        );
+    }
+
+    if !dry_run.skipped.is_empty() {
+        lines.push(String::default());
+        lines.push(format!("Pods to skip ({}):", dry_run.skipped.len()));
+        lines.extend(
+            dry_run
+                .skipped
+                .iter()
+                .map(|(pod, reason)| format!("  \x1b[33m{}: {}\x1b[39m", pod, reason)),
+        );
+    }
+
+    lines
+}
+
+fn confirm_lines(req: &NodeRequest, body: Vec<String>) -> Vec<String> {
+    let (question, note) = match req {
+        NodeRequest::Cordon(node) => (
+            format!("Cordon node/{}?", node),
+            "New pods will not be scheduled on the node.",
+        ),
+        NodeRequest::Uncordon(node) => (
+            format!("Uncordon node/{}?", node),
+            "New pods can be scheduled on the node again.",
+        ),
+        NodeRequest::Drain(node) => (
+            format!("Drain node/{}?", node),
+            "The node is cordoned and its pods are evicted, respecting PodDisruptionBudgets.",
+        ),
+    };
+
+    [question, note.to_string(), String::default()]
+        .into_iter()
+        .chain(body)
+        .chain([
+            String::default(),
+            "Press \x1b[1my\x1b[0m to continue, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to cancel."
+                .to_string(),
+        ])
+        .collect()
+}
+
+fn update_popup(w: &mut Window, lines: Vec<String>) {
+    let items = lines
+        .into_iter()
+        .map(|line| LiteralItem::new(line, None))
+        .collect();
+
+    w.find_widget_mut(NODE_ACTION_POPUP_ID)
+        .update_widget_item(Item::Array(items));
+}
+
+fn close_hint() -> String {
+    "Press \x1b[1mEnter\x1b[0m or \x1b[1mEsc\x1b[0m to close.".to_string()
+}
+
+fn confirm(
+    tx: Sender<Message>,
+    state: SharedNodeActionState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(req) = state.borrow_mut().confirm.take() else {
+            return EventResult::Nop;
+        };
+
+        // drainの進捗はテキストペインに表示する
+        if let NodeRequest::Drain(_) = req {
+            w.close_popup();
+            w.activate_widget_by_id(NODE_DRAIN_WIDGET_ID);
+        } else {
+            update_popup(w, vec!["Sending request ...".to_string()]);
+        }
+
+        tx.send(req.into()).expect("Failed to send NodeRequest");
+
+        EventResult::Nop
+    }
+}
+
+fn cancel(state: SharedNodeActionState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        state.borrow_mut().confirm.take();
+
+        w.close_popup();
+
+        EventResult::Nop
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    features::{component_id::NODE_DRAIN_WIDGET_ID, node::message::DrainEvent},
+    logger,
+    ui::{
+        widget::{config::WidgetConfig, Item, LiteralItem, Text, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+pub fn drain_widget() -> Widget<'static> {
+    Text::builder()
+        .id(NODE_DRAIN_WIDGET_ID)
+        .widget_config(&WidgetConfig::builder().title("Drain").build())
+        .wrap()
+        .follow()
+        .build()
+        .into()
+}
+
+/// drainの進捗をテキストペインに追記する
+pub fn show_drain_event(w: &mut Window, ev: DrainEvent) {
+    let line = match ev {
+        DrainEvent::Started(node) => {
+            w.widget_clear(NODE_DRAIN_WIDGET_ID);
+
+            *w.find_widget_mut(NODE_DRAIN_WIDGET_ID)
+                .widget_config_mut()
+                .append_title_mut() = Some(format!(" : {}", node).into());
+
+            format!("Draining node/{} ...", node)
+        }
+        DrainEvent::Progress(message) => message,
+        DrainEvent::Finished(Ok(message)) => format!("\x1b[32m{}\x1b[39m", message),
+        DrainEvent::Finished(Err(err)) => {
+            logger!(error, "Drain failed: {:?}", err);
+
+            format!("\x1b[31m{}\x1b[39m", err)
+        }
+    };
+
+    w.find_widget_mut(NODE_DRAIN_WIDGET_ID)
+        .append_widget_item(Item::Array(vec![LiteralItem::new(line, None)]));
+}
@@ -0,0 +1,95 @@
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::{component_id::NODE_WIDGET_ID, node::message::NodeRequest},
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, SelectedItem, Table, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+use super::action::{open_confirm_popup, show_message, SharedNodeActionState};
+
+pub fn node_widget(tx: &Sender<Message>, state: SharedNodeActionState) -> Widget<'static> {
+    Table::builder()
+        .id(NODE_WIDGET_ID)
+        .widget_config(&WidgetConfig::builder().title("Node").build())
+        .filtered_key("NAME")
+        .block_injection(block_injection())
+        .action('c', cordon(tx.clone(), state.clone(), true))
+        .action('u', cordon(tx.clone(), state.clone(), false))
+        .action('d', drain(tx.clone(), state))
+        .build()
+        .into()
+}
+
+fn block_injection() -> impl Fn(&Table) -> WidgetConfig {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut widget_config = table.widget_config().clone();
+
+        *widget_config.append_title_mut() =
+            Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        widget_config
+    }
+}
+
+fn cordon(
+    tx: Sender<Message>,
+    state: SharedNodeActionState,
+    unschedulable: bool,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some((node, is_unschedulable)) = selected_node(w) else {
+            return EventResult::Ignore;
+        };
+
+        match (unschedulable, is_unschedulable) {
+            (true, true) => show_message(w, format!("node/{} is already cordoned", node)),
+            (false, false) => show_message(w, format!("node/{} is not cordoned", node)),
+            (true, false) => open_confirm_popup(w, &tx, &state, NodeRequest::Cordon(node)),
+            (false, true) => open_confirm_popup(w, &tx, &state, NodeRequest::Uncordon(node)),
+        }
+
+        EventResult::Nop
+    }
+}
+
+fn drain(tx: Sender<Message>, state: SharedNodeActionState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some((node, _)) = selected_node(w) else {
+            return EventResult::Ignore;
+        };
+
+        open_confirm_popup(w, &tx, &state, NodeRequest::Drain(node));
+
+        EventResult::Nop
+    }
+}
+
+/// 選択中のノード名と、スケジュール不可かどうかを取得する
+fn selected_node(w: &Window) -> Option<(String, bool)> {
+    let Some(SelectedItem::TableRow {
+        metadata: Some(metadata),
+        ..
+    }) = w.find_widget(NODE_WIDGET_ID).widget_item()
+    else {
+        return None;
+    };
+
+    let name = metadata.get("name")?.to_string();
+
+    let unschedulable = metadata
+        .get("unschedulable")
+        .is_some_and(|unschedulable| unschedulable == "true");
+
+    Some((name, unschedulable))
+}
@@ -1,6 +1,10 @@
+mod debug;
+mod exec;
 mod filter;
 mod log;
 mod pod;
 
+pub use debug::*;
+pub use exec::*;
 pub use log::*;
 pub use pod::*;
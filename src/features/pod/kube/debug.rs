@@ -0,0 +1,322 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use crossbeam::channel::Sender;
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    api::{Patch, PatchParams},
+    Api,
+};
+use serde_json::json;
+
+use crate::{
+    features::{dry_run::kube::DryRunDiff, pod::message::DebugMessage},
+    kube::KubeClient,
+    logger,
+    message::Message,
+    workers::kube::AbortWorker,
+};
+
+use super::exec::{ExecMode, ExecTarget};
+
+/// エフェメラルコンテナ名の接頭辞
+const CONTAINER_NAME_PREFIX: &str = "debugger";
+
+/// コンテナが起動したかを確認する間隔
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// コンテナの起動を待つ時間
+///
+/// イメージのpullに時間がかかる場合を考慮する
+const START_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 起動に失敗したとみなすコンテナの待機理由
+const FAILED_WAITING_REASONS: [&str; 4] = [
+    "ErrImagePull",
+    "ImagePullBackOff",
+    "InvalidImageName",
+    "CreateContainerError",
+];
+
+/// デバッグ用のエフェメラルコンテナを追加するPod
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugRequest {
+    pub namespace: String,
+    pub pod: String,
+    pub image: String,
+    /// プロセス名前空間を共有するコンテナ
+    pub target: Option<String>,
+}
+
+/// エフェメラルコンテナの追加をサーバーサイドのdry-runで実行し、Podの差分を返す
+///
+/// エフェメラルコンテナは追加すると削除できないため、追加する前に確認する
+pub async fn dry_run_debug(client: &KubeClient, req: &DebugRequest) -> Result<DryRunDiff> {
+    logger!(info, "Dry-run debug request {:?}", req);
+
+    let api: Api<Pod> = Api::namespaced(client.to_client(), &req.namespace);
+
+    let live = api.get(&req.pod).await?;
+
+    let container = container_name(&ephemeral_container_names(&live));
+
+    let dry_run = api
+        .patch_ephemeral_containers(
+            &req.pod,
+            &PatchParams {
+                dry_run: true,
+                ..Default::default()
+            },
+            &Patch::Strategic(ephemeral_container_patch(
+                &container,
+                &req.image,
+                req.target.as_deref(),
+            )),
+        )
+        .await?;
+
+    DryRunDiff::from_resources(&live, &dry_run)
+}
+
+#[derive(Clone)]
+pub struct DebugWorker {
+    tx: Sender<Message>,
+    client: KubeClient,
+    req: DebugRequest,
+}
+
+impl DebugWorker {
+    pub fn new(tx: Sender<Message>, client: KubeClient, req: DebugRequest) -> Self {
+        Self { tx, client, req }
+    }
+
+    /// エフェメラルコンテナを追加し、起動するまで待つ
+    async fn debug(&self) -> Result<ExecTarget> {
+        let DebugRequest {
+            namespace,
+            pod,
+            image,
+            target,
+        } = &self.req;
+
+        let api: Api<Pod> = Api::namespaced(self.client.to_client(), namespace);
+
+        let container = container_name(&ephemeral_container_names(&api.get(pod).await?));
+
+        logger!(
+            info,
+            "Add ephemeral container {} ({}) to pod {}/{}",
+            container,
+            image,
+            namespace,
+            pod
+        );
+
+        api.patch_ephemeral_containers(
+            pod,
+            &PatchParams::default(),
+            &Patch::Strategic(ephemeral_container_patch(
+                &container,
+                image,
+                target.as_deref(),
+            )),
+        )
+        .await?;
+
+        loop {
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+
+            if is_running(&api.get(pod).await?, &container)? {
+                break;
+            }
+        }
+
+        Ok(ExecTarget {
+            namespace: namespace.to_string(),
+            pod: pod.to_string(),
+            container,
+            mode: ExecMode::Attach,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AbortWorker for DebugWorker {
+    async fn run(&self) {
+        let result = match tokio::time::timeout(START_TIMEOUT, self.debug()).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!(
+                "Timed out after {}s waiting for the debug container to start",
+                START_TIMEOUT.as_secs()
+            )),
+        };
+
+        if let Err(err) = &result {
+            logger!(error, "Failed to debug pod {:?}: {:?}", self.req, err);
+        }
+
+        self.tx
+            .send(DebugMessage::Response(self.req.clone(), result).into())
+            .expect("Failed to send DebugMessage::Response");
+    }
+}
+
+fn ephemeral_container_names(pod: &Pod) -> Vec<String> {
+    pod.spec
+        .iter()
+        .flat_map(|spec| spec.ephemeral_containers.iter().flatten())
+        .map(|container| container.name.clone())
+        .collect()
+}
+
+/// 既存のエフェメラルコンテナと重複しない名前を生成する（例: `debugger-1`）
+///
+/// エフェメラルコンテナは削除できないため、連番で名前を付ける
+fn container_name(existing: &[String]) -> String {
+    (1..)
+        .map(|n| format!("{}-{}", CONTAINER_NAME_PREFIX, n))
+        .find(|name| !existing.contains(name))
+        .expect("Failed to generate container name")
+}
+
+/// `kubectl debug` と同様にstdinとttyを有効にしたエフェメラルコンテナを追加する
+fn ephemeral_container_patch(name: &str, image: &str, target: Option<&str>) -> serde_json::Value {
+    let mut container = json!({
+        "name": name,
+        "image": image,
+        "stdin": true,
+        "tty": true,
+        "terminationMessagePolicy": "File",
+    });
+
+    if let Some(target) = target {
+        container["targetContainerName"] = json!(target);
+    }
+
+    json!({ "spec": { "ephemeralContainers": [container] } })
+}
+
+/// エフェメラルコンテナが起動しているかを返す
+///
+/// 起動できなかった場合はエラーを返す
+fn is_running(pod: &Pod, container: &str) -> Result<bool> {
+    let Some(status) = pod
+        .status
+        .iter()
+        .flat_map(|status| status.ephemeral_container_statuses.iter().flatten())
+        .find(|status| status.name == container)
+    else {
+        return Ok(false);
+    };
+
+    let Some(state) = &status.state else {
+        return Ok(false);
+    };
+
+    if state.running.is_some() {
+        return Ok(true);
+    }
+
+    if let Some(terminated) = &state.terminated {
+        return Err(anyhow!(
+            "Container {} terminated: {} (exit code {})",
+            container,
+            terminated.reason.as_deref().unwrap_or("Unknown"),
+            terminated.exit_code
+        ));
+    }
+
+    if let Some(waiting) = &state.waiting {
+        if let Some(reason) = waiting
+            .reason
+            .as_deref()
+            .filter(|reason| FAILED_WAITING_REASONS.contains(reason))
+        {
+            return Err(anyhow!(
+                "Container {} failed to start: {}: {}",
+                container,
+                reason,
+                waiting.message.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(vec![], "debugger-1")]
+    #[case(vec!["debugger-1", "debugger-3"], "debugger-2")]
+    fn 既存のコンテナと重複しない名前を生成する(
+        #[case] existing: Vec<&str>,
+        #[case] expected: &str,
+    ) {
+        let existing: Vec<String> = existing.into_iter().map(String::from).collect();
+
+        assert_eq!(container_name(&existing), expected);
+    }
+
+    #[test]
+    fn targetを指定するとプロセス名前空間を共有する() {
+        assert_eq!(
+            ephemeral_container_patch("debugger-1", "busybox", Some("app")),
+            json!({
+                "spec": {
+                    "ephemeralContainers": [{
+                        "name": "debugger-1",
+                        "image": "busybox",
+                        "stdin": true,
+                        "tty": true,
+                        "terminationMessagePolicy": "File",
+                        "targetContainerName": "app",
+                    }],
+                },
+            })
+        );
+        assert!(
+            ephemeral_container_patch("debugger-1", "busybox", None)["spec"]["ephemeralContainers"]
+                [0]
+            .get("targetContainerName")
+            .is_none()
+        );
+    }
+
+    fn pod(state: serde_json::Value) -> Pod {
+        serde_json::from_value(json!({
+            "metadata": { "name": "app-0" },
+            "status": {
+                "ephemeralContainerStatuses": [{
+                    "name": "debugger-1",
+                    "image": "busybox",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 0,
+                    "state": state,
+                }],
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn コンテナの状態から起動を判定する() {
+        assert!(is_running(&pod(json!({ "running": {} })), "debugger-1").unwrap());
+        assert!(!is_running(
+            &pod(json!({ "waiting": { "reason": "ContainerCreating" } })),
+            "debugger-1"
+        )
+        .unwrap());
+        assert!(!is_running(&pod(json!({ "running": {} })), "debugger-2").unwrap());
+        assert!(is_running(
+            &pod(json!({ "waiting": { "reason": "ImagePullBackOff", "message": "not found" } })),
+            "debugger-1"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,337 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as _, Result};
+use futures::SinkExt as _;
+use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::apis::meta::v1::Status};
+use kube::{
+    api::{AttachParams, AttachedProcess, TerminalSize},
+    Api, Client,
+};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt as _},
+    sync::mpsc::UnboundedReceiver,
+};
+
+use crate::{kube::KubeClient, logger};
+
+/// コンテナ内で起動するシェル
+///
+/// bashがあればbashを、なければshを起動する
+const SHELL_COMMAND: &str = "command -v bash >/dev/null 2>&1 && exec bash || exec sh";
+
+/// 端末の大きさの変化を確認する間隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// コマンドが0以外で終了した場合のStatusのreason
+///
+/// シェルの終了コードはエラーとして扱わない
+const NON_ZERO_EXIT_CODE: &str = "NonZeroExitCode";
+
+/// attachした直後に表示する案内
+///
+/// 接続前に出力されたプロンプトは表示されないため、`kubectl attach` と同じ案内を表示する
+const ATTACH_HINT: &str = "If you don't see a command prompt, try pressing enter.\r\n";
+
+/// コンテナへの接続方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecMode {
+    /// シェルを起動する（`kubectl exec` 相当）
+    Shell,
+    /// コンテナのメインプロセスに接続する（`kubectl attach` 相当）
+    Attach,
+}
+
+/// execの対象となるコンテナ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecTarget {
+    pub namespace: String,
+    pub pod: String,
+    pub container: String,
+    pub mode: ExecMode,
+}
+
+impl ExecTarget {
+    /// TTYを割り当ててコンテナに接続する
+    async fn connect(&self, client: Client) -> Result<AttachedProcess> {
+        let api: Api<Pod> = Api::namespaced(client, &self.namespace);
+
+        let params = AttachParams::interactive_tty().container(&self.container);
+
+        let attached = match self.mode {
+            ExecMode::Shell => {
+                api.exec(&self.pod, ["sh", "-c", SHELL_COMMAND], &params)
+                    .await?
+            }
+            ExecMode::Attach => api.attach(&self.pod, &params).await?,
+        };
+
+        Ok(attached)
+    }
+}
+
+/// 端末をコンテナにつなぎ、コンテナのプロセスが終了するまで入出力を中継する
+///
+/// 呼び出し元で端末をrawモードにし、UserInputスレッドが読み取ったキー入力をinputに転送しておく
+pub async fn exec_session(
+    client: Client,
+    target: &ExecTarget,
+    mut input: UnboundedReceiver<Event>,
+) -> Result<()> {
+    logger!(info, "exec session start: {:?}", target);
+
+    let mut attached = target.connect(client).await?;
+
+    let mut stdin = attached
+        .stdin()
+        .ok_or_else(|| anyhow!("Failed to get stdin of the container"))?;
+
+    let mut stdout = attached
+        .stdout()
+        .ok_or_else(|| anyhow!("Failed to get stdout of the container"))?;
+
+    let mut terminal_size = attached.terminal_size();
+
+    let status = attached.take_status();
+
+    let mut size = None;
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    let mut terminal = tokio::io::stdout();
+
+    if target.mode == ExecMode::Attach {
+        terminal.write_all(ATTACH_HINT.as_bytes()).await?;
+        terminal.flush().await?;
+    }
+
+    // コンテナのプロセスが終了すると出力が閉じられる
+    let output = tokio::io::copy(&mut stdout, &mut terminal);
+
+    tokio::pin!(output);
+
+    let relay = async {
+        loop {
+            tokio::select! {
+                result = &mut output => {
+                    result.context("Failed to write the output of the container")?;
+                    break;
+                }
+                Some(ev) = input.recv() => {
+                    if let Some(bytes) = encode_input(&ev) {
+                        write_input(&mut stdin, &bytes).await?;
+                    }
+                }
+                _ = interval.tick() => {
+                    let current = ratatui::crossterm::terminal::size().ok();
+
+                    if current != size {
+                        size = current;
+
+                        if let (Some(sender), Some((width, height))) = (terminal_size.as_mut(), current) {
+                            // リサイズに失敗しても入出力は続けられるため、エラーは記録するだけにする
+                            if let Err(err) = sender.send(TerminalSize { width, height }).await {
+                                logger!(warn, "Failed to resize the terminal: {:?}", err);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        anyhow::Ok(())
+    };
+
+    relay.await?;
+
+    let status = match status {
+        Some(status) => status.await,
+        None => None,
+    };
+
+    logger!(info, "exec session end: {:?}", status);
+
+    check_status(status)
+}
+
+async fn write_input(stdin: &mut (impl AsyncWrite + Unpin), input: &[u8]) -> Result<()> {
+    stdin
+        .write_all(input)
+        .await
+        .context("Failed to write the input to the container")?;
+
+    stdin
+        .flush()
+        .await
+        .context("Failed to write the input to the container")
+}
+
+/// コンテナのプロセスの終了状態を確認する
+///
+/// シェルが0以外の終了コードで終わっただけの場合はエラーにしない
+fn check_status(status: Option<Status>) -> Result<()> {
+    let Some(status) = status else {
+        return Ok(());
+    };
+
+    if status.status.as_deref() == Some("Success")
+        || status.reason.as_deref() == Some(NON_ZERO_EXIT_CODE)
+    {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "{}",
+        status
+            .message
+            .unwrap_or_else(|| "The exec session failed".to_string())
+    ))
+}
+
+/// キー入力を端末が送るバイト列に変換する
+///
+/// crosstermが解釈したキー入力をxterm互換のエスケープシーケンスに戻す
+fn encode_input(ev: &Event) -> Option<Vec<u8>> {
+    let KeyEvent {
+        code, modifiers, ..
+    } = match ev {
+        Event::Key(key) => key,
+        Event::Paste(text) => return Some(text.as_bytes().to_vec()),
+        _ => return None,
+    };
+
+    let bytes = match code {
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => vec![control_char(*c)?],
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => b"\r".to_vec(),
+        KeyCode::Tab => b"\t".to_vec(),
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => b"\x7f".to_vec(),
+        KeyCode::Esc => b"\x1b".to_vec(),
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n @ 1..=4) => format!("\x1bO{}", (b'P' + n - 1) as char).into_bytes(),
+        KeyCode::F(n @ 5..=12) => {
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][(n - 5) as usize];
+
+            format!("\x1b[{}~", code).into_bytes()
+        }
+        _ => return None,
+    };
+
+    // Altを押しながらの入力はESCを前に付けて送る
+    if modifiers.contains(KeyModifiers::ALT) {
+        return Some([b"\x1b".as_slice(), &bytes].concat());
+    }
+
+    Some(bytes)
+}
+
+/// Ctrlと組み合わせた文字の制御コード
+fn control_char(c: char) -> Option<u8> {
+    match c.to_ascii_lowercase() {
+        c @ 'a'..='z' => Some(c as u8 - b'a' + 1),
+        '@' | ' ' | '2' => Some(0),
+        '[' | '3' => Some(0x1b),
+        '\\' | '4' => Some(0x1c),
+        ']' | '5' => Some(0x1d),
+        '^' | '6' => Some(0x1e),
+        '_' | '7' | '/' => Some(0x1f),
+        '8' | '?' => Some(0x7f),
+        _ => None,
+    }
+}
+
+/// Podのコンテナ名を定義順に取得する
+pub async fn fetch_containers(
+    client: &KubeClient,
+    namespace: &str,
+    pod: &str,
+) -> Result<Vec<String>> {
+    let api: Api<Pod> = Api::namespaced(client.to_client(), namespace);
+
+    let pod = api.get(pod).await?;
+
+    let containers = pod
+        .spec
+        .map(|spec| spec.containers.into_iter().map(|c| c.name).collect())
+        .unwrap_or_default();
+
+    Ok(containers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::KeyEventKind;
+    use rstest::rstest;
+
+    fn status(status: &str, reason: Option<&str>) -> Status {
+        Status {
+            status: Some(status.to_string()),
+            reason: reason.map(ToString::to_string),
+            message: Some("command terminated with non-zero exit code".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn シェルの終了コードはエラーにしない() {
+        assert!(check_status(None).is_ok());
+        assert!(check_status(Some(status("Success", None))).is_ok());
+        assert!(check_status(Some(status("Failure", Some(NON_ZERO_EXIT_CODE)))).is_ok());
+    }
+
+    #[test]
+    fn 接続に失敗した場合はエラーにする() {
+        let status = Status {
+            status: Some("Failure".to_string()),
+            reason: Some("InternalError".to_string()),
+            message: Some("container not found (\"app\")".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            check_status(Some(status)).unwrap_err().to_string(),
+            "container not found (\"app\")"
+        );
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new_with_kind(
+            code,
+            modifiers,
+            KeyEventKind::Press,
+        ))
+    }
+
+    #[rstest]
+    #[case(key(KeyCode::Char('a'), KeyModifiers::NONE), Some(b"a".to_vec()))]
+    #[case(key(KeyCode::Char('A'), KeyModifiers::SHIFT), Some(b"A".to_vec()))]
+    #[case(key(KeyCode::Char('あ'), KeyModifiers::NONE), Some("あ".as_bytes().to_vec()))]
+    #[case(key(KeyCode::Char('c'), KeyModifiers::CONTROL), Some(vec![0x03]))]
+    #[case(key(KeyCode::Char('d'), KeyModifiers::CONTROL), Some(vec![0x04]))]
+    #[case(key(KeyCode::Char('b'), KeyModifiers::ALT), Some(b"\x1bb".to_vec()))]
+    #[case(key(KeyCode::Enter, KeyModifiers::NONE), Some(b"\r".to_vec()))]
+    #[case(key(KeyCode::Backspace, KeyModifiers::NONE), Some(vec![0x7f]))]
+    #[case(key(KeyCode::Up, KeyModifiers::NONE), Some(b"\x1b[A".to_vec()))]
+    #[case(key(KeyCode::Delete, KeyModifiers::NONE), Some(b"\x1b[3~".to_vec()))]
+    #[case(key(KeyCode::F(1), KeyModifiers::NONE), Some(b"\x1bOP".to_vec()))]
+    #[case(key(KeyCode::F(12), KeyModifiers::NONE), Some(b"\x1b[24~".to_vec()))]
+    #[case(Event::Paste("ls -l\n".to_string()), Some(b"ls -l\n".to_vec()))]
+    #[case(Event::FocusGained, None)]
+    fn キー入力を端末のバイト列に変換する(
+        #[case] ev: Event,
+        #[case] expected: Option<Vec<u8>>,
+    ) {
+        assert_eq!(encode_input(&ev), expected);
+    }
+}
@@ -1,8 +1,9 @@
 mod parser;
 
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
 use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use regex::Regex;
 
 use self::parser::parse_attributes;
@@ -25,8 +26,21 @@ pub struct Filter {
     pub label_selector: Option<LabelSelector>,
     pub include_log: Option<Vec<Regex>>,
     pub exclude_log: Option<Vec<Regex>>,
+    pub json: Option<Vec<JsonFilter>>,
+    pub since: Option<LogSince>,
+    pub tail_lines: Option<i64>,
+    pub previous: bool,
+    /// JSON形式のログを整形して表示する
+    pub pretty: bool,
+    pub expression: Option<FilterExpression>,
+    /// 複数のストリームのログをタイムスタンプ順に並び替えるための保持期間
+    pub reorder_window: Option<Duration>,
+    pub highlight: Option<Vec<LogHighlight>>,
 }
 
+/// `reorder:` で保持期間を省略した場合の値
+const DEFAULT_REORDER_WINDOW: Duration = Duration::from_secs(1);
+
 impl Filter {
     pub fn parse(query: &str) -> Result<Self> {
         let parsed_attrs = FilterAttributes::parse(query)?;
@@ -35,6 +49,8 @@ impl Filter {
 
         let mut filter = Filter::default();
 
+        let mut expressions = Vec::new();
+
         for attr in valid_attrs {
             match attr {
                 FilterAttribute::Pod(regex) => {
@@ -69,7 +85,7 @@ impl Filter {
 
                 FilterAttribute::Resource(resource) => match resource {
                     SpecifiedResource::Pod(name) => {
-                        let regex = Regex::new(&format!("^{}$", name))?;
+                        let regex = Regex::new(&format!("^{}$", regex::escape(name)))?;
                         filter.pod = Some(regex);
                     }
 
@@ -137,9 +153,93 @@ impl Filter {
                         filter.exclude_log = Some(vec![regex]);
                     }
                 }
+
+                FilterAttribute::Json(path, regex) => {
+                    let json = JsonFilter::new(path, &regex)?;
+
+                    if let Some(vec) = &mut filter.json {
+                        vec.push(json);
+                    } else {
+                        filter.json = Some(vec![json]);
+                    }
+                }
+
+                FilterAttribute::Since(value) => {
+                    filter.since = Some(LogSince::parse(&value)?);
+                }
+
+                FilterAttribute::Tail(value) => {
+                    let Ok(lines) = value.parse::<i64>() else {
+                        bail!(FilterError::Syntax(format!(
+                            "Invalid tail value: {}",
+                            value
+                        )));
+                    };
+
+                    filter.tail_lines = Some(lines);
+                }
+
+                FilterAttribute::Previous => {
+                    filter.previous = true;
+                }
+
+                FilterAttribute::Pretty => {
+                    filter.pretty = true;
+                }
+
+                FilterAttribute::Reorder(value) => {
+                    let window = match value {
+                        Some(value) => match LogSince::parse_duration(&value) {
+                            Some(seconds) if 0 < seconds => Duration::from_secs(seconds as u64),
+                            _ => {
+                                bail!(FilterError::Syntax(format!(
+                                    "Invalid reorder value: {}. Use a positive duration (e.g. 2s, 1m).",
+                                    value
+                                )));
+                            }
+                        },
+                        None => DEFAULT_REORDER_WINDOW,
+                    };
+
+                    filter.reorder_window = Some(window);
+                }
+
+                FilterAttribute::Highlight(regex, color) => {
+                    let color = match color {
+                        Some(color) => HighlightColor::parse(color).ok_or_else(|| {
+                            FilterError::Syntax(format!(
+                                "Invalid highlight color: {}. Use one of {}.",
+                                color,
+                                HighlightColor::NAMES.join(", ")
+                            ))
+                        })?,
+                        None => HighlightColor::default(),
+                    };
+
+                    let highlight = LogHighlight {
+                        regex: Regex::new(&regex)?,
+                        color,
+                    };
+
+                    if let Some(vec) = &mut filter.highlight {
+                        vec.push(highlight);
+                    } else {
+                        filter.highlight = Some(vec![highlight]);
+                    }
+                }
+
+                attr @ (FilterAttribute::And(_) | FilterAttribute::Or(_)) => {
+                    expressions.push(FilterExpression::compile(attr)?);
+                }
             }
         }
 
+        filter.expression = match expressions.len() {
+            0 => None,
+            1 => expressions.pop(),
+            _ => Some(FilterExpression::And(expressions)),
+        };
+
         Ok(filter)
     }
 
@@ -205,10 +305,358 @@ impl std::fmt::Display for Filter {
             }
         }
 
+        if let Some(json) = &self.json {
+            for j in json {
+                buf.push(format!("json={}", j));
+            }
+        }
+
+        if let Some(since) = &self.since {
+            buf.push(format!("since={}", since));
+        }
+
+        if let Some(tail_lines) = &self.tail_lines {
+            buf.push(format!("tail={}", tail_lines));
+        }
+
+        if self.previous {
+            buf.push("previous=true".to_string());
+        }
+
+        if self.pretty {
+            buf.push("pretty=true".to_string());
+        }
+
+        if let Some(window) = &self.reorder_window {
+            buf.push(format!("reorder={}s", window.as_secs()));
+        }
+
+        if let Some(highlight) = &self.highlight {
+            for h in highlight {
+                buf.push(format!("highlight={}={}", h.regex.as_str(), h.color));
+            }
+        }
+
+        if let Some(expression) = &self.expression {
+            buf.push(format!("expression={}", expression));
+        }
+
         write!(f, "{}", buf.join(" "))
     }
 }
 
+/// or/and/括弧で組み立てた条件式
+#[derive(Debug, Clone)]
+pub enum FilterExpression {
+    And(Vec<FilterExpression>),
+    Or(Vec<FilterExpression>),
+    Pod(Regex),
+    ExcludePod(Regex),
+    Container(Regex),
+    ExcludeContainer(Regex),
+    IncludeLog(Regex),
+    ExcludeLog(Regex),
+    Json(JsonFilter),
+}
+
+impl FilterExpression {
+    fn compile(attr: FilterAttribute<'_>) -> Result<Self> {
+        let expression = match attr {
+            FilterAttribute::And(attrs) => Self::And(
+                attrs
+                    .into_iter()
+                    .map(Self::compile)
+                    .collect::<Result<_>>()?,
+            ),
+            FilterAttribute::Or(attrs) => Self::Or(
+                attrs
+                    .into_iter()
+                    .map(Self::compile)
+                    .collect::<Result<_>>()?,
+            ),
+            FilterAttribute::Pod(regex) => Self::Pod(Regex::new(&regex)?),
+            FilterAttribute::ExcludePod(regex) => Self::ExcludePod(Regex::new(&regex)?),
+            FilterAttribute::Container(regex) => Self::Container(Regex::new(&regex)?),
+            FilterAttribute::ExcludeContainer(regex) => Self::ExcludeContainer(Regex::new(&regex)?),
+            FilterAttribute::IncludeLog(regex) => Self::IncludeLog(Regex::new(&regex)?),
+            FilterAttribute::ExcludeLog(regex) => Self::ExcludeLog(Regex::new(&regex)?),
+            FilterAttribute::Json(path, regex) => Self::Json(JsonFilter::new(path, &regex)?),
+            FilterAttribute::Resource(SpecifiedResource::Pod(name)) => {
+                Self::Pod(Regex::new(&format!("^{}$", regex::escape(name)))?)
+            }
+            _ => {
+                bail!(FilterError::Syntax("Only pod, container, log and json queries can be combined with or, and and parentheses.".into()));
+            }
+        };
+
+        Ok(expression)
+    }
+
+    /// 条件式を評価する
+    ///
+    /// 評価に必要な値（コンテナ名、ログ）が与えられていない条件を含み、
+    /// 結果が確定しない場合はNoneを返す
+    pub fn evaluate(
+        &self,
+        pod: &str,
+        container: Option<&str>,
+        log: Option<LogContent<'_>>,
+    ) -> Option<bool> {
+        match self {
+            Self::And(expressions) => {
+                let mut result = Some(true);
+
+                for expression in expressions {
+                    match expression.evaluate(pod, container, log) {
+                        Some(false) => return Some(false),
+                        Some(true) => {}
+                        None => result = None,
+                    }
+                }
+
+                result
+            }
+            Self::Or(expressions) => {
+                let mut result = Some(false);
+
+                for expression in expressions {
+                    match expression.evaluate(pod, container, log) {
+                        Some(true) => return Some(true),
+                        Some(false) => {}
+                        None => result = None,
+                    }
+                }
+
+                result
+            }
+            Self::Pod(re) => Some(re.is_match(pod)),
+            Self::ExcludePod(re) => Some(!re.is_match(pod)),
+            Self::Container(re) => container.map(|c| re.is_match(c)),
+            Self::ExcludeContainer(re) => container.map(|c| !re.is_match(c)),
+            Self::IncludeLog(re) => log.map(|l| re.is_match(l.text)),
+            Self::ExcludeLog(re) => log.map(|l| !re.is_match(l.text)),
+            Self::Json(json) => log.map(|l| l.json.is_some_and(|value| json.is_match(value))),
+        }
+    }
+
+    /// jsonの条件を含むか
+    pub fn contains_json(&self) -> bool {
+        match self {
+            Self::And(expressions) | Self::Or(expressions) => {
+                expressions.iter().any(Self::contains_json)
+            }
+            Self::Json(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// 条件式で評価するログ
+#[derive(Debug, Clone, Copy)]
+pub struct LogContent<'a> {
+    pub text: &'a str,
+    /// JSON形式のログをパースした値（JSON形式でないログはNone）
+    pub json: Option<&'a serde_json::Value>,
+}
+
+impl std::fmt::Display for FilterExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn join(expressions: &[FilterExpression], sep: &str) -> String {
+            expressions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(sep)
+        }
+
+        match self {
+            Self::And(expressions) => write!(f, "({})", join(expressions, " and ")),
+            Self::Or(expressions) => write!(f, "({})", join(expressions, " or ")),
+            Self::Pod(re) => write!(f, "pod={}", re.as_str()),
+            Self::ExcludePod(re) => write!(f, "exclude_pod={}", re.as_str()),
+            Self::Container(re) => write!(f, "container={}", re.as_str()),
+            Self::ExcludeContainer(re) => write!(f, "exclude_container={}", re.as_str()),
+            Self::IncludeLog(re) => write!(f, "include={}", re.as_str()),
+            Self::ExcludeLog(re) => write!(f, "exclude={}", re.as_str()),
+            Self::Json(json) => write!(f, "json={}", json),
+        }
+    }
+}
+
+/// JSON形式のログのフィールドに対するフィルター
+#[derive(Debug, Clone)]
+pub struct JsonFilter {
+    path: Vec<String>,
+    regex: Regex,
+}
+
+impl JsonFilter {
+    fn new(path: &str, regex: &str) -> Result<Self> {
+        Ok(Self {
+            path: path.split('.').map(ToString::to_string).collect(),
+            regex: Regex::new(regex)?,
+        })
+    }
+
+    /// パスで指定したフィールドの値が正規表現にマッチするか判定する
+    ///
+    /// 文字列以外の値はJSON表記に変換して判定する
+    pub fn is_match(&self, value: &serde_json::Value) -> bool {
+        let field = self.path.iter().try_fold(value, |value, key| match value {
+            serde_json::Value::Object(map) => map.get(key),
+            serde_json::Value::Array(vec) => key.parse::<usize>().ok().and_then(|i| vec.get(i)),
+            _ => None,
+        });
+
+        match field {
+            Some(serde_json::Value::String(s)) => self.regex.is_match(s),
+            Some(value) => self.regex.is_match(&value.to_string()),
+            None => false,
+        }
+    }
+}
+
+impl std::fmt::Display for JsonFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.path.join("."), self.regex.as_str())
+    }
+}
+
+/// ログのハイライト規則
+#[derive(Debug, Clone)]
+pub struct LogHighlight {
+    pub regex: Regex,
+    pub color: HighlightColor,
+}
+
+/// ハイライトの背景色
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum HighlightColor {
+    Red,
+    Green,
+    #[default]
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl HighlightColor {
+    const NAMES: [&'static str; 7] = ["red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+    fn parse(value: &str) -> Option<Self> {
+        let color = match value {
+            "red" => Self::Red,
+            "green" => Self::Green,
+            "yellow" => Self::Yellow,
+            "blue" => Self::Blue,
+            "magenta" => Self::Magenta,
+            "cyan" => Self::Cyan,
+            "white" => Self::White,
+            _ => return None,
+        };
+
+        Some(color)
+    }
+}
+
+impl std::fmt::Display for HighlightColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Red => "red",
+            Self::Green => "green",
+            Self::Yellow => "yellow",
+            Self::Blue => "blue",
+            Self::Magenta => "magenta",
+            Self::Cyan => "cyan",
+            Self::White => "white",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// ログの取得開始位置
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LogSince {
+    /// 現在時刻からの相対時間（秒）
+    Seconds(i64),
+    /// RFC3339形式の時刻
+    Time(DateTime<Utc>),
+}
+
+impl LogSince {
+    /// `5m`, `1h30m`, `30s` 形式の期間、またはRFC3339形式の時刻をパースする
+    ///
+    /// APIサーバーは0秒の `sinceSeconds` を受け付けないため、0の期間はエラーにする
+    fn parse(value: &str) -> Result<Self> {
+        match Self::parse_duration(value) {
+            Some(seconds) if 0 < seconds => return Ok(Self::Seconds(seconds)),
+            Some(_) => {
+                bail!(FilterError::Syntax(format!(
+                    "Invalid since value: {}. Use a positive duration (e.g. 30s, 5m, 1h30m).",
+                    value
+                )));
+            }
+            None => {}
+        }
+
+        if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+            return Ok(Self::Time(time.into()));
+        }
+
+        bail!(FilterError::Syntax(format!(
+            "Invalid since value: {}. Use a duration (e.g. 30s, 5m, 1h30m) or an RFC3339 timestamp.",
+            value
+        )))
+    }
+
+    fn parse_duration(value: &str) -> Option<i64> {
+        if value.is_empty() {
+            return None;
+        }
+
+        let mut seconds: i64 = 0;
+        let mut digits = String::new();
+
+        for c in value.chars() {
+            match c {
+                '0'..='9' => digits.push(c),
+                'h' | 'm' | 's' => {
+                    let n: i64 = digits.parse().ok()?;
+
+                    let unit = match c {
+                        'h' => 3600,
+                        'm' => 60,
+                        _ => 1,
+                    };
+
+                    seconds = seconds.checked_add(n.checked_mul(unit)?)?;
+
+                    digits.clear();
+                }
+                _ => return None,
+            }
+        }
+
+        if !digits.is_empty() {
+            return None;
+        }
+
+        Some(seconds)
+    }
+}
+
+impl std::fmt::Display for LogSince {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogSince::Seconds(seconds) => write!(f, "{}s", seconds),
+            LogSince::Time(time) => write!(f, "{}", time.to_rfc3339()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LabelSelector {
     Resource(RetrievableResource),
@@ -281,6 +729,15 @@ pub enum FilterAttribute<'a> {
     FieldSelector(Cow<'a, str>),
     IncludeLog(Cow<'a, str>),
     ExcludeLog(Cow<'a, str>),
+    Json(&'a str, Cow<'a, str>),
+    Since(Cow<'a, str>),
+    Tail(Cow<'a, str>),
+    Previous,
+    Pretty,
+    Reorder(Option<Cow<'a, str>>),
+    Highlight(Cow<'a, str>, Option<&'a str>),
+    And(Vec<FilterAttribute<'a>>),
+    Or(Vec<FilterAttribute<'a>>),
 }
 
 struct FilterAttributes;
@@ -305,3 +762,221 @@ impl<'a> From<SpecifiedResource<'a>> for FilterAttribute<'a> {
         Self::Resource(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn text(text: &str) -> LogContent<'_> {
+        LogContent { text, json: None }
+    }
+
+    #[rstest]
+    #[case("30s", LogSince::Seconds(30))]
+    #[case("5m", LogSince::Seconds(300))]
+    #[case("1h30m", LogSince::Seconds(5400))]
+    #[case("1h0m10s", LogSince::Seconds(3610))]
+    #[case(
+        "2024-01-01T00:00:00Z",
+        LogSince::Time(DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().into())
+    )]
+    #[case(
+        "2024-01-01T09:00:00+09:00",
+        LogSince::Time(DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().into())
+    )]
+    fn since(#[case] value: &str, #[case] expected: LogSince) {
+        assert_eq!(LogSince::parse(value).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("5")]
+    #[case("m")]
+    #[case("5d")]
+    #[case("2024-01-01")]
+    #[case("0s")]
+    #[case("0m")]
+    #[case("0h0m0s")]
+    fn since_error(#[case] value: &str) {
+        assert!(LogSince::parse(value).is_err());
+    }
+
+    #[test]
+    fn since_tail_previous() {
+        let filter = Filter::parse("pod:app since:10m tail:100 previous:").unwrap();
+
+        assert_eq!(filter.since, Some(LogSince::Seconds(600)));
+        assert_eq!(filter.tail_lines, Some(100));
+        assert!(filter.previous);
+    }
+
+    #[rstest]
+    #[case("deploy/app", None)]
+    #[case("deploy/app reorder:", Some(Duration::from_secs(1)))]
+    #[case("deploy/app reorder:3s", Some(Duration::from_secs(3)))]
+    fn reorder(#[case] query: &str, #[case] expected: Option<Duration>) {
+        let filter = Filter::parse(query).unwrap();
+
+        assert_eq!(filter.reorder_window, expected);
+    }
+
+    #[rstest]
+    #[case("reorder:0s")]
+    #[case("reorder:3")]
+    fn reorder_error(#[case] query: &str) {
+        assert!(Filter::parse(query).is_err());
+    }
+
+    #[test]
+    fn highlight() {
+        let filter = Filter::parse("pod:app highlight:error=red highlight:warn").unwrap();
+
+        let actual: Vec<_> = filter
+            .highlight
+            .unwrap()
+            .iter()
+            .map(|h| (h.regex.as_str().to_string(), h.color))
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("error".to_string(), HighlightColor::Red),
+                ("warn".to_string(), HighlightColor::Yellow),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_invalid_color() {
+        assert!(Filter::parse(r#"highlight:"error"=orange"#).is_err());
+    }
+
+    #[test]
+    fn plain_query_has_no_expression() {
+        let filter = Filter::parse("pod:api log:error log:panic").unwrap();
+
+        assert!(filter.expression.is_none());
+        assert_eq!(filter.include_log.map(|v| v.len()), Some(2));
+    }
+
+    #[rstest]
+    #[case("api-0", None, None, None)]
+    #[case("api-0", Some("app"), None, None)]
+    #[case("api-0", Some("app"), Some("error"), Some(true))]
+    #[case("api-0", Some("app"), Some("info"), Some(false))]
+    #[case("api-0", Some("istio-proxy"), None, Some(false))]
+    #[case("worker-0", Some("app"), Some("panic"), Some(true))]
+    #[case("db-0", None, None, Some(false))]
+    fn evaluate_expression(
+        #[case] pod: &str,
+        #[case] container: Option<&str>,
+        #[case] log: Option<&str>,
+        #[case] expected: Option<bool>,
+    ) {
+        let filter = Filter::parse(
+            "(pod:api or pod:worker) and !container:istio-proxy and (log:error or log:panic)",
+        )
+        .unwrap();
+
+        let expression = filter.expression.unwrap();
+
+        assert_eq!(expression.evaluate(pod, container, log.map(text)), expected);
+    }
+
+    #[test]
+    fn expression_with_plain_attributes() {
+        let filter = Filter::parse("label:app=api log:error or log:panic").unwrap();
+
+        assert!(filter.label_selector.is_some());
+        assert!(filter.include_log.is_none());
+        assert_eq!(
+            filter.expression.map(|expr| expr.to_string()),
+            Some("(include=error or include=panic)".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case("pod/app.v1", "app.v1", true)]
+    #[case("pod/app.v1", "app-v1", false)]
+    #[case("(pod/app.v1 or log:error)", "app-v1", false)]
+    fn specified_pod_is_exact_match(
+        #[case] query: &str,
+        #[case] pod: &str,
+        #[case] expected: bool,
+    ) {
+        let filter = Filter::parse(query).unwrap();
+
+        let actual = match filter.expression {
+            Some(expression) => expression.evaluate(pod, None, Some(text("info"))),
+            None => filter.pod.map(|re| re.is_match(pod)),
+        };
+
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[rstest]
+    #[case("label:app=api or log:error")]
+    #[case("(since:5m or log:error)")]
+    #[case("deployment/app or log:error")]
+    fn expression_error(#[case] query: &str) {
+        assert!(Filter::parse(query).is_err());
+    }
+
+    #[rstest]
+    #[case("level=error", r#"{"level":"error"}"#, true)]
+    #[case("level=error", r#"{"level":"info"}"#, false)]
+    #[case("level=error", r#"{"msg":"error"}"#, false)]
+    #[case("req.status=5..", r#"{"req":{"status":503}}"#, true)]
+    #[case("req.status=5..", r#"{"req":{"status":200}}"#, false)]
+    #[case("items.1.name=^b$", r#"{"items":[{"name":"a"},{"name":"b"}]}"#, true)]
+    #[case("ok=true", r#"{"ok":true}"#, true)]
+    #[case("level=error", "level=error", false)]
+    fn json_filter(#[case] query: &str, #[case] log: &str, #[case] expected: bool) {
+        let (path, regex) = query.split_once('=').unwrap();
+
+        let filter = JsonFilter::new(path, regex).unwrap();
+
+        let actual = serde_json::from_str(log).is_ok_and(|value| filter.is_match(&value));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn json_in_expression() {
+        let filter = Filter::parse("json:level=error or json:req.status=5..").unwrap();
+
+        let expression = filter.expression.unwrap();
+
+        let evaluate = |log: &str| {
+            let json = serde_json::from_str(log).ok();
+
+            expression.evaluate(
+                "api",
+                Some("app"),
+                Some(LogContent {
+                    text: log,
+                    json: json.as_ref(),
+                }),
+            )
+        };
+
+        assert_eq!(
+            evaluate(r#"{"level":"warn","req":{"status":500}}"#),
+            Some(true)
+        );
+        assert_eq!(evaluate(r#"{"level":"info"}"#), Some(false));
+        assert_eq!(evaluate("level=error"), Some(false));
+        assert!(expression.contains_json());
+    }
+
+    #[test]
+    fn pretty() {
+        let filter = Filter::parse("pod:app pretty:").unwrap();
+
+        assert!(filter.pretty);
+        assert_eq!(filter.to_string(), "pod=app pretty=true");
+    }
+}
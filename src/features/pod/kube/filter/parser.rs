@@ -2,22 +2,123 @@ use std::borrow::Cow;
 
 use nom::{
     branch::alt,
-    bytes::complete::{is_not, tag},
-    character::complete::{alphanumeric1, anychar, char, multispace0, multispace1},
-    combinator::{all_consuming, map, recognize, value, verify},
-    error::{ContextError, ParseError},
+    bytes::complete::{is_not, tag, tag_no_case},
+    character::complete::{alpha1, alphanumeric1, anychar, char, digit1, multispace0, multispace1},
+    combinator::{all_consuming, map, opt, recognize, value, verify},
+    error::{ContextError, ErrorKind, ParseError},
     multi::{fold_many0, many1_count, separated_list1},
-    sequence::{delimited, preceded, separated_pair},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
     IResult,
 };
 
-use super::{FilterAttribute, SpecifiedResource};
+use super::{FilterAttribute, HighlightColor, SpecifiedResource};
+
+/// `|`の後に続く場合にorとして扱う条件の接頭辞（組み合わせ可能な条件のみ）
+fn combinable_prefix<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, &'a str, E> {
+    recognize(pair(
+        opt(char('!')),
+        alt((
+            terminated(
+                alt((
+                    tag("pods"),
+                    tag("pod"),
+                    tag("po"),
+                    tag("p"),
+                    tag("containers"),
+                    tag("container"),
+                    tag("co"),
+                    tag("c"),
+                    tag("logs"),
+                    tag("log"),
+                    tag("lo"),
+                    tag("l"),
+                )),
+                char(':'),
+            ),
+            tag("json:"),
+        )),
+    ))(s)
+}
+
+/// 続く文字列に対応する`(`のない`)`があるか（括弧で囲まれたグループの中にいるか）を返す
+///
+/// クォートで囲まれた部分とエスケープされた文字は数えない
+fn in_group(s: &str) -> bool {
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in s.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match (quote, c) {
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return true,
+            (None, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    false
+}
 
 /// 空白文字を含まない文字列をパースする
+///
+/// 対応する`(`のない`)`はグループの終端として扱うため含めない。
+/// グループの中で`|`の後に条件が続く場合はorとして扱うため含めない。
+/// グループの外では既存のクエリの意味を変えないよう、正規表現の一部として扱う
 fn non_space<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     s: &'a str,
 ) -> IResult<&str, Cow<'_, str>, E> {
-    let (remaining, value) = verify(is_not(" \t\r\n"), |s: &str| !s.starts_with(['"', '\'']))(s)?;
+    let mut depth = 0_usize;
+    let mut escaped = false;
+    let mut end = s.len();
+
+    for (i, c) in s.char_indices() {
+        if c.is_ascii_whitespace() {
+            end = i;
+            break;
+        }
+
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match c {
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                end = i;
+                break;
+            }
+            ')' => depth -= 1,
+            '|' if depth == 0
+                && combinable_prefix::<E>(&s[i + 1..]).is_ok()
+                && in_group(&s[i + 1..]) =>
+            {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let (value, remaining) = s.split_at(end);
+
+    if value.is_empty() || value.starts_with(['"', '\'']) {
+        return Err(nom::Err::Error(E::from_error_kind(s, ErrorKind::IsNot)));
+    }
+
     Ok((remaining, Cow::Borrowed(value)))
 }
 
@@ -113,6 +214,16 @@ fn resource_name<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     recognize(many1_count(alt((alphanumeric1, tag("-"), tag(".")))))(s)
 }
 
+/// `req.status`のようにドット区切りのJSONフィールドのパスをパースする
+fn json_path<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&str, &str, E> {
+    recognize(separated_list1(
+        char('.'),
+        many1_count(alt((alphanumeric1, tag("_"), tag("-")))),
+    ))(s)
+}
+
 fn pod<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     s: &'a str,
 ) -> IResult<&'a str, FilterAttribute, E> {
@@ -195,6 +306,81 @@ fn field_selector<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     Ok((remaining, FilterAttribute::FieldSelector(value)))
 }
 
+fn json<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, (_, (path, value))) = separated_pair(
+        tag("json"),
+        char(':'),
+        separated_pair(json_path, char('='), regex),
+    )(s)?;
+    Ok((remaining, FilterAttribute::Json(path, value)))
+}
+
+fn since<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, (_, value)) = separated_pair(tag("since"), char(':'), non_space)(s)?;
+    Ok((remaining, FilterAttribute::Since(value)))
+}
+
+fn tail<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, (_, value)) = separated_pair(tag("tail"), char(':'), digit1)(s)?;
+    Ok((remaining, FilterAttribute::Tail(Cow::Borrowed(value))))
+}
+
+fn previous<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    value(FilterAttribute::Previous, tag("previous:"))(s)
+}
+
+fn pretty<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute<'a>, E> {
+    value(FilterAttribute::Pretty, tag("pretty:"))(s)
+}
+
+/// `<regex>=<color>` 形式の値から色を取り出す
+///
+/// 末尾が既知の色名でない場合は値全体を正規表現として扱う
+fn split_highlight_color(value: &str) -> (&str, Option<&str>) {
+    match value.rsplit_once('=') {
+        Some((regex, color)) if !regex.is_empty() && HighlightColor::parse(color).is_some() => {
+            (regex, Some(color))
+        }
+        _ => (value, None),
+    }
+}
+
+fn highlight<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, (value, color)) = preceded(
+        tag("highlight:"),
+        alt((
+            pair(quoted, opt(preceded(char('='), alpha1))),
+            map(non_space, |value| match value {
+                Cow::Borrowed(value) => {
+                    let (regex, color) = split_highlight_color(value);
+                    (Cow::Borrowed(regex), color)
+                }
+                Cow::Owned(value) => (Cow::Owned(value), None),
+            }),
+        )),
+    )(s)?;
+    Ok((remaining, FilterAttribute::Highlight(value, color)))
+}
+
+fn reorder<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, value) = preceded(tag("reorder:"), opt(non_space))(s)?;
+    Ok((remaining, FilterAttribute::Reorder(value)))
+}
+
 fn specified_daemonset<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     s: &'a str,
 ) -> IResult<&'a str, FilterAttribute, E> {
@@ -309,17 +495,99 @@ fn attribute<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
         exclude_container,
         include_log,
         exclude_log,
+        json,
+        alt((since, tail, previous, pretty, reorder)),
+        highlight,
     ))(s)?;
 
     Ok((remaining, value))
 }
 
+/// 複数の条件をまとめる
+///
+/// 条件が1つの場合はそのまま返す
+fn combine<'a>(
+    mut values: Vec<FilterAttribute<'a>>,
+    f: impl FnOnce(Vec<FilterAttribute<'a>>) -> FilterAttribute<'a>,
+) -> FilterAttribute<'a> {
+    if values.len() == 1 {
+        values.remove(0)
+    } else {
+        f(values)
+    }
+}
+
+/// 空白区切りの条件をまとめる
+///
+/// トップレベルと同じく、複数の`log:`はいずれかに一致すればよく、それ以外の条件は全て満たす必要がある
+fn combine_spaced(values: Vec<FilterAttribute<'_>>) -> FilterAttribute<'_> {
+    let position = values
+        .iter()
+        .position(|value| matches!(value, FilterAttribute::IncludeLog(_)));
+
+    let (logs, mut values): (Vec<_>, Vec<_>) = values
+        .into_iter()
+        .partition(|value| matches!(value, FilterAttribute::IncludeLog(_)));
+
+    if let Some(position) = position {
+        values.insert(position, combine(logs, FilterAttribute::Or));
+    }
+
+    combine(values, FilterAttribute::And)
+}
+
+/// 括弧で囲まれたグループをパースする
+///
+/// グループ内の空白区切りの条件はトップレベルと同じ規則でまとめる
+fn group<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, value) = delimited(
+        terminated(char('('), multispace0),
+        separated_list1(multispace1, or_expression),
+        preceded(multispace0, char(')')),
+    )(s)?;
+
+    Ok((remaining, combine_spaced(value)))
+}
+
+fn primary<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    alt((group, attribute))(s)
+}
+
+fn and_expression<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, value) = separated_list1(
+        delimited(multispace1, tag_no_case("and"), multispace1),
+        primary,
+    )(s)?;
+
+    Ok((remaining, combine(value, FilterAttribute::And)))
+}
+
+fn or_expression<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    s: &'a str,
+) -> IResult<&'a str, FilterAttribute, E> {
+    let (remaining, value) = separated_list1(
+        alt((
+            delimited(multispace1, alt((tag_no_case("or"), tag("|"))), multispace1),
+            tag("|"),
+        )),
+        and_expression,
+    )(s)?;
+
+    Ok((remaining, combine(value, FilterAttribute::Or)))
+}
+
 fn split_attributes<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     s: &'a str,
 ) -> IResult<&'a str, Vec<FilterAttribute>, E> {
     let (remaining, value) = delimited(
         multispace0,
-        separated_list1(multispace1, attribute),
+        separated_list1(multispace1, or_expression),
         multispace0,
     )(s)?;
 
@@ -443,6 +711,109 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    /// Json
+    #[rstest]
+    #[case("json:level=error", "level", "error")]
+    #[case("json:req.status=5..", "req.status", "5..")]
+    #[case("json:items.0.name='a b'", "items.0.name", "a b")]
+    #[case("json:http_status-code=^2", "http_status-code", "^2")]
+    fn json(#[case] query: &str, #[case] expected_path: &str, #[case] expected: &str) {
+        let (remaining, actual) = super::json::<Error<_>>(query).unwrap();
+
+        assert_eq!(
+            actual,
+            FilterAttribute::Json(expected_path, expected.into())
+        );
+        assert_eq!(remaining, "");
+    }
+
+    #[rstest]
+    #[case("json:level")]
+    #[case("json:=error")]
+    #[case("json:req..status=500")]
+    fn json_error(#[case] query: &str) {
+        let actual = super::json::<Error<_>>(query);
+
+        assert!(actual.is_err());
+    }
+
+    /// Since
+    #[rstest]
+    #[case("since:5m", "5m")]
+    #[case("since:1h30m", "1h30m")]
+    #[case("since:2024-01-01T00:00:00Z", "2024-01-01T00:00:00Z")]
+    fn since(#[case] query: &str, #[case] expected: &str) {
+        let (remaining, actual) = super::since::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Since(expected.into()));
+        assert_eq!(remaining, "");
+    }
+
+    /// Tail
+    #[rstest]
+    #[case("tail:0", "0")]
+    #[case("tail:100", "100")]
+    fn tail(#[case] query: &str, #[case] expected: &str) {
+        let (remaining, actual) = super::tail::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Tail(expected.into()));
+        assert_eq!(remaining, "");
+    }
+
+    #[rstest]
+    #[case("tail:")]
+    #[case("tail:-1")]
+    #[case("tail:abc")]
+    fn tail_error(#[case] query: &str) {
+        let actual = super::tail::<Error<_>>(query);
+
+        assert!(actual.is_err());
+    }
+
+    /// Previous
+    #[test]
+    fn previous() {
+        let (remaining, actual) = super::previous::<Error<_>>("previous:").unwrap();
+
+        assert_eq!(actual, FilterAttribute::Previous);
+        assert_eq!(remaining, "");
+    }
+
+    /// Highlight
+    #[rstest]
+    #[case("highlight:error", "error", None)]
+    #[case("highlight:error=red", "error", Some("red"))]
+    #[case("highlight:a=b", "a=b", None)]
+    #[case("highlight:a=b=cyan", "a=b", Some("cyan"))]
+    #[case("highlight:=red", "=red", None)]
+    #[case(r#"highlight:"time out"=yellow"#, "time out", Some("yellow"))]
+    #[case(r#"highlight:"a=red""#, "a=red", None)]
+    fn highlight(#[case] query: &str, #[case] expected: &str, #[case] color: Option<&str>) {
+        let (remaining, actual) = super::highlight::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Highlight(expected.into(), color));
+        assert_eq!(remaining, "");
+    }
+
+    /// Reorder
+    #[rstest]
+    #[case("reorder:", None)]
+    #[case("reorder:3s", Some("3s"))]
+    fn reorder(#[case] query: &str, #[case] expected: Option<&str>) {
+        let (remaining, actual) = super::reorder::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, FilterAttribute::Reorder(expected.map(Into::into)));
+        assert_eq!(remaining, "");
+    }
+
+    #[test]
+    fn reorder_without_window_followed_by_attribute() {
+        let (remaining, actual) = super::reorder::<Error<_>>("reorder: log:hoge").unwrap();
+
+        assert_eq!(actual, FilterAttribute::Reorder(None));
+        assert_eq!(remaining, " log:hoge");
+    }
+
     /// Specified resoruces
 
     /// DaemonSet
@@ -651,6 +1022,13 @@ mod tests {
     #[case("!log:hoge", FilterAttribute::ExcludeLog("hoge".into()))]
     #[case("labels:foo=bar", FilterAttribute::LabelSelector("foo=bar".into()))]
     #[case("fields:foo=bar", FilterAttribute::FieldSelector("foo=bar".into()))]
+    #[case("json:level=error", FilterAttribute::Json("level", "error".into()))]
+    #[case("since:5m", FilterAttribute::Since("5m".into()))]
+    #[case("tail:10", FilterAttribute::Tail("10".into()))]
+    #[case("previous:", FilterAttribute::Previous)]
+    #[case("pretty:", FilterAttribute::Pretty)]
+    #[case("reorder:2s", FilterAttribute::Reorder(Some("2s".into())))]
+    #[case("highlight:error=red", FilterAttribute::Highlight("error".into(), Some("red")))]
     #[case("daemonset/app", FilterAttribute::Resource(SpecifiedResource::DaemonSet("app")))]
     #[case("deployment/app", FilterAttribute::Resource(SpecifiedResource::Deployment("app")))]
     #[case("job/app", FilterAttribute::Resource(SpecifiedResource::Job("app")))]
@@ -677,6 +1055,9 @@ mod tests {
             "!log:hoge",
             "labels:foo=bar",
             "fields:foo=bar",
+            "since:5m",
+            "tail:10",
+            "previous:",
             "daemonset/app",
             "deployment/app",
             "job/app",
@@ -699,6 +1080,9 @@ mod tests {
             FilterAttribute::ExcludeLog("hoge".into()),
             FilterAttribute::LabelSelector("foo=bar".into()),
             FilterAttribute::FieldSelector("foo=bar".into()),
+            FilterAttribute::Since("5m".into()),
+            FilterAttribute::Tail("10".into()),
+            FilterAttribute::Previous,
             FilterAttribute::Resource(SpecifiedResource::DaemonSet("app")),
             FilterAttribute::Resource(SpecifiedResource::Deployment("app")),
             FilterAttribute::Resource(SpecifiedResource::Job("app")),
@@ -735,10 +1119,156 @@ mod tests {
         assert_eq!(remaining, "");
     }
 
+    #[rustfmt::skip]
+    #[rstest]
+    #[case("foo)", "foo", ")")]
+    #[case("(foo|bar))", "(foo|bar)", ")")]
+    #[case(r"foo\))", r"foo\)", ")")]
+    #[case("foo) bar", "foo", ") bar")]
+    #[case("foo|bar", "foo|bar", "")]
+    #[case("foo|pod:bar", "foo|pod:bar", "")]
+    #[case("foo|pod:bar)", "foo", "|pod:bar)")]
+    #[case("foo|pod:'(bar'", "foo|pod:'(bar'", "")]
+    #[case("(foo|pod:bar)", "(foo|pod:bar)", "")]
+    fn unquoted_with_paren(#[case] query: &str, #[case] expected: &str, #[case] expected_remaining: &str) {
+        let (remaining, actual) = super::unquoted::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(remaining, expected_remaining);
+    }
+
+    #[rustfmt::skip]
+    #[rstest]
+    #[case(
+        "log:error or log:panic",
+        FilterAttribute::Or(vec![
+            FilterAttribute::IncludeLog("error".into()),
+            FilterAttribute::IncludeLog("panic".into()),
+        ])
+    )]
+    #[case(
+        "pod:api AND !log:debug",
+        FilterAttribute::And(vec![
+            FilterAttribute::Pod("api".into()),
+            FilterAttribute::ExcludeLog("debug".into()),
+        ])
+    )]
+    #[case(
+        "pod:api or pod:worker and log:error",
+        FilterAttribute::Or(vec![
+            FilterAttribute::Pod("api".into()),
+            FilterAttribute::And(vec![
+                FilterAttribute::Pod("worker".into()),
+                FilterAttribute::IncludeLog("error".into()),
+            ]),
+        ])
+    )]
+    #[case(
+        "(pod:api or pod:worker) and log:error",
+        FilterAttribute::And(vec![
+            FilterAttribute::Or(vec![
+                FilterAttribute::Pod("api".into()),
+                FilterAttribute::Pod("worker".into()),
+            ]),
+            FilterAttribute::IncludeLog("error".into()),
+        ])
+    )]
+    #[case(
+        "( pod:api !container:istio-proxy ) or log:'(error)'",
+        FilterAttribute::Or(vec![
+            FilterAttribute::And(vec![
+                FilterAttribute::Pod("api".into()),
+                FilterAttribute::ExcludeContainer("istio-proxy".into()),
+            ]),
+            FilterAttribute::IncludeLog("(error)".into()),
+        ])
+    )]
+    #[case("(pod:api)", FilterAttribute::Pod("api".into()))]
+    #[case(
+        "(pod:api|!log:debug|json:level=error)",
+        FilterAttribute::Or(vec![
+            FilterAttribute::Pod("api".into()),
+            FilterAttribute::ExcludeLog("debug".into()),
+            FilterAttribute::Json("level", "error".into()),
+        ])
+    )]
+    #[case(
+        "pod:api | log:error",
+        FilterAttribute::Or(vec![
+            FilterAttribute::Pod("api".into()),
+            FilterAttribute::IncludeLog("error".into()),
+        ])
+    )]
+    #[case("log:error|warn", FilterAttribute::IncludeLog("error|warn".into()))]
+    #[case("log:a|pod:b", FilterAttribute::IncludeLog("a|pod:b".into()))]
+    #[case("log:a|l:b", FilterAttribute::IncludeLog("a|l:b".into()))]
+
+    #[case(
+        "(pod:api log:error log:panic)",
+        FilterAttribute::And(vec![
+            FilterAttribute::Pod("api".into()),
+            FilterAttribute::Or(vec![
+                FilterAttribute::IncludeLog("error".into()),
+                FilterAttribute::IncludeLog("panic".into()),
+            ]),
+        ])
+    )]
+    fn or_expression(#[case] query: &str, #[case] expected: FilterAttribute) {
+        let (remaining, actual) = super::or_expression::<Error<_>>(query).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(remaining, "");
+    }
+
+    #[test]
+    fn parse_attributes_with_group() {
+        let query = "(pod:api|pod:worker) !container:istio-proxy (log:error or log:panic)";
+
+        let (remaining, actual) = super::parse_attributes::<Error<_>>(query).unwrap();
+
+        let expected = vec![
+            FilterAttribute::Or(vec![
+                FilterAttribute::Pod("api".into()),
+                FilterAttribute::Pod("worker".into()),
+            ]),
+            FilterAttribute::ExcludeContainer("istio-proxy".into()),
+            FilterAttribute::Or(vec![
+                FilterAttribute::IncludeLog("error".into()),
+                FilterAttribute::IncludeLog("panic".into()),
+            ]),
+        ];
+
+        assert_eq!(actual, expected);
+        assert_eq!(remaining, "");
+    }
+
+    #[test]
+    fn グループの外の区切りのない縦棒は正規表現の一部として扱う() {
+        let query = "log:a|pod:b (pod:c|pod:d)";
+
+        let (remaining, actual) = super::parse_attributes::<Error<_>>(query).unwrap();
+
+        let expected = vec![
+            FilterAttribute::IncludeLog("a|pod:b".into()),
+            FilterAttribute::Or(vec![
+                FilterAttribute::Pod("c".into()),
+                FilterAttribute::Pod("d".into()),
+            ]),
+        ];
+
+        assert_eq!(actual, expected);
+        assert_eq!(remaining, "");
+    }
+
     #[rstest]
     #[case("     ")]
     #[case("")]
     #[case("hoge:hoge")]
+    #[case("(pod:hoge")]
+    #[case("pod:hoge)")]
+    #[case("pod:hoge or")]
+    #[case("and pod:hoge")]
+    #[case("()")]
     fn parse_error(#[case] query: &str) {
         let actual = super::parse_attributes::<Error<_>>(query);
 
@@ -1,8 +1,11 @@
+mod highlight;
+mod json_log;
 mod log_collector;
+mod log_file;
 mod log_streamer;
 mod pod_watcher;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use anyhow::{anyhow, bail, Result};
 use async_trait::async_trait;
@@ -14,7 +17,10 @@ use k8s_openapi::api::{
     core::v1::Service,
 };
 use kube::Api;
-use tokio::task::{JoinError, JoinHandle};
+use tokio::{
+    sync::watch,
+    task::{JoinError, JoinHandle},
+};
 
 use crate::{
     kube::{context::Namespace, KubeClient},
@@ -23,8 +29,11 @@ use crate::{
     workers::kube::{AbortWorker, Worker},
 };
 
-pub use self::log_streamer::LogPrefixType;
-pub use super::filter::{Filter, LabelSelector, RetrievableResource};
+pub use self::{log_file::save_log, log_streamer::LogPrefixType};
+pub use super::filter::{
+    Filter, FilterExpression, HighlightColor, JsonFilter, LabelSelector, LogContent, LogHighlight,
+    LogSince, RetrievableResource,
+};
 
 use self::{
     log_collector::{LogBuffer, LogCollector},
@@ -42,11 +51,17 @@ macro_rules! send_response {
     };
 }
 
+/// Podごとのペインを識別するID
+pub fn log_pane_id(namespace: &str, pod_name: &str) -> String {
+    format!("{}/{}", namespace, pod_name)
+}
+
 #[derive(Debug, Clone)]
 pub struct LogConfig {
     namespaces: Namespace,
     query: String,
     prefix_type: LogPrefixType,
+    split_panes: bool,
 }
 
 impl LogConfig {
@@ -55,8 +70,15 @@ impl LogConfig {
             namespaces,
             query,
             prefix_type,
+            split_panes: false,
         }
     }
+
+    /// Podごとにペインを分けてログを表示する
+    pub fn split_panes(mut self, split_panes: bool) -> Self {
+        self.split_panes = split_panes;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -64,11 +86,23 @@ pub struct LogWorker {
     tx: Sender<Message>,
     client: KubeClient,
     config: LogConfig,
+    closed_panes: watch::Receiver<HashSet<String>>,
 }
 
 impl LogWorker {
     pub fn new(tx: Sender<Message>, client: KubeClient, config: LogConfig) -> Self {
-        Self { tx, client, config }
+        Self {
+            tx,
+            client,
+            config,
+            closed_panes: watch::channel(HashSet::default()).1,
+        }
+    }
+
+    /// ユーザーが閉じたペインのIDを受け取り、そのPodのログの取得をやめる
+    pub fn closed_panes(mut self, closed_panes: watch::Receiver<HashSet<String>>) -> Self {
+        self.closed_panes = closed_panes;
+        self
     }
 
     async fn spawn_tasks(&self, filter: Filter) -> Result<LogHandle> {
@@ -81,6 +115,13 @@ impl LogWorker {
 
         let log_buffer = LogBuffer::default();
 
+        // ペインごとにPod名を表示するため、ログにはコンテナ名のみ付与する
+        let prefix_type = if self.config.split_panes {
+            LogPrefixType::OnlyContainer
+        } else {
+            self.config.prefix_type
+        };
+
         for namespace in namespaces {
             // retrieve label selector
             let label_selector = if let Some(value) = &filter.label_selector {
@@ -103,15 +144,26 @@ impl LogWorker {
                 exclude_pod: filter.exclude_pod.clone(),
                 container: filter.container.clone(),
                 exclude_container: filter.exclude_container.clone(),
+                expression: filter.expression.clone(),
             })
             .selector(PodWatcherSelector {
                 label_selector,
                 field_selector: filter.field_selector.clone(),
             })
+            .split_panes(self.config.split_panes)
+            .closed_panes(self.closed_panes.clone())
             .log_streamer_options(LogStreamerOptions {
-                prefix_type: self.config.prefix_type,
+                prefix_type,
                 include_log: filter.include_log.clone(),
                 exclude_log: filter.exclude_log.clone(),
+                json: filter.json.clone(),
+                since: filter.since,
+                tail_lines: filter.tail_lines,
+                previous: filter.previous,
+                pretty: filter.pretty,
+                expression: filter.expression.clone(),
+                split_panes: self.config.split_panes,
+                highlight: filter.highlight.clone(),
             });
 
             pod_watchers.push(pod_watcher);
@@ -120,7 +172,10 @@ impl LogWorker {
         let mut handles: Vec<_> = pod_watchers.iter().map(PodWatcher::spawn).collect();
 
         // collector
-        let collector_handle = LogCollector::new(self.tx.clone(), log_buffer.clone()).spawn();
+        let collector_handle = LogCollector::new(self.tx.clone(), log_buffer.clone())
+            .reorder_window(filter.reorder_window)
+            .split_panes(self.config.split_panes)
+            .spawn();
 
         handles.push(collector_handle);
 
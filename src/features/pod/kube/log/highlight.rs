@@ -0,0 +1,115 @@
+use std::ops::Range;
+
+use crate::ansi::{self, strip_ansi};
+
+use super::{HighlightColor, LogHighlight};
+
+/// ハイライト規則にマッチした部分を背景色で装飾する
+///
+/// マッチした範囲が重なる場合は先に開始する範囲を優先し、
+/// 開始位置が同じ場合は先に指定した規則を優先する
+pub fn apply(content: &str, highlights: &[LogHighlight]) -> String {
+    let plain = strip_ansi(content);
+
+    let mut matches: Vec<(Range<usize>, &str)> = highlights
+        .iter()
+        .flat_map(|highlight| {
+            let sgr = sgr(highlight.color);
+
+            highlight
+                .regex
+                .find_iter(&plain)
+                .filter(|m| !m.is_empty())
+                .map(move |m| (m.range(), sgr))
+        })
+        .collect();
+
+    if matches.is_empty() {
+        return content.to_string();
+    }
+
+    matches.sort_by_key(|(range, _)| range.start);
+
+    let mut ranges: Vec<(Range<usize>, &str)> = Vec::with_capacity(matches.len());
+
+    for (range, sgr) in matches {
+        if ranges
+            .last()
+            .is_some_and(|(last, _)| range.start < last.end)
+        {
+            continue;
+        }
+
+        ranges.push((range, sgr));
+    }
+
+    ansi::highlight(content, &ranges)
+}
+
+/// 背景色と、背景色に合わせた文字色
+fn sgr(color: HighlightColor) -> &'static str {
+    match color {
+        HighlightColor::Red => "30;41",
+        HighlightColor::Green => "30;42",
+        HighlightColor::Yellow => "30;43",
+        HighlightColor::Blue => "97;44",
+        HighlightColor::Magenta => "97;45",
+        HighlightColor::Cyan => "30;46",
+        HighlightColor::White => "30;47",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+
+    use super::*;
+
+    fn highlight(regex: &str, color: HighlightColor) -> LogHighlight {
+        LogHighlight {
+            regex: Regex::new(regex).unwrap(),
+            color,
+        }
+    }
+
+    #[test]
+    fn マッチした部分を装飾する() {
+        let actual = apply(
+            "GET /api 500 error",
+            &[
+                highlight("error", HighlightColor::Red),
+                highlight(r"\b5\d\d\b", HighlightColor::Yellow),
+            ],
+        );
+
+        assert_eq!(
+            actual,
+            "GET /api \x1b[30;43m500\x1b[0m \x1b[30;41merror\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn 重なる範囲は先に開始する範囲を優先する() {
+        let actual = apply(
+            "connection timeout",
+            &[
+                highlight("timeout", HighlightColor::Red),
+                highlight("connection time", HighlightColor::Cyan),
+            ],
+        );
+
+        assert_eq!(actual, "\x1b[30;46mconnection time\x1b[0mout");
+    }
+
+    #[test]
+    fn マッチしない場合はそのまま返す() {
+        assert_eq!(
+            apply(
+                "\x1b[33minfo\x1b[39m",
+                &[highlight("error", HighlightColor::Red)]
+            ),
+            "\x1b[33minfo\x1b[39m"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use serde_json::{Map, Value};
+
+use crate::workers::kube::color::fg::Color;
+
+const TIMESTAMP_KEYS: [&str; 4] = ["ts", "time", "timestamp", "@timestamp"];
+
+const LEVEL_KEYS: [&str; 3] = ["level", "lvl", "severity"];
+
+const MESSAGE_KEYS: [&str; 2] = ["msg", "message"];
+
+/// JSONオブジェクト形式のログをパースする
+pub fn parse(s: &str) -> Option<Value> {
+    if !s.trim_start().starts_with('{') {
+        return None;
+    }
+
+    serde_json::from_str(s).ok().filter(Value::is_object)
+}
+
+/// JSON形式のログを `<ts> <LEVEL> <msg> key=value ...` の形式に整形する
+///
+/// level、msgのどちらも含まない場合は構造化ログとみなさずNoneを返す
+pub fn format(value: Value) -> Option<String> {
+    let Value::Object(mut map) = value else {
+        return None;
+    };
+
+    let ts = remove_first(&mut map, &TIMESTAMP_KEYS);
+    let level = remove_first(&mut map, &LEVEL_KEYS);
+    let msg = remove_first(&mut map, &MESSAGE_KEYS);
+
+    if level.is_none() && msg.is_none() {
+        return None;
+    }
+
+    let mut buf = Vec::new();
+
+    if let Some(ts) = ts {
+        buf.push(Color::DarkGray.wrap(to_plain_string(&ts)));
+    }
+
+    if let Some(level) = level {
+        let level = to_plain_string(&level).to_uppercase();
+
+        buf.push(level_color(&level).wrap(level));
+    }
+
+    if let Some(msg) = msg {
+        buf.push(to_plain_string(&msg));
+    }
+
+    for (key, value) in map {
+        buf.push(format!(
+            "{}={}",
+            Color::Cyan.wrap(key),
+            to_field_string(&value)
+        ));
+    }
+
+    Some(buf.join(" "))
+}
+
+fn remove_first(map: &mut Map<String, Value>, keys: &[&str]) -> Option<Value> {
+    keys.iter().find_map(|key| map.remove(*key))
+}
+
+fn level_color(level: &str) -> Color {
+    match level {
+        "FATAL" | "PANIC" | "CRITICAL" | "CRIT" | "ERROR" | "ERR" => Color::LightRed,
+        "WARNING" | "WARN" => Color::LightYellow,
+        "INFO" | "NOTICE" => Color::LightGreen,
+        "DEBUG" | "TRACE" => Color::LightBlue,
+        _ => Color::White,
+    }
+}
+
+fn to_plain_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// 空白を含む文字列はJSON表記（ダブルクォート付き）で出力する
+fn to_field_string(value: &Value) -> String {
+    match value {
+        Value::String(s) if !s.is_empty() && !s.contains(char::is_whitespace) => s.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"{"level":"info"}"#, true)]
+    #[case(r#"  {"level":"info"}"#, true)]
+    #[case(r#"["level","info"]"#, false)]
+    #[case(r#"{"level":"info""#, false)]
+    #[case("level=info", false)]
+    fn parse(#[case] log: &str, #[case] expected: bool) {
+        assert_eq!(super::parse(log).is_some(), expected);
+    }
+
+    #[test]
+    fn format() {
+        let value = super::parse(
+            r#"{"ts":"2024-01-01T00:00:00Z","level":"error","msg":"failed","req":{"status":500},"path":"/a b","user":"foo"}"#,
+        )
+        .unwrap();
+
+        let actual = super::format(value).unwrap();
+
+        let expected = [
+            "\x1b[90m2024-01-01T00:00:00Z\x1b[39m",
+            "\x1b[91mERROR\x1b[39m",
+            "failed",
+            "\x1b[36mpath\x1b[39m=\"/a b\"",
+            "\x1b[36mreq\x1b[39m={\"status\":500}",
+            "\x1b[36muser\x1b[39m=foo",
+        ]
+        .join(" ");
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn format_without_level_and_msg() {
+        let value = super::parse(r#"{"ts":"2024-01-01T00:00:00Z","foo":"bar"}"#).unwrap();
+
+        assert!(super::format(value).is_none());
+    }
+}
@@ -1,22 +1,75 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use crossbeam::channel::Sender;
-use tokio::{sync::Mutex, time};
+use tokio::{
+    sync::Mutex,
+    time::{self, Duration, Instant},
+};
 
-use crate::{message::Message, send_response, workers::kube::Worker};
+use crate::{
+    features::pod::message::{LogEntry, LogMessage},
+    message::Message,
+    send_response,
+    workers::kube::Worker,
+};
 
-pub type LogBuffer = Arc<Mutex<Vec<String>>>;
+pub type LogBuffer = Arc<Mutex<Vec<LogLine>>>;
+
+/// 出力するログ行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// 並び替えに使うタイムスタンプ
+    ///
+    /// Noneの行は並び替えの対象外として受信後すぐに出力する
+    pub timestamp: Option<DateTime<Utc>>,
+    /// 出力先のペイン（ペイン分割時のみ）
+    pub pane: Option<Arc<str>>,
+    /// ログ本文の行か
+    ///
+    /// 開始・終了の表示は並び替えにタイムスタンプを使うが、タイムスタンプを表示しない
+    pub is_log: bool,
+    pub content: String,
+}
+
+impl LogLine {
+    fn into_entry(self) -> LogEntry {
+        LogEntry {
+            timestamp: self.timestamp.filter(|_| self.is_log),
+            content: self.content,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct LogCollector {
     tx: Sender<Message>,
     buffer: LogBuffer,
+    reorder_window: Option<Duration>,
+    split_panes: bool,
 }
 
 impl LogCollector {
     pub fn new(tx: Sender<Message>, buffer: LogBuffer) -> Self {
-        Self { tx, buffer }
+        Self {
+            tx,
+            buffer,
+            reorder_window: None,
+            split_panes: false,
+        }
+    }
+
+    /// ログをペインごとにまとめて送信する
+    pub fn split_panes(mut self, split_panes: bool) -> Self {
+        self.split_panes = split_panes;
+        self
+    }
+
+    /// 指定した時間だけログを保持し、タイムスタンプ順に並び替えて出力する
+    pub fn reorder_window(mut self, reorder_window: Option<Duration>) -> Self {
+        self.reorder_window = reorder_window;
+        self
     }
 }
 
@@ -27,14 +80,238 @@ impl Worker for LogCollector {
     async fn run(&self) -> Self::Output {
         let mut interval = tokio::time::interval(time::Duration::from_millis(200));
 
+        let mut reorder_buffer = self.reorder_window.map(ReorderBuffer::new);
+
         loop {
             interval.tick().await;
 
-            let mut buf = self.buffer.lock().await;
+            let lines = std::mem::take(&mut *self.buffer.lock().await);
+
+            let lines = if let Some(reorder_buffer) = &mut reorder_buffer {
+                let now = Instant::now();
+
+                reorder_buffer.push(lines, now);
+                reorder_buffer.pop_ready(now)
+            } else {
+                lines
+            };
 
-            if !buf.is_empty() {
-                send_response!(self.tx, Ok(std::mem::take(&mut buf)));
+            if lines.is_empty() {
+                continue;
             }
+
+            if self.split_panes {
+                self.tx
+                    .send(LogMessage::PaneResponse(group_by_pane(lines)).into())
+                    .expect("Failed to send LogMessage::PaneResponse");
+            } else {
+                send_response!(
+                    self.tx,
+                    Ok(lines.into_iter().map(LogLine::into_entry).collect())
+                );
+            }
+        }
+    }
+}
+
+/// ペインごとにログをまとめる
+///
+/// ペインは最初に出現した順に並べ、ペイン内のログは受信順を保つ
+fn group_by_pane(lines: Vec<LogLine>) -> Vec<(String, Vec<LogEntry>)> {
+    let mut panes: Vec<(String, Vec<LogEntry>)> = Vec::new();
+
+    for line in lines {
+        let pane = line.pane.as_deref().unwrap_or_default().to_string();
+
+        if let Some((_, entries)) = panes.iter_mut().find(|(id, _)| *id == pane) {
+            entries.push(line.into_entry());
+        } else {
+            panes.push((pane, vec![line.into_entry()]));
+        }
+    }
+
+    panes
+}
+
+/// 複数のストリームから受信したログ行をタイムスタンプ順に並び替える
+///
+/// 受信してからwindowが経過した行を出力対象とし、
+/// 出力対象の行と、それより古いタイムスタンプを持つ行をまとめて出力する
+#[derive(Debug)]
+struct ReorderBuffer {
+    window: Duration,
+    pending: Vec<(Instant, DateTime<Utc>, LogLine)>,
+    immediate: Vec<LogLine>,
+}
+
+impl ReorderBuffer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Vec::new(),
+            immediate: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, lines: Vec<LogLine>, now: Instant) {
+        for line in lines {
+            match line.timestamp {
+                Some(timestamp) => self.pending.push((now, timestamp, line)),
+                None => self.immediate.push(line),
+            }
+        }
+    }
+
+    fn pop_ready(&mut self, now: Instant) -> Vec<LogLine> {
+        let mut ready = std::mem::take(&mut self.immediate);
+
+        let Some(watermark) = self
+            .pending
+            .iter()
+            .filter(|(received, _, _)| self.window <= now.saturating_duration_since(*received))
+            .map(|(_, timestamp, _)| *timestamp)
+            .max()
+        else {
+            return ready;
+        };
+
+        // 同じタイムスタンプの行は受信順を保つ
+        self.pending.sort_by_key(|(_, timestamp, _)| *timestamp);
+
+        let index = self
+            .pending
+            .partition_point(|(_, timestamp, _)| *timestamp <= watermark);
+
+        ready.extend(self.pending.drain(..index).map(|(_, _, line)| line));
+
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn line(timestamp: Option<&str>, content: &str) -> LogLine {
+        LogLine {
+            timestamp: timestamp.map(|ts| DateTime::parse_from_rfc3339(ts).unwrap().into()),
+            pane: None,
+            is_log: true,
+            content: content.to_string(),
         }
     }
+
+    fn contents(lines: Vec<LogLine>) -> Vec<String> {
+        lines.into_iter().map(|line| line.content).collect()
+    }
+
+    #[test]
+    fn ペインごとにログをまとめる() {
+        let pane_line = |pane: &str, content: &str| LogLine {
+            timestamp: None,
+            pane: Some(pane.into()),
+            is_log: true,
+            content: content.to_string(),
+        };
+
+        let actual = group_by_pane(vec![
+            pane_line("ns/b", "b-1"),
+            pane_line("ns/a", "a-1"),
+            pane_line("ns/b", "b-2"),
+        ]);
+
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "ns/b".to_string(),
+                    vec![LogEntry::new("b-1"), LogEntry::new("b-2")]
+                ),
+                ("ns/a".to_string(), vec![LogEntry::new("a-1")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn 保持期間が経過した行をタイムスタンプ順に出力する() {
+        let mut buffer = ReorderBuffer::new(Duration::from_secs(1));
+
+        let start = Instant::now();
+
+        buffer.push(
+            vec![
+                line(Some("2024-01-01T00:00:02Z"), "a-2"),
+                line(Some("2024-01-01T00:00:03Z"), "a-3"),
+            ],
+            start,
+        );
+
+        buffer.push(
+            vec![
+                line(Some("2024-01-01T00:00:01Z"), "b-1"),
+                line(Some("2024-01-01T00:00:04Z"), "b-4"),
+            ],
+            start + Duration::from_millis(500),
+        );
+
+        assert!(buffer
+            .pop_ready(start + Duration::from_millis(500))
+            .is_empty());
+
+        // a-3までが出力対象となり、それより古いb-1も合わせて出力する
+        assert_eq!(
+            contents(buffer.pop_ready(start + Duration::from_secs(1))),
+            vec!["b-1", "a-2", "a-3"]
+        );
+
+        assert_eq!(
+            contents(buffer.pop_ready(start + Duration::from_millis(1500))),
+            vec!["b-4"]
+        );
+    }
+
+    #[test]
+    fn 同じタイムスタンプの行は受信順に出力する() {
+        let mut buffer = ReorderBuffer::new(Duration::from_secs(1));
+
+        let start = Instant::now();
+
+        buffer.push(
+            vec![
+                line(Some("2024-01-01T00:00:01Z"), "a-1"),
+                line(Some("2024-01-01T00:00:00Z"), "b-0"),
+                line(Some("2024-01-01T00:00:01Z"), "b-1"),
+                line(Some("2024-01-01T00:00:01Z"), "a-1'"),
+            ],
+            start,
+        );
+
+        assert_eq!(
+            contents(buffer.pop_ready(start + Duration::from_secs(1))),
+            vec!["b-0", "a-1", "b-1", "a-1'"]
+        );
+    }
+
+    #[test]
+    fn タイムスタンプのない行はすぐに出力する() {
+        let mut buffer = ReorderBuffer::new(Duration::from_secs(1));
+
+        let start = Instant::now();
+
+        buffer.push(
+            vec![
+                line(None, "+ app"),
+                line(Some("2024-01-01T00:00:00Z"), "app-0"),
+            ],
+            start,
+        );
+
+        assert_eq!(contents(buffer.pop_ready(start)), vec!["+ app"]);
+
+        assert_eq!(
+            contents(buffer.pop_ready(start + Duration::from_secs(1))),
+            vec!["app-0"]
+        );
+    }
 }
@@ -0,0 +1,81 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write as _},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result};
+
+use crate::{
+    features::pod::message::{LogSaveRequest, LogSaveResponse},
+    logger,
+};
+
+/// ログをファイルに書き込む
+///
+/// 相対パスはカレントディレクトリからのパスとして扱い、結果には絶対パスを返す
+pub fn save_log(req: LogSaveRequest) -> LogSaveResponse {
+    let LogSaveRequest { path, lines, raw } = req;
+
+    let path = absolute_path(path);
+
+    let result = write_lines(&path, &lines);
+
+    match &result {
+        Ok(_) => logger!(info, "Log saved to {}", path.display()),
+        Err(err) => logger!(error, "Failed to save log to {}: {:?}", path.display(), err),
+    }
+
+    LogSaveResponse {
+        path,
+        lines: lines.len(),
+        raw,
+        result,
+    }
+}
+
+fn absolute_path(path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        return path;
+    }
+
+    match std::env::current_dir() {
+        Ok(dir) => dir.join(path),
+        Err(_) => path,
+    }
+}
+
+fn write_lines(path: &Path, lines: &[String]) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+    let mut writer = BufWriter::new(file);
+
+    for line in lines {
+        writeln!(writer, "{}", line)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn 相対パスはカレントディレクトリからのパスにする() {
+        let actual = absolute_path(PathBuf::from("kubetui.log"));
+
+        assert_eq!(actual, std::env::current_dir().unwrap().join("kubetui.log"));
+    }
+
+    #[test]
+    fn 絶対パスはそのまま使う() {
+        let actual = absolute_path(PathBuf::from("/tmp/kubetui.log"));
+
+        assert_eq!(actual, PathBuf::from("/tmp/kubetui.log"));
+    }
+}
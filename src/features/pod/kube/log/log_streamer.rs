@@ -14,7 +14,7 @@ use futures::{AsyncBufReadExt, TryStreamExt};
 use k8s_openapi::api::core::v1::Pod;
 use kube::{api::LogParams, Api};
 use regex::Regex;
-use tokio::time;
+use tokio::time::{self, Duration};
 
 use crate::{
     kube::KubeClient,
@@ -22,7 +22,11 @@ use crate::{
     workers::kube::{color::fg::Color, AbortWorker},
 };
 
-use super::log_collector::LogBuffer;
+use super::{
+    highlight, json_log,
+    log_collector::{LogBuffer, LogLine},
+    log_pane_id, FilterExpression, JsonFilter, LogContent, LogHighlight, LogSince,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum LogPrefixType {
@@ -75,6 +79,17 @@ pub struct LogStreamerOptions {
     pub prefix_type: LogPrefixType,
     pub include_log: Option<Vec<Regex>>,
     pub exclude_log: Option<Vec<Regex>>,
+    pub json: Option<Vec<JsonFilter>>,
+    pub since: Option<LogSince>,
+    pub tail_lines: Option<i64>,
+    /// 直前に終了したコンテナのログを取得する
+    pub previous: bool,
+    /// JSON形式のログを整形して表示する
+    pub pretty: bool,
+    pub expression: Option<FilterExpression>,
+    /// Podごとのペインに出力する
+    pub split_panes: bool,
+    pub highlight: Option<Vec<LogHighlight>>,
 }
 
 #[derive(Clone)]
@@ -96,31 +111,63 @@ pub struct LogStreamer {
 #[async_trait]
 impl AbortWorker for LogStreamer {
     async fn run(&self) {
-        let mut interval = tokio::time::interval(time::Duration::from_secs(3));
+        let prefix = self.log_prefix();
 
-        let mut last_timestamp: Option<DateTime<Utc>> = None;
+        let pane = self.pane();
 
-        let prefix = self.log_prefix();
+        let mut cursor = LogCursor::default();
+
+        let mut backoff = Backoff::default();
 
-        self.send_started_message().await;
+        self.send_started_message(&pane).await;
 
         loop {
-            interval.tick().await;
+            let result = self.stream(&prefix, &pane, &mut cursor).await;
 
-            let result = self.fetch(&prefix, &mut last_timestamp).await;
+            // 終了済みコンテナのログはストリームが閉じた時点で全て取得できている
+            if self.options.previous {
+                if let Err(err) = result {
+                    logger!(error, "{}", err);
+
+                    // 再接続しないため、終了済みコンテナが見つからない場合などはログウィジェットに表示する
+                    self.send_error_message(&pane, &err).await;
+                }
 
-            if let Err(err) = result {
-                logger!(error, "{}", err)
-            } else if self.is_terminated.load(Ordering::Relaxed) {
-                // 正常終了は下記2パターン確認しているため、
-                // コンテナ終了時のみループを抜ける処理を組み込む。
-                //   - コンテナが終了している
-                //   - 長時間実行
                 break;
             }
+
+            match result {
+                Ok(received) => {
+                    if self.is_terminated.load(Ordering::Relaxed) {
+                        // 正常終了は下記2パターン確認しているため、
+                        // コンテナ終了時のみループを抜ける処理を組み込む。
+                        //   - コンテナが終了している
+                        //   - 長時間実行
+                        break;
+                    }
+
+                    if 0 < received {
+                        backoff.reset();
+                    }
+
+                    logger!(
+                        info,
+                        "log stream closed. reconnecting. target={}/{}/{}",
+                        self.namespace(),
+                        self.pod_name(),
+                        self.container_name()
+                    );
+                }
+                Err(err) => {
+                    logger!(error, "{}", err);
+                }
+            }
+
+            time::sleep(backoff.next_delay()).await;
         }
 
-        self.send_finished_message().await;
+        self.send_finished_message(&pane, cursor.last_timestamp)
+            .await;
     }
 }
 
@@ -145,40 +192,84 @@ impl LogStreamer {
         self
     }
 
-    async fn fetch(&self, prefix: &str, last_timestamp: &mut Option<DateTime<Utc>>) -> Result<()> {
-        let log_params = self.log_params(last_timestamp);
+    /// follow=trueでログストリームを開き、ストリームが閉じられるまで読み続ける
+    ///
+    /// 戻り値は今回のストリームで新たに受信した行数
+    async fn stream(
+        &self,
+        prefix: &str,
+        pane: &Option<Arc<str>>,
+        cursor: &mut LogCursor,
+    ) -> Result<usize> {
+        let log_params = self.log_params(cursor.last_timestamp);
 
         let api: Api<Pod> = Api::namespaced(self.client.to_client(), self.namespace());
 
         let mut logs = api.log_stream(self.pod_name(), &log_params).await?.lines();
 
+        let mut skip = cursor.resume();
+
+        let mut received = 0;
+
+        let parse_json = self.needs_json();
+
         while let Some(line) = logs.try_next().await? {
-            let mut buf = self.log_buffer.lock().await;
+            let (dt, content) =
+                if let Ok((dt, content)) = chrono::DateTime::parse_and_remainder(&line, "%+") {
+                    let dt: DateTime<Utc> = dt.into();
 
-            if let Ok((dt, content)) = chrono::DateTime::parse_and_remainder(&line, "%+") {
-                let dt: DateTime<Utc> = dt.into();
+                    if skip.should_skip(dt) {
+                        continue;
+                    }
 
-                if last_timestamp.is_some_and(|lts| dt <= lts) {
-                    continue;
-                }
+                    cursor.advance(dt);
 
-                if self.is_exclude(content) || !self.is_include(content) {
-                    continue;
-                }
+                    (Some(dt), content)
+                } else {
+                    (None, line.as_str())
+                };
 
-                buf.push(format!("{}{}", prefix, content));
+            received += 1;
 
-                *last_timestamp = Some(dt);
+            if self.is_exclude(content) || !self.is_include(content) {
+                continue;
+            }
+
+            let json = if parse_json {
+                json_log::parse(content)
             } else {
-                if self.is_exclude(&line) || !self.is_include(&line) {
-                    continue;
-                }
+                None
+            };
 
-                buf.push(format!("{}{}", prefix, line));
+            if !self.is_match_json(json.as_ref()) || !self.is_match(content, json.as_ref()) {
+                continue;
             }
+
+            let formatted = json
+                .filter(|_| self.options.pretty)
+                .and_then(json_log::format);
+
+            let content = formatted.as_deref().unwrap_or(content);
+
+            let highlighted = self
+                .options
+                .highlight
+                .as_ref()
+                .map(|highlight| highlight::apply(content, highlight));
+
+            let content = highlighted.as_deref().unwrap_or(content);
+
+            let mut buf = self.log_buffer.lock().await;
+
+            buf.push(LogLine {
+                timestamp: dt,
+                pane: pane.clone(),
+                is_log: true,
+                content: format!("{}{}", prefix, content),
+            });
         }
 
-        Ok(())
+        Ok(received)
     }
 
     fn is_exclude(&self, s: &str) -> bool {
@@ -196,20 +287,85 @@ impl LogStreamer {
         include.iter().any(|include| include.is_match(s))
     }
 
-    async fn send_started_message(&self) {
+    /// JSON形式でないログはjsonフィルターが指定されている場合に除外する
+    fn is_match_json(&self, value: Option<&serde_json::Value>) -> bool {
+        let Some(json) = &self.options.json else {
+            return true;
+        };
+
+        value.is_some_and(|value| json.iter().all(|filter| filter.is_match(value)))
+    }
+
+    fn is_match(&self, s: &str, json: Option<&serde_json::Value>) -> bool {
+        let log = LogContent { text: s, json };
+
+        !self.options.expression.as_ref().is_some_and(|expr| {
+            expr.evaluate(self.pod_name(), Some(self.container_name()), Some(log)) == Some(false)
+        })
+    }
+
+    /// JSON形式のログのパースが必要か
+    ///
+    /// jsonフィルターも整形も使わない場合は、行ごとのパースを省く
+    fn needs_json(&self) -> bool {
+        self.options.pretty
+            || self.options.json.is_some()
+            || self
+                .options
+                .expression
+                .as_ref()
+                .is_some_and(FilterExpression::contains_json)
+    }
+
+    async fn send_started_message(&self, pane: &Option<Arc<str>>) {
         let sign = Color::LightGreen.wrap("+");
 
         let mut buf = self.log_buffer.lock().await;
 
-        buf.push(format!("{} {}", sign, self.log_prefix_content()));
+        buf.push(LogLine {
+            timestamp: None,
+            pane: pane.clone(),
+            is_log: false,
+            content: format!("{} {}", sign, self.log_prefix_content()),
+        });
     }
 
-    async fn send_finished_message(&self) {
+    /// ログの取得に失敗した理由をログウィジェットに表示する
+    async fn send_error_message(&self, pane: &Option<Arc<str>>, err: &anyhow::Error) {
+        let prefix = self.log_prefix();
+
+        let mut buf = self.log_buffer.lock().await;
+
+        for line in format!("{:?}", err).lines() {
+            buf.push(LogLine {
+                timestamp: None,
+                pane: pane.clone(),
+                is_log: false,
+                content: format!(
+                    "{}{}",
+                    prefix,
+                    Color::Red.wrap(format!("[kubetui] {}", line))
+                ),
+            });
+        }
+    }
+
+    /// 並び替え時に最後のログより後ろに出力されるよう、最後のログのタイムスタンプを付与する
+    async fn send_finished_message(
+        &self,
+        pane: &Option<Arc<str>>,
+        last_timestamp: Option<DateTime<Utc>>,
+    ) {
         let sign = Color::LightRed.wrap("-");
 
         let mut buf = self.log_buffer.lock().await;
 
-        buf.push(format!("{} {}", sign, self.log_prefix_content()));
+        buf.push(LogLine {
+            timestamp: last_timestamp,
+            pane: pane.clone(),
+            is_log: false,
+            content: format!("{} {}", sign, self.log_prefix_content()),
+        });
     }
 
     fn log_prefix_content(&self) -> String {
@@ -293,14 +449,36 @@ impl LogStreamer {
         PREFIX_COLOR_LIST[index % PREFIX_COLOR_LIST.len()]
     }
 
-    fn log_params(&self, last_timestamp: &Option<DateTime<Utc>>) -> LogParams {
-        LogParams {
-            follow: true,
+    fn log_params(&self, last_timestamp: Option<DateTime<Utc>>) -> LogParams {
+        let mut params = LogParams {
+            follow: !self.options.previous,
             container: Some(self.container_name().to_string()),
             timestamps: true,
-            since_time: *last_timestamp,
+            previous: self.options.previous,
             ..Default::default()
+        };
+
+        // 再接続時は出力済みの位置から再開するため、since・tailの指定は初回接続時のみ使用する
+        if last_timestamp.is_some() {
+            params.since_time = last_timestamp;
+            return params;
         }
+
+        match self.options.since {
+            Some(LogSince::Seconds(seconds)) => params.since_seconds = Some(seconds),
+            Some(LogSince::Time(time)) => params.since_time = Some(time),
+            None => {}
+        }
+
+        params.tail_lines = self.options.tail_lines;
+
+        params
+    }
+
+    fn pane(&self) -> Option<Arc<str>> {
+        self.options
+            .split_panes
+            .then(|| log_pane_id(self.namespace(), self.pod_name()).into())
     }
 
     fn namespace(&self) -> &str {
@@ -315,3 +493,153 @@ impl LogStreamer {
         &self.target.container_name
     }
 }
+
+/// 再接続時の待機時間を指数的に伸ばす
+#[derive(Debug)]
+struct Backoff {
+    current: Duration,
+}
+
+impl Backoff {
+    const INITIAL: Duration = Duration::from_secs(1);
+    const MAX: Duration = Duration::from_secs(30);
+
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.current;
+
+        self.current = (self.current * 2).min(Self::MAX);
+
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.current = Self::INITIAL;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            current: Self::INITIAL,
+        }
+    }
+}
+
+/// 出力済みのログ位置を管理する
+///
+/// since_timeは秒精度で切り捨てられるため、再接続時は同じタイムスタンプの行が再送される。
+/// 最後のタイムスタンプと、そのタイムスタンプで出力した行数を保持して重複と欠落を防ぐ。
+#[derive(Debug, Default)]
+struct LogCursor {
+    last_timestamp: Option<DateTime<Utc>>,
+    lines_at_last_timestamp: usize,
+}
+
+impl LogCursor {
+    fn advance(&mut self, dt: DateTime<Utc>) {
+        if self.last_timestamp == Some(dt) {
+            self.lines_at_last_timestamp += 1;
+        } else {
+            self.last_timestamp = Some(dt);
+            self.lines_at_last_timestamp = 1;
+        }
+    }
+
+    /// 再接続したストリームで読み飛ばす範囲を返す
+    fn resume(&self) -> LogSkip {
+        LogSkip {
+            last_timestamp: self.last_timestamp,
+            remaining: self.lines_at_last_timestamp,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LogSkip {
+    last_timestamp: Option<DateTime<Utc>>,
+    remaining: usize,
+}
+
+impl LogSkip {
+    fn should_skip(&mut self, dt: DateTime<Utc>) -> bool {
+        let Some(last_timestamp) = self.last_timestamp else {
+            return false;
+        };
+
+        if dt < last_timestamp {
+            return true;
+        }
+
+        if dt == last_timestamp && 0 < self.remaining {
+            self.remaining -= 1;
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn timestamp(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().into()
+    }
+
+    #[test]
+    fn backoffは最大値まで倍増しresetで初期値に戻る() {
+        let mut backoff = Backoff::default();
+
+        let delays: Vec<_> = (0..7).map(|_| backoff.next_delay().as_secs()).collect();
+
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30]);
+
+        backoff.reset();
+
+        assert_eq!(backoff.next_delay(), Backoff::INITIAL);
+    }
+
+    #[test]
+    fn 再接続時に出力済みの行だけを読み飛ばす() {
+        let mut cursor = LogCursor::default();
+
+        // 1回目の接続
+        let mut skip = cursor.resume();
+        for ts in [
+            "2024-01-01T00:00:00.000000001Z",
+            "2024-01-01T00:00:01Z",
+            "2024-01-01T00:00:01Z",
+        ] {
+            let dt = timestamp(ts);
+            assert!(!skip.should_skip(dt));
+            cursor.advance(dt);
+        }
+
+        // 2回目の接続
+        // since_timeより前の行と同じタイムスタンプで出力済みの2行は読み飛ばし、3行目以降を出力する
+        let mut skip = cursor.resume();
+        let actual: Vec<bool> = [
+            "2024-01-01T00:00:00.000000001Z",
+            "2024-01-01T00:00:01Z",
+            "2024-01-01T00:00:01Z",
+            "2024-01-01T00:00:01Z",
+            "2024-01-01T00:00:02Z",
+        ]
+        .into_iter()
+        .map(|ts| skip.should_skip(timestamp(ts)))
+        .collect();
+
+        assert_eq!(actual, vec![true, true, true, false, false]);
+    }
+
+    #[test]
+    fn 初回接続時は読み飛ばさない() {
+        let cursor = LogCursor::default();
+
+        let mut skip = cursor.resume();
+
+        assert!(!skip.should_skip(timestamp("2024-01-01T00:00:00Z")));
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Display,
     ops::{Deref, DerefMut},
     sync::{
@@ -15,9 +15,10 @@ use futures::{StreamExt, TryStreamExt};
 use k8s_openapi::api::core::v1::{ContainerState, ContainerStatus, Pod};
 use kube::{api::WatchParams, core::WatchEvent, Api, ResourceExt};
 use regex::Regex;
-use tokio::task::AbortHandle;
+use tokio::{sync::watch, task::AbortHandle};
 
 use crate::{
+    features::pod::message::LogMessage,
     kube::KubeClient,
     logger,
     message::Message,
@@ -27,7 +28,9 @@ use crate::{
 
 use super::{
     log_collector::LogBuffer,
+    log_pane_id,
     log_streamer::{LogStreamer, LogStreamerOptions, LogStreamerTarget},
+    FilterExpression,
 };
 
 #[derive(Default, Debug, Clone)]
@@ -36,6 +39,7 @@ pub struct PodWatcherFilter {
     pub exclude_pod: Option<Vec<Regex>>,
     pub container: Option<Regex>,
     pub exclude_container: Option<Vec<Regex>>,
+    pub expression: Option<FilterExpression>,
 }
 
 impl PodWatcherFilter {
@@ -45,9 +49,13 @@ impl PodWatcherFilter {
                 .exclude_pod
                 .as_ref()
                 .is_some_and(|exclude| exclude.iter().any(|re| re.is_match(pod)))
+            || self
+                .expression
+                .as_ref()
+                .is_some_and(|expr| expr.evaluate(pod, None, None) == Some(false))
     }
 
-    fn is_exclude_container(&self, container: &str) -> bool {
+    fn is_exclude_container(&self, pod: &str, container: &str) -> bool {
         self.container
             .as_ref()
             .is_some_and(|re| !re.is_match(container))
@@ -55,6 +63,10 @@ impl PodWatcherFilter {
                 .exclude_container
                 .as_ref()
                 .is_some_and(|exclude| exclude.iter().any(|re| re.is_match(container)))
+            || self
+                .expression
+                .as_ref()
+                .is_some_and(|expr| expr.evaluate(pod, Some(container), None) == Some(false))
     }
 }
 
@@ -73,6 +85,8 @@ pub struct PodWatcher {
     filter: PodWatcherFilter,
     selector: PodWatcherSelector,
     log_streamer_options: LogStreamerOptions,
+    split_panes: bool,
+    closed_panes: watch::Receiver<HashSet<String>>,
 }
 
 #[async_trait]
@@ -86,6 +100,8 @@ impl Worker for PodWatcher {
 
         let mut tasks = Tasks::default();
 
+        let mut closed_panes = self.closed_panes.clone();
+
         loop {
             let Ok(stream) = api.watch(&lp, "0").await else {
                 continue;
@@ -93,7 +109,19 @@ impl Worker for PodWatcher {
 
             let mut stream = stream.boxed();
 
-            while let Ok(Some(status)) = stream.try_next().await {
+            loop {
+                let status = tokio::select! {
+                    status = stream.try_next() => status,
+                    Ok(()) = closed_panes.changed() => {
+                        tasks.abort_with_panes(&closed_panes.borrow_and_update());
+                        continue;
+                    }
+                };
+
+                let Ok(Some(status)) = status else {
+                    break;
+                };
+
                 use WatchEvent::*;
 
                 match status {
@@ -136,6 +164,11 @@ impl Worker for PodWatcher {
                         );
 
                         self.abort_tasks(&mut tasks, &pod);
+
+                        self.send_pane_message(LogMessage::PaneRemoved(log_pane_id(
+                            &self.namespace,
+                            name,
+                        )));
                     }
                     Bookmark(_) => {}
                     Error(err) => {
@@ -162,6 +195,8 @@ impl PodWatcher {
             filter: PodWatcherFilter::default(),
             selector: PodWatcherSelector::default(),
             log_streamer_options: LogStreamerOptions::default(),
+            split_panes: false,
+            closed_panes: watch::channel(HashSet::default()).1,
         }
     }
 
@@ -180,6 +215,28 @@ impl PodWatcher {
         self
     }
 
+    /// Podの追加・削除をペインの追加・削除として通知する
+    pub fn split_panes(mut self, split_panes: bool) -> Self {
+        self.split_panes = split_panes;
+        self
+    }
+
+    /// ユーザーが閉じたペインのID
+    ///
+    /// 閉じたペインのPodはログの取得をやめ、クエリを変更するまで再開しない
+    pub fn closed_panes(mut self, closed_panes: watch::Receiver<HashSet<String>>) -> Self {
+        self.closed_panes = closed_panes;
+        self
+    }
+
+    fn send_pane_message(&self, message: LogMessage) {
+        if self.split_panes {
+            self.tx
+                .send(message.into())
+                .expect("Failed to send LogMessage");
+        }
+    }
+
     fn watch_params(&self) -> WatchParams {
         let mut lp = WatchParams::default().timeout(180);
 
@@ -197,6 +254,14 @@ impl PodWatcher {
     }
 
     fn spawn_tasks(&self, tasks: &mut Tasks, pod: &Pod, pod_name: String, pod_uid: String) {
+        if self
+            .closed_panes
+            .borrow()
+            .contains(&log_pane_id(&self.namespace, &pod_name))
+        {
+            return;
+        }
+
         // コンテナステータスを集約
         let container_statuses = Self::aggregate_container_statuses(pod);
 
@@ -204,7 +269,7 @@ impl PodWatcher {
         for status in container_statuses {
             let container_name = status.name.clone();
 
-            if self.filter.is_exclude_container(&container_name) {
+            if self.filter.is_exclude_container(&pod_name, &container_name) {
                 continue;
             }
 
@@ -257,6 +322,11 @@ impl PodWatcher {
                 container_name: container_name.clone(),
             };
 
+            self.send_pane_message(LogMessage::PaneAdded(log_pane_id(
+                &self.namespace,
+                &pod_name,
+            )));
+
             let is_terminated = Arc::new(AtomicBool::new(Self::is_terminated(&status)));
 
             let handler = LogStreamer::new(
@@ -408,6 +478,11 @@ impl Tasks {
     fn abort_with_pod_name(&mut self, pod_name: &str) {
         self.0.retain(|k, _| k.pod_name != pod_name)
     }
+
+    fn abort_with_panes(&mut self, panes: &HashSet<String>) {
+        self.0
+            .retain(|k, _| !panes.contains(&log_pane_id(&k.namespace, &k.pod_name)))
+    }
 }
 
 impl Deref for Tasks {
@@ -467,3 +542,50 @@ impl Drop for TaskState {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn task(pod_name: &str) -> (TaskId, TaskState, tokio::task::JoinHandle<()>) {
+        let handle = tokio::spawn(std::future::pending());
+
+        let id = TaskId {
+            namespace: "default".into(),
+            pod_name: pod_name.into(),
+            container_name: "app".into(),
+        };
+
+        let state = TaskState {
+            handler: handle.abort_handle(),
+            is_terminated: Arc::new(AtomicBool::new(false)),
+            pod_name: pod_name.into(),
+            pod_uid: format!("{}-uid", pod_name),
+            container_name: "app".into(),
+            container_id: format!("{}-container", pod_name),
+        };
+
+        (id, state, handle)
+    }
+
+    #[tokio::test]
+    async fn 閉じたペインのpodのログの取得を止める() {
+        let mut tasks = Tasks::default();
+
+        let (id, state, closed) = task("closed");
+        tasks.insert(id, state);
+
+        let (id, state, open) = task("open");
+        tasks.insert(id, state);
+
+        tasks.abort_with_panes(&HashSet::from(["default/closed".to_string()]));
+
+        assert_eq!(
+            tasks.keys().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["default/open/app"]
+        );
+        assert!(closed.await.unwrap_err().is_cancelled());
+        assert!(!open.is_finished());
+    }
+}
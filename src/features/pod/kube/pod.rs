@@ -2,13 +2,14 @@ use std::collections::BTreeMap;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use futures::future::try_join_all;
 use k8s_openapi::{api::core::v1::Pod, Resource as _};
 
 use crate::{
+    features::component_id::POD_TAB_ID,
     kube::{
         apis::v1_table::TableRow,
-        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow},
+        informer::{ResourcePath, TableInformers},
+        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow, ResourceRows},
     },
     message::Message,
     workers::kube::{
@@ -33,18 +34,25 @@ impl Worker for PodPoller {
     type Output = WorkerResult;
 
     async fn run(&self) -> Self::Output {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
-
         let Self {
-            base: PollerBase {
-                is_terminated, tx, ..
-            },
+            base:
+                PollerBase {
+                    is_terminated,
+                    tx,
+                    informers,
+                    polling,
+                    health,
+                    ..
+                },
         } = self;
 
+        let mut changes = informers.changes(polling.ticker(POD_TAB_ID));
+
         while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
-            interval.tick().await;
+            changes.wait().await;
+            health.wait_backoff().await;
 
-            let pod_info = self.get_pod_info().await;
+            let pod_info = self.get_pod_info(changes.informers()).await;
 
             tx.send(Message::Kube(Kube::Pod(pod_info)))
                 .expect("Failed to Kube::Pod");
@@ -55,12 +63,10 @@ impl Worker for PodPoller {
 }
 
 impl PodPoller {
-    async fn get_pod_info(&self) -> Result<KubeTable> {
+    async fn get_pod_info(&self, informers: &TableInformers) -> Result<KubeTable> {
         let namespaces = self.base.shared_target_namespaces.read().await;
 
-        let jobs = self.get_pods_per_namespace(&namespaces).await;
-
-        let ok_only: Vec<KubeTableRow> = jobs?.into_iter().flatten().collect();
+        let ok_only: ResourceRows = self.get_pods_per_namespace(informers, &namespaces).await?;
 
         let mut table = KubeTable {
             header: if namespaces.len() == 1 {
@@ -77,59 +83,56 @@ impl PodPoller {
             ..Default::default()
         };
 
-        table.update_rows(ok_only);
+        table.update_resource_rows(ok_only);
 
         Ok(table)
     }
 
     async fn get_pods_per_namespace(
         &self,
+        informers: &TableInformers,
         namespaces: &[String],
-    ) -> Result<Vec<Vec<KubeTableRow>>> {
+    ) -> Result<ResourceRows> {
         let insert_ns = insert_ns(namespaces);
-        try_join_all(namespaces.iter().map(|ns| {
-            get_resource_per_namespace(
-                &self.base.kube_client,
-                format!("api/v1/namespaces/{}/{}", ns, "pods"),
-                &["Name", "Ready", "Status", "Age"],
-                move |row: &TableRow, indexes: &[usize]| {
-                    let mut row: Vec<String> =
-                        indexes.iter().map(|i| row.cells[*i].to_string()).collect();
-
-                    let name = row[0].clone();
-
-                    let color = match row[2].as_str() {
-                        s if s == "Completed" || s.contains("Evicted") => Some(90),
-                        s if s.contains("BackOff")
-                            || s.contains("Err")
-                            || s.contains("Unknown") =>
-                        {
-                            Some(31)
-                        }
-                        _ => None,
-                    };
-
-                    if insert_ns {
-                        row.insert(0, ns.to_string())
+        get_resource_per_namespace(
+            informers,
+            &ResourcePath::of::<Pod>(),
+            namespaces,
+            &["Name", "Ready", "Status", "Age"],
+            |ns: &str, row: &TableRow, indexes: &[usize]| {
+                let mut row: Vec<String> =
+                    indexes.iter().map(|i| row.cells[*i].to_string()).collect();
+
+                let name = row[0].clone();
+
+                let color = match row[2].as_str() {
+                    s if s == "Completed" || s.contains("Evicted") => Some(90),
+                    s if s.contains("BackOff") || s.contains("Err") || s.contains("Unknown") => {
+                        Some(31)
                     }
-
-                    if let Some(color) = color {
-                        row.iter_mut()
-                            .for_each(|r| *r = format!("\x1b[{}m{}\x1b[0m", color, r))
-                    }
-
-                    KubeTableRow {
-                        namespace: ns.to_string(),
-                        name,
-                        row,
-                        metadata: Some(BTreeMap::from([(
-                            "kind".to_string(),
-                            Pod::KIND.to_string(),
-                        )])),
-                    }
-                },
-            )
-        }))
+                    _ => None,
+                };
+
+                if insert_ns {
+                    row.insert(0, ns.to_string())
+                }
+
+                if let Some(color) = color {
+                    row.iter_mut()
+                        .for_each(|r| *r = format!("\x1b[{}m{}\x1b[0m", color, r))
+                }
+
+                KubeTableRow {
+                    namespace: ns.to_string(),
+                    name,
+                    row,
+                    metadata: Some(BTreeMap::from([(
+                        "kind".to_string(),
+                        Pod::KIND.to_string(),
+                    )])),
+                }
+            },
+        )
         .await
     }
 }
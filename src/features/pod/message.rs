@@ -1,13 +1,72 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::Event;
+use tokio::sync::mpsc::UnboundedReceiver;
 
-use crate::{message::Message, workers::kube::message::Kube};
+use crate::{
+    features::dry_run::kube::DryRunDiff, message::Message, ui::WindowAction,
+    workers::kube::message::Kube,
+};
 
 use super::kube::LogConfig;
 
+pub use super::kube::{DebugRequest, ExecMode, ExecTarget};
+
+/// 描画スレッドに送るログ行
+///
+/// タイムスタンプの表示形式を描画側で切り替えられるよう、タイムスタンプは本文と分けて送る
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// 表示するタイムスタンプ（開始・終了の表示やエラーはNone）
+    pub timestamp: Option<DateTime<Utc>>,
+    pub content: String,
+}
+
+impl LogEntry {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            timestamp: None,
+            content: content.into(),
+        }
+    }
+}
+
+/// ログのファイルへの保存
+#[derive(Debug)]
+pub struct LogSaveRequest {
+    pub path: PathBuf,
+    pub lines: Vec<String>,
+    /// ANSIエスケープシーケンスを残したか
+    pub raw: bool,
+}
+
+#[derive(Debug)]
+pub struct LogSaveResponse {
+    /// 保存先の絶対パス
+    pub path: PathBuf,
+    pub lines: usize,
+    pub raw: bool,
+    pub result: Result<()>,
+}
+
 #[derive(Debug)]
 pub enum LogMessage {
     Request(LogConfig),
-    Response(Result<Vec<String>>),
+    Response(Result<Vec<LogEntry>>),
+    /// ペインIDごとのログ
+    PaneResponse(Vec<(String, Vec<LogEntry>)>),
+    /// ログを出力するPodが追加された
+    PaneAdded(String),
+    /// ログを出力していたPodが削除された
+    PaneRemoved(String),
+    /// ユーザーがペインを閉じた
+    PaneClosed(String),
+    /// 描画スレッドを止めないよう、ファイルへの書き込みはkubeワーカーで行う
+    SaveRequest(LogSaveRequest),
+    SaveResponse(LogSaveResponse),
 }
 
 impl From<LogMessage> for Message {
@@ -15,3 +74,54 @@ impl From<LogMessage> for Message {
         Message::Kube(Kube::Log(m))
     }
 }
+
+#[derive(Debug)]
+pub enum ExecMessage {
+    /// exec対象のPodのコンテナ一覧を要求する
+    Request { namespace: String, pod: String },
+    Response {
+        namespace: String,
+        pod: String,
+        containers: Result<Vec<String>>,
+    },
+    /// 端末をコンテナにつなぐ
+    ///
+    /// inputにはUserInputスレッドが読み取ったキー入力が届く。
+    /// セッションが終了したら結果をdoneに送る
+    Session {
+        target: ExecTarget,
+        input: UnboundedReceiver<Event>,
+        done: Sender<Result<()>>,
+    },
+}
+
+impl From<ExecMessage> for Message {
+    fn from(m: ExecMessage) -> Message {
+        Message::Kube(Kube::Exec(m))
+    }
+}
+
+/// 画面を一時停止してコンテナに接続する
+impl From<ExecTarget> for WindowAction {
+    fn from(target: ExecTarget) -> WindowAction {
+        WindowAction::Suspend(Box::new(target))
+    }
+}
+
+#[derive(Debug)]
+pub enum DebugMessage {
+    /// Podにデバッグ用のエフェメラルコンテナを追加する
+    Request(DebugRequest),
+    /// 起動したエフェメラルコンテナ
+    Response(DebugRequest, Result<ExecTarget>),
+    /// エフェメラルコンテナの追加をサーバーサイドのdry-runで実行する
+    DryRun(DebugRequest),
+    /// dry-runで得たPodの差分
+    DryRunResponse(DebugRequest, Result<DryRunDiff>),
+}
+
+impl From<DebugMessage> for Message {
+    fn from(m: DebugMessage) -> Message {
+        Message::Kube(Kube::Debug(m))
+    }
+}
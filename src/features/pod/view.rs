@@ -1,4 +1,11 @@
+mod debug;
+mod exec;
+mod log_pane;
 mod tab;
 mod widgets;
 
+pub use debug::*;
+pub use exec::*;
+pub use log_pane::*;
 pub use tab::*;
+pub use widgets::{append_log, run_log_query, show_log_save_result, LogState, SharedLogState};
@@ -0,0 +1,353 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::{POD_DEBUG_POPUP_ID, POD_DEBUG_STATUS_POPUP_ID, POD_WIDGET_ID},
+        dry_run::{kube::DryRunDiff, view::diff_lines},
+        pod::message::{DebugMessage, DebugRequest, ExecTarget},
+    },
+    logger,
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            config::WidgetConfig, input::InputFormBuilder, Item, LiteralItem, SelectedItem, Text,
+            Widget, WidgetTrait as _,
+        },
+        Window, WindowAction,
+    },
+};
+
+/// デバッグ用コンテナの入力と起動待ちの状態
+#[derive(Debug, Default)]
+pub struct DebugState {
+    /// イメージを入力中のPod（namespace, name）
+    pub pod: Option<(String, String)>,
+    /// 入力欄に表示するイメージ
+    ///
+    /// 初期値は `--debug-image` で指定したイメージで、以降は前回入力したイメージ
+    pub image: String,
+    /// dry-runの結果を確認中のコンテナ
+    pub confirm: Option<DebugRequest>,
+    /// 起動を待っているコンテナ
+    pub waiting: Option<DebugRequest>,
+}
+
+impl DebugState {
+    pub fn new(image: impl Into<String>) -> Self {
+        Self {
+            image: image.into(),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SharedDebugState = Rc<RefCell<DebugState>>;
+
+pub fn debug_popup_widget(tx: &Sender<Message>, state: SharedDebugState) -> Widget<'static> {
+    InputFormBuilder::default()
+        .id(POD_DEBUG_POPUP_ID)
+        .widget_config(
+            WidgetConfig::builder()
+                .title("Debug image [target container]")
+                .build(),
+        )
+        .actions(UserEvent::from(KeyCode::Enter), start(tx.clone(), state))
+        .build()
+        .into()
+}
+
+pub fn debug_status_popup_widget(tx: &Sender<Message>, state: SharedDebugState) -> Widget<'static> {
+    Text::builder()
+        .id(POD_DEBUG_STATUS_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Debug").build())
+        .wrap()
+        .action('y', confirm(tx.clone(), state.clone()))
+        .action('n', cancel(state.clone()))
+        .action(UserEvent::from(KeyCode::Enter), cancel(state.clone()))
+        .action(UserEvent::from(KeyCode::Esc), cancel(state.clone()))
+        .action('q', cancel(state))
+        .build()
+        .into()
+}
+
+/// 選択中のPodにデバッグ用コンテナを追加するため、イメージの入力ポップアップを開く
+pub fn request_debug(state: SharedDebugState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::TableRow {
+            metadata: Some(metadata),
+            ..
+        }) = w.find_widget(POD_WIDGET_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(pod)) = (metadata.get("namespace"), metadata.get("name")) else {
+            return EventResult::Ignore;
+        };
+
+        let mut state = state.borrow_mut();
+
+        let widget = w.find_widget_mut(POD_DEBUG_POPUP_ID);
+
+        *widget.widget_config_mut().append_title_mut() =
+            Some(format!(" : {}/{}", namespace, pod).into());
+
+        widget.update_widget_item(Item::Single(state.image.clone().into()));
+
+        state.pod = Some((namespace.to_string(), pod.to_string()));
+
+        w.open_popup(POD_DEBUG_POPUP_ID);
+
+        EventResult::Nop
+    }
+}
+
+/// dry-runの結果を確認ポップアップに表示する
+///
+/// 確認待ちのコンテナが変わっていた場合は何もしない
+pub fn show_debug_dry_run_result(
+    w: &mut Window,
+    state: &SharedDebugState,
+    req: DebugRequest,
+    result: Result<DryRunDiff>,
+) {
+    if state.borrow().confirm.as_ref() != Some(&req) {
+        return;
+    }
+
+    let body = match result {
+        Ok(diff) if diff.is_changed() => diff_lines(&diff.live, &diff.dry_run),
+        Ok(_) => vec!["The server reports no changes to the pod.".to_string()],
+        Err(err) => {
+            logger!(error, "Dry-run failed for {:?}: {:?}", req, err);
+
+            ["Dry-run failed:".to_string()]
+                .into_iter()
+                .chain(format!("{:?}", err).lines().map(String::from))
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    update_status(w, confirm_lines(&req, body));
+}
+
+/// 起動したデバッグ用コンテナに接続する
+///
+/// 起動を待っている間にポップアップを閉じていた場合は接続しない
+pub fn attach_debug_container(
+    w: &mut Window,
+    state: &SharedDebugState,
+    req: DebugRequest,
+    result: Result<ExecTarget>,
+) -> WindowAction {
+    if state.borrow().waiting.as_ref() != Some(&req) {
+        logger!(
+            info,
+            "Debug container is ready but no longer awaited: {:?}",
+            req
+        );
+        return WindowAction::Continue;
+    }
+
+    state.borrow_mut().waiting = None;
+
+    match result {
+        Ok(target) => {
+            w.close_popup();
+
+            target.into()
+        }
+        Err(err) => {
+            update_status(
+                w,
+                ["Failed to start the debug container:".to_string()]
+                    .into_iter()
+                    .chain(format!("{:?}", err).lines().map(String::from))
+                    .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                    .collect(),
+            );
+
+            WindowAction::Continue
+        }
+    }
+}
+
+fn start(tx: Sender<Message>, state: SharedDebugState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::Literal { item, .. }) =
+            w.find_widget(POD_DEBUG_POPUP_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let Some((image, target)) = parse_debug_input(&item) else {
+            return EventResult::Ignore;
+        };
+
+        let mut state = state.borrow_mut();
+
+        let Some((namespace, pod)) = state.pod.take() else {
+            return EventResult::Ignore;
+        };
+
+        let req = DebugRequest {
+            namespace,
+            pod,
+            image,
+            target,
+        };
+
+        update_status(
+            w,
+            confirm_lines(
+                &req,
+                vec!["\x1b[90mRunning server-side dry-run ...\x1b[39m".to_string()],
+            ),
+        );
+
+        w.open_popup(POD_DEBUG_STATUS_POPUP_ID);
+
+        state.image.clone_from(&req.image);
+        state.confirm = Some(req.clone());
+
+        tx.send(DebugMessage::DryRun(req).into())
+            .expect("Failed to send DebugMessage::DryRun");
+
+        EventResult::Nop
+    }
+}
+
+/// 確認したコンテナを追加し、起動を待つ
+fn confirm(tx: Sender<Message>, state: SharedDebugState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let mut state = state.borrow_mut();
+
+        let Some(req) = state.confirm.take() else {
+            return EventResult::Nop;
+        };
+
+        update_status(
+            w,
+            vec![format!(
+                "Starting {} in {}/{} ...",
+                req.image, req.namespace, req.pod
+            )],
+        );
+
+        state.waiting = Some(req.clone());
+
+        tx.send(DebugMessage::Request(req).into())
+            .expect("Failed to send DebugMessage::Request");
+
+        EventResult::Nop
+    }
+}
+
+fn cancel(state: SharedDebugState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let mut state = state.borrow_mut();
+
+        state.confirm.take();
+        state.waiting.take();
+
+        w.close_popup();
+
+        EventResult::Nop
+    }
+}
+
+fn confirm_lines(req: &DebugRequest, body: Vec<String>) -> Vec<String> {
+    let target = req
+        .target
+        .as_ref()
+        .map(|target| format!(" targeting container {}", target))
+        .unwrap_or_default();
+
+    [
+        format!(
+            "Add an ephemeral container ({}) to pod/{} in {}{}?",
+            req.image, req.pod, req.namespace, target
+        ),
+        "Ephemeral containers cannot be removed from the pod once added.".to_string(),
+        String::default(),
+    ]
+    .into_iter()
+    .chain(body)
+    .chain([
+        String::default(),
+        "Press \x1b[1my\x1b[0m to continue, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to cancel."
+            .to_string(),
+    ])
+    .collect()
+}
+
+fn update_status(w: &mut Window, lines: Vec<String>) {
+    let items = lines
+        .into_iter()
+        .map(|line| LiteralItem::new(line, None))
+        .collect();
+
+    w.find_widget_mut(POD_DEBUG_STATUS_POPUP_ID)
+        .update_widget_item(Item::Array(items));
+}
+
+/// `IMAGE [TARGET_CONTAINER]` 形式の入力を解析する
+fn parse_debug_input(s: &str) -> Option<(String, Option<String>)> {
+    let mut words = s.split_whitespace();
+
+    let image = words.next()?.to_string();
+
+    let target = words.next().map(String::from);
+
+    if words.next().is_some() {
+        return None;
+    }
+
+    Some((image, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("busybox", Some(("busybox", None)))]
+    #[case(" busybox:1.36  app ", Some(("busybox:1.36", Some("app"))))]
+    #[case("", None)]
+    #[case("busybox app extra", None)]
+    fn イメージとコンテナ名を解析する(
+        #[case] input: &str,
+        #[case] expected: Option<(&str, Option<&str>)>,
+    ) {
+        let expected =
+            expected.map(|(image, target)| (image.to_string(), target.map(String::from)));
+
+        assert_eq!(parse_debug_input(input), expected);
+    }
+
+    #[test]
+    fn 確認ポップアップに追加するコンテナとdry_runの結果を表示する() {
+        let req = DebugRequest {
+            namespace: "default".to_string(),
+            pod: "app-0".to_string(),
+            image: "busybox".to_string(),
+            target: Some("app".to_string()),
+        };
+
+        let actual = confirm_lines(&req, vec!["+ diff".to_string()]);
+
+        assert_eq!(
+            actual[0],
+            "Add an ephemeral container (busybox) to pod/app-0 in default targeting container app?"
+        );
+        assert_eq!(actual[2..5], ["", "+ diff", ""]);
+    }
+}
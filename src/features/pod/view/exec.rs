@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::{
+        component_id::{POD_EXEC_POPUP_ID, POD_WIDGET_ID},
+        pod::message::{ExecMessage, ExecMode, ExecTarget},
+    },
+    logger,
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{
+            config::WidgetConfig, Item, LiteralItem, SelectedItem, SingleSelect, Widget,
+            WidgetTrait as _,
+        },
+        Window, WindowAction,
+    },
+};
+
+pub fn exec_popup_widget() -> Widget<'static> {
+    SingleSelect::builder()
+        .id(POD_EXEC_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Container").build())
+        .on_select(on_select())
+        .build()
+        .into()
+}
+
+fn on_select() -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
+    move |w: &mut Window, v: &LiteralItem| {
+        logger!(info, "Select Item: {:?}", v);
+
+        // エラーメッセージの行はメタデータを持たない
+        let Some(metadata) = v.metadata.as_ref() else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(pod), Some(container)) = (
+            metadata.get("namespace"),
+            metadata.get("name"),
+            metadata.get("container"),
+        ) else {
+            return EventResult::Ignore;
+        };
+
+        w.close_popup();
+
+        EventResult::WindowAction(
+            ExecTarget {
+                namespace: namespace.to_string(),
+                pod: pod.to_string(),
+                container: container.to_string(),
+                mode: ExecMode::Shell,
+            }
+            .into(),
+        )
+    }
+}
+
+/// 選択中のPodのコンテナ一覧を要求する
+pub fn request_exec(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::TableRow {
+            metadata: Some(metadata),
+            ..
+        }) = w.find_widget(POD_WIDGET_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(pod)) = (metadata.get("namespace"), metadata.get("name")) else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(
+            ExecMessage::Request {
+                namespace: namespace.to_string(),
+                pod: pod.to_string(),
+            }
+            .into(),
+        )
+        .expect("Failed to send ExecMessage::Request");
+
+        EventResult::Nop
+    }
+}
+
+/// コンテナが1つの場合はそのまま接続し、複数の場合は選択用のポップアップを開く
+pub fn select_exec_container(
+    w: &mut Window,
+    namespace: String,
+    pod: String,
+    containers: Vec<String>,
+) -> WindowAction {
+    if let [container] = containers.as_slice() {
+        return ExecTarget {
+            namespace,
+            pod,
+            container: container.to_string(),
+            mode: ExecMode::Shell,
+        }
+        .into();
+    }
+
+    let items = containers
+        .into_iter()
+        .map(|container| {
+            let metadata = BTreeMap::from([
+                ("namespace".to_string(), namespace.to_string()),
+                ("name".to_string(), pod.to_string()),
+                ("container".to_string(), container.to_string()),
+            ]);
+
+            LiteralItem::new(container, Some(metadata))
+        })
+        .collect();
+
+    open_exec_popup(w, &namespace, &pod, Item::Array(items));
+
+    WindowAction::Continue
+}
+
+/// ポップアップにコンテナ一覧、またはエラーを表示する
+pub fn open_exec_popup(w: &mut Window, namespace: &str, pod: &str, items: Item) {
+    let widget = w.find_widget_mut(POD_EXEC_POPUP_ID);
+
+    *widget.widget_config_mut().append_title_mut() =
+        Some(format!(" : {}/{}", namespace, pod).into());
+
+    widget.update_widget_item(items);
+
+    w.open_popup(POD_EXEC_POPUP_ID);
+}
@@ -0,0 +1,186 @@
+use crossbeam::channel::Sender;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    widgets::Block,
+};
+
+use crate::{
+    features::{
+        component_id::{POD_LOG_PANE_WIDGET_ID, POD_LOG_WIDGET_ID, POD_TAB_ID},
+        pod::message::{LogEntry, LogMessage},
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, Item, Text, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+use super::widgets::{save_log, SharedLogState};
+
+/// ペインIDからウィジェットIDを生成する
+fn log_pane_widget_id(pane: &str) -> String {
+    format!("{}:{}", POD_LOG_PANE_WIDGET_ID, pane)
+}
+
+pub fn is_log_pane_widget_id(id: &str) -> bool {
+    log_pane_id(id).is_some()
+}
+
+/// ウィジェットIDからペインIDを取り出す
+fn log_pane_id(id: &str) -> Option<&str> {
+    id.strip_prefix(POD_LOG_PANE_WIDGET_ID)
+        .and_then(|id| id.strip_prefix(':'))
+}
+
+/// Podごとのログを表示するペインを追加する
+///
+/// クリップボードと最大行数はログウィジェットの設定を引き継ぐ
+pub fn add_log_pane(w: &mut Window, tx: &Sender<Message>, state: &SharedLogState, pane: &str) {
+    let id = log_pane_widget_id(pane);
+
+    let Some(tab) = w.find_tab_mut(POD_TAB_ID) else {
+        return;
+    };
+
+    // 終了したPodと同じ名前のPodが起動した場合は、同じペインに続けて出力する
+    if let Some(widget) = tab.find_widget_mut(&id) {
+        *widget.widget_config_mut().append_title_mut() = None;
+        return;
+    }
+
+    let Some(log_widget) = tab.find_widget(POD_LOG_WIDGET_ID) else {
+        return;
+    };
+
+    let log_widget = log_widget.as_text();
+
+    let mut builder = Text::builder()
+        .id(id)
+        .widget_config(&WidgetConfig::builder().title(pane).build())
+        .wrap()
+        .follow()
+        .block_injection(block_injection())
+        .action(
+            UserEvent::from(KeyCode::Char('s')),
+            save_log(false, state.clone()),
+        )
+        .action(
+            UserEvent::Key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            save_log(true, state.clone()),
+        )
+        .action('x', close_log_pane(tx.clone(), state.clone()));
+
+    if let Some(max_lines) = log_widget.max_lines() {
+        builder = builder.max_lines(max_lines);
+    }
+
+    if let Some(clipboard) = log_widget.clipboard() {
+        builder = builder.clipboard(clipboard.clone());
+    }
+
+    let widget: Widget<'static> = builder.build().into();
+
+    tab.push_widget(widget);
+}
+
+/// Podが終了したことをペインのタイトルに表示する
+///
+/// 終了までのログを読めるよう、ペインはユーザーが閉じるかクエリを変更するまで残す
+pub fn terminate_log_pane(w: &mut Window, pane: &str) {
+    if let Some(widget) = w
+        .find_tab_mut(POD_TAB_ID)
+        .and_then(|tab| tab.find_widget_mut(&log_pane_widget_id(pane)))
+    {
+        *widget.widget_config_mut().append_title_mut() = Some(" [terminated]".into());
+    }
+}
+
+/// 選択中のペインを閉じ、そのPodのログの取得をやめる
+fn close_log_pane(
+    tx: Sender<Message>,
+    state: SharedLogState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let id = w.active_widget_id().to_string();
+
+        if let Some(tab) = w.find_tab_mut(POD_TAB_ID) {
+            tab.remove_widget_by_id(&id);
+        }
+
+        state.borrow_mut().remove(&id);
+
+        if let Some(pane) = log_pane_id(&id) {
+            tx.send(LogMessage::PaneClosed(pane.to_string()).into())
+                .expect("Failed to send LogMessage::PaneClosed");
+        }
+
+        EventResult::Nop
+    }
+}
+
+pub fn remove_log_panes(w: &mut Window) {
+    let Some(tab) = w.find_tab_mut(POD_TAB_ID) else {
+        return;
+    };
+
+    let ids: Vec<String> = tab
+        .as_ref_widgets()
+        .iter()
+        .map(|w| w.id())
+        .filter(|id| is_log_pane_widget_id(id))
+        .map(ToString::to_string)
+        .collect();
+
+    for id in ids {
+        tab.remove_widget_by_id(&id);
+    }
+}
+
+/// ペインにログを追加する
+///
+/// 削除済みのペイン宛てのログは破棄する
+pub fn append_log_pane(w: &mut Window, state: &SharedLogState, pane: &str, entries: Vec<LogEntry>) {
+    let id = log_pane_widget_id(pane);
+
+    let Some(widget) = w
+        .find_tab_mut(POD_TAB_ID)
+        .and_then(|tab| tab.find_widget_mut(&id))
+    else {
+        return;
+    };
+
+    let items = state.borrow_mut().push(&id, entries);
+
+    widget.append_widget_item(Item::Array(items));
+}
+
+fn block_injection() -> impl Fn(&Text, bool, bool) -> Block<'static> {
+    |text: &Text, is_active: bool, is_mouse_over: bool| {
+        let (index, size) = text.state();
+
+        let mut config = text.widget_config().clone();
+
+        let title = config.title().to_string();
+
+        *config.title_mut() = format!("{} [{}/{}]", title, index, size).into();
+
+        config.render_block(text.can_activate() && is_active, is_mouse_over)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("pod_log_pane_widget:default/app-0", true)]
+    #[case("pod_log_pane_widget", false)]
+    #[case("pod_log_pane_widgets:default/app-0", false)]
+    #[case("pod_log_widget", false)]
+    fn is_log_pane_widget_id(#[case] id: &str, #[case] expected: bool) {
+        assert_eq!(super::is_log_pane_widget_id(id), expected);
+    }
+}
@@ -6,6 +6,7 @@ use ratatui::layout::{Constraint, Direction};
 use crate::{
     clipboard::Clipboard,
     features::component_id::{POD_TAB_ID, POD_WIDGET_ID},
+    features::port_forward::view::SharedPortForwardState,
     kube::context::Namespace,
     message::Message,
     ui::{
@@ -15,27 +16,54 @@ use crate::{
     },
 };
 
-use super::widgets::{log_query_help_widget, log_query_widget, log_widget, pod_widget};
+use super::{
+    debug_popup_widget, debug_status_popup_widget, exec_popup_widget, is_log_pane_widget_id,
+    widgets::{
+        log_query_help_widget, log_query_widget, log_save_popup_widget,
+        log_save_status_popup_widget, log_widget, pod_widget, SharedLogState,
+    },
+    SharedDebugState,
+};
 
 pub struct PodTab {
     pub tab: Tab<'static>,
     pub log_query_help_popup: Widget<'static>,
+    pub log_save_popup: Widget<'static>,
+    pub log_save_status_popup: Widget<'static>,
+    pub exec_popup: Widget<'static>,
+    pub debug_popup: Widget<'static>,
+    pub debug_status_popup: Widget<'static>,
 }
 
 impl PodTab {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         title: &'static str,
         tx: &Sender<Message>,
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
         split_direction: Direction,
         namespaces: Rc<RefCell<Namespace>>,
+        log_max_lines: Option<usize>,
+        log_state: &SharedLogState,
+        port_forward_state: &SharedPortForwardState,
+        debug_state: &SharedDebugState,
     ) -> Self {
-        let pod_widget = pod_widget(tx);
-        let log_query_widget = log_query_widget(tx, namespaces);
-        let log_widget = log_widget(clipboard);
+        let pod_widget = pod_widget(
+            tx,
+            log_state.clone(),
+            port_forward_state.clone(),
+            debug_state.clone(),
+        );
+        let log_query_widget = log_query_widget(tx, namespaces, log_state.clone());
+        let log_widget = log_widget(tx, clipboard, log_max_lines, log_state.clone());
         let log_query_help_widget = log_query_help_widget();
+        let log_save_popup_widget = log_save_popup_widget(tx, log_state.clone());
+        let log_save_status_popup_widget = log_save_status_popup_widget();
+        let exec_popup_widget = exec_popup_widget();
+        let debug_popup_widget = debug_popup_widget(tx, debug_state.clone());
+        let debug_status_popup_widget = debug_status_popup_widget(tx, debug_state.clone());
 
-        let layout = TabLayout::new(layout, split_direction);
+        let layout = TabLayout::dynamic(layout, split_direction);
 
         let mut tab = Tab::new(
             POD_TAB_ID,
@@ -49,11 +77,16 @@ impl PodTab {
         Self {
             tab,
             log_query_help_popup: log_query_help_widget,
+            log_save_popup: log_save_popup_widget,
+            log_save_status_popup: log_save_status_popup_widget,
+            exec_popup: exec_popup_widget,
+            debug_popup: debug_popup_widget,
+            debug_status_popup: debug_status_popup_widget,
         }
     }
 }
 
-fn layout(split_direction: Direction) -> NestedWidgetLayout {
+fn layout(split_direction: Direction, widget_ids: &[&str]) -> NestedWidgetLayout {
     let (pod_constraint, log_constraint) = match split_direction {
         Direction::Horizontal => (Constraint::Percentage(50), Constraint::Percentage(50)),
         Direction::Vertical => (Constraint::Percentage(45), Constraint::Percentage(55)),
@@ -64,15 +97,53 @@ fn layout(split_direction: Direction) -> NestedWidgetLayout {
     let log_query_layout =
         NestedLayoutElement(Constraint::Length(3), LayoutElement::WidgetIndex(1));
 
+    let pane_indices: Vec<usize> = widget_ids
+        .iter()
+        .enumerate()
+        .filter(|(_, id)| is_log_pane_widget_id(id))
+        .map(|(i, _)| i)
+        .collect();
+
+    // ペイン分割時のログウィジェットにはエラーだけが出力されるため1行に縮め、
+    // その下にPodごとのペインを横に並べる
+    let log_layouts = if pane_indices.is_empty() {
+        vec![
+            log_query_layout,
+            NestedLayoutElement(Constraint::Min(3), LayoutElement::WidgetIndex(2)),
+        ]
+    } else {
+        let pane_len = pane_indices.len() as u32;
+
+        let panes = pane_indices
+            .into_iter()
+            .map(|i| {
+                NestedLayoutElement(
+                    Constraint::Ratio(1, pane_len),
+                    LayoutElement::WidgetIndex(i),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        vec![
+            log_query_layout,
+            NestedLayoutElement(Constraint::Length(3), LayoutElement::WidgetIndex(2)),
+            NestedLayoutElement(
+                Constraint::Min(3),
+                LayoutElement::NestedElement(
+                    NestedWidgetLayout::default()
+                        .direction(Direction::Horizontal)
+                        .nested_widget_layout(panes),
+                ),
+            ),
+        ]
+    };
+
     let log_layout = NestedLayoutElement(
         log_constraint,
         LayoutElement::NestedElement(
             NestedWidgetLayout::default()
                 .direction(Direction::Vertical)
-                .nested_widget_layout([
-                    log_query_layout,
-                    NestedLayoutElement(Constraint::Min(3), LayoutElement::WidgetIndex(2)),
-                ]),
+                .nested_widget_layout(log_layouts),
         ),
     );
 
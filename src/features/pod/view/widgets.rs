@@ -1,9 +1,14 @@
 mod log;
 mod log_query;
 mod log_query_help;
+mod log_save;
 mod pod;
 
 pub(super) use log::*;
+pub use log::{append_log, LogState, SharedLogState};
+pub use log_query::run_log_query;
 pub(super) use log_query::*;
 pub(super) use log_query_help::*;
+pub use log_save::show_log_save_result;
+pub(super) use log_save::*;
 pub(super) use pod::*;
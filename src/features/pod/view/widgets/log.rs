@@ -1,26 +1,303 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
 
-use ratatui::{crossterm::event::KeyCode, widgets::Block};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use crossbeam::channel::Sender;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    widgets::Block,
+};
 
 use crate::{
     clipboard::Clipboard,
-    features::component_id::POD_LOG_WIDGET_ID,
-    message::UserEvent,
+    features::{
+        component_id::{POD_LOG_WIDGET_ID, POD_TAB_ID},
+        pod::{
+            kube::LogConfig,
+            message::{LogEntry, LogMessage},
+            view::remove_log_panes,
+        },
+    },
+    message::{Message, UserEvent},
     ui::{
         event::EventResult,
-        widget::{config::WidgetConfig, Item, Text, Widget, WidgetTrait as _},
+        util::chars::convert_tabs_to_spaces,
+        widget::{config::WidgetConfig, Item, LiteralItem, Text, Widget, WidgetTrait as _},
         Window,
     },
+    workers::kube::color::fg::Color,
 };
 
-pub fn log_widget(clipboard: &Option<Rc<RefCell<Clipboard>>>) -> Widget<'static> {
-    let builder = Text::builder()
+use super::{save_log, LogSnapshot};
+
+/// ログ行の先頭に表示するタイムスタンプの形式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogTimestampType {
+    #[default]
+    None,
+    /// Kubernetesが付与したRFC3339形式のタイムスタンプ
+    Rfc3339,
+    /// ローカルタイムゾーンの時刻
+    Local,
+    /// 同じウィジェットに表示した直前の行からの経過時間
+    Delta,
+}
+
+impl LogTimestampType {
+    /// None → Rfc3339 → Local → Delta → None の順に切り替える
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::Rfc3339,
+            Self::Rfc3339 => Self::Local,
+            Self::Local => Self::Delta,
+            Self::Delta => Self::None,
+        }
+    }
+
+    fn format(self, dt: DateTime<Utc>, prev: Option<DateTime<Utc>>) -> Option<String> {
+        match self {
+            Self::None => None,
+            Self::Rfc3339 => Some(dt.to_rfc3339_opts(SecondsFormat::Nanos, true)),
+            Self::Local => Some(
+                dt.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+                    .to_string(),
+            ),
+            Self::Delta => {
+                let millis = prev
+                    .map(|prev| (dt - prev).num_milliseconds().max(0))
+                    .unwrap_or_default();
+
+                Some(format!(
+                    "{:>10}",
+                    format!("+{}.{:03}s", millis / 1000, millis % 1000)
+                ))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for LogTimestampType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Rfc3339 => write!(f, "rfc3339"),
+            Self::Local => write!(f, "local"),
+            Self::Delta => write!(f, "delta"),
+        }
+    }
+}
+
+/// ログウィジェットの表示状態
+///
+/// ログクエリフォームとPodテーブルから実行したクエリを保持し、
+/// ペイン表示の切り替え時に同じクエリを再実行する。
+/// タイムスタンプの表示形式の切り替え時は、ウィジェットが保持する行を描画し直す
+#[derive(Debug, Default)]
+pub struct LogState {
+    timestamp_type: LogTimestampType,
+    split_panes: bool,
+    config: Option<LogConfig>,
+    max_lines: Option<usize>,
+    /// ウィジェットIDごとの、表示中の各行のタイムスタンプ
+    ///
+    /// ログ本文はウィジェットが保持しているため、描画し直しに必要な情報だけを持つ
+    lines: HashMap<String, VecDeque<Option<LineTimestamp>>>,
+    /// ファイル名の入力中のログ
+    pub(super) save: Option<LogSnapshot>,
+}
+
+pub type SharedLogState = Rc<RefCell<LogState>>;
+
+/// 表示中の行に付けたタイムスタンプ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LineTimestamp {
+    timestamp: DateTime<Utc>,
+    /// 行の先頭に付けたタイムスタンプ部分のバイト数
+    prefix_len: usize,
+}
+
+impl LogState {
+    /// ウィジェットと同じく、max_linesを超えた古いログは破棄する
+    pub fn new(max_lines: Option<usize>) -> Self {
+        Self {
+            max_lines,
+            ..Default::default()
+        }
+    }
+
+    /// 現在の表示設定でログの取得をリクエストする
+    pub fn request(&mut self, tx: &Sender<Message>, config: LogConfig) {
+        let config = config.split_panes(self.split_panes);
+
+        self.config = Some(config.clone());
+
+        tx.send(LogMessage::Request(config).into())
+            .expect("Failed to send LogMessage::Request");
+    }
+
+    /// 受信したログを現在の表示形式で整形し、各行のタイムスタンプを保持する
+    pub(crate) fn push(&mut self, id: &str, entries: Vec<LogEntry>) -> Vec<LiteralItem> {
+        let timestamp_type = self.timestamp_type;
+
+        let lines = self.lines.entry(id.to_string()).or_default();
+
+        let mut prev = lines
+            .iter()
+            .rev()
+            .find_map(|line| line.map(|l| l.timestamp));
+
+        let items = entries
+            .into_iter()
+            .map(|entry| {
+                let content = convert_tabs_to_spaces(&entry.content);
+
+                let (item, line) =
+                    format_line(timestamp_type, entry.timestamp, &content, &mut prev);
+
+                lines.push_back(line);
+
+                item
+            })
+            .collect();
+
+        // ウィジェットと同じく、max_linesを超えた古い行は破棄する
+        if let Some(max_lines) = self.max_lines {
+            let overflow = lines.len().saturating_sub(max_lines);
+
+            lines.drain(..overflow);
+        }
+
+        items
+    }
+
+    /// ウィジェットが保持する行を現在の表示形式で整形し直す
+    ///
+    /// 行の末尾を揃えて対応付け、タイムスタンプを持たない行はそのまま返す
+    fn render<'a>(
+        &mut self,
+        id: &str,
+        literal_lines: impl ExactSizeIterator<Item = &'a str>,
+    ) -> Vec<LiteralItem> {
+        let timestamp_type = self.timestamp_type;
+
+        let lines = self.lines.entry(id.to_string()).or_default();
+
+        let overflow = lines.len().saturating_sub(literal_lines.len());
+
+        lines.drain(..overflow);
+
+        let missing = literal_lines.len() - lines.len();
+
+        let mut prev = None;
+
+        let (items, rendered): (Vec<_>, VecDeque<_>) = literal_lines
+            .zip(std::iter::repeat_n(None, missing).chain(lines.iter().copied()))
+            .map(|(literal, line)| match line {
+                Some(LineTimestamp {
+                    timestamp,
+                    prefix_len,
+                }) => format_line(
+                    timestamp_type,
+                    Some(timestamp),
+                    &literal[prefix_len..],
+                    &mut prev,
+                ),
+                None => (LiteralItem::new(literal, None), None),
+            })
+            .unzip();
+
+        *lines = rendered;
+
+        items
+    }
+
+    pub(crate) fn remove(&mut self, id: &str) {
+        self.lines.remove(id);
+    }
+
+    /// 表示中のクエリと各行のタイムスタンプを破棄する
+    pub fn reset(&mut self) {
+        self.config = None;
+        self.lines.clear();
+    }
+}
+
+/// タイムスタンプを付けてログを1行に整形する
+///
+/// prevは直前に表示した行のタイムスタンプで、経過時間の計算に使う
+fn format_line(
+    timestamp_type: LogTimestampType,
+    timestamp: Option<DateTime<Utc>>,
+    content: &str,
+    prev: &mut Option<DateTime<Utc>>,
+) -> (LiteralItem, Option<LineTimestamp>) {
+    let Some(dt) = timestamp else {
+        return (LiteralItem::new(content, None), None);
+    };
+
+    let prefix = timestamp_type
+        .format(dt, *prev)
+        .map(|timestamp| format!("{} ", Color::DarkGray.wrap(timestamp)))
+        .unwrap_or_default();
+
+    *prev = Some(dt);
+
+    let line = LineTimestamp {
+        timestamp: dt,
+        prefix_len: prefix.len(),
+    };
+
+    (LiteralItem::new(prefix + content, None), Some(line))
+}
+
+/// ログウィジェットにログを追加する
+pub fn append_log(w: &mut Window, state: &SharedLogState, entries: Vec<LogEntry>) {
+    let items = state.borrow_mut().push(POD_LOG_WIDGET_ID, entries);
+
+    w.find_widget_mut(POD_LOG_WIDGET_ID)
+        .append_widget_item(Item::Array(items));
+}
+
+pub fn log_widget(
+    tx: &Sender<Message>,
+    clipboard: &Option<Rc<RefCell<Clipboard>>>,
+    max_lines: Option<usize>,
+    state: SharedLogState,
+) -> Widget<'static> {
+    let mut builder = Text::builder()
         .id(POD_LOG_WIDGET_ID)
         .widget_config(&WidgetConfig::builder().title("Log").build())
         .wrap()
         .follow()
-        .block_injection(block_injection())
-        .action(UserEvent::from(KeyCode::Enter), add_blankline());
+        .block_injection(block_injection(state.clone()))
+        .action(
+            UserEvent::from(KeyCode::Enter),
+            add_blankline(state.clone()),
+        )
+        .action(
+            UserEvent::from(KeyCode::Char('t')),
+            toggle_timestamp(state.clone()),
+        )
+        .action(
+            UserEvent::from(KeyCode::Char('p')),
+            toggle_split_panes(tx.clone(), state.clone()),
+        )
+        .action(
+            UserEvent::from(KeyCode::Char('s')),
+            save_log(false, state.clone()),
+        )
+        .action(
+            UserEvent::Key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            save_log(true, state),
+        );
+
+    if let Some(max_lines) = max_lines {
+        builder = builder.max_lines(max_lines);
+    }
 
     if let Some(cb) = clipboard {
         builder.clipboard(cb.clone())
@@ -31,25 +308,334 @@ pub fn log_widget(clipboard: &Option<Rc<RefCell<Clipboard>>>) -> Widget<'static>
     .into()
 }
 
-fn block_injection() -> impl Fn(&Text, bool, bool) -> Block<'static> {
-    |text: &Text, is_active: bool, is_mouse_over: bool| {
+fn block_injection(state: SharedLogState) -> impl Fn(&Text, bool, bool) -> Block<'static> {
+    move |text: &Text, is_active: bool, is_mouse_over: bool| {
         let (index, size) = text.state();
 
         let mut config = text.widget_config().clone();
 
-        *config.title_mut() = format!("Log [{}/{}]", index, size).into();
+        let mut title = format!("Log [{}/{}]", index, size);
+
+        let timestamp_type = state.borrow().timestamp_type;
+
+        if timestamp_type != LogTimestampType::None {
+            title += &format!(" [timestamp: {}]", timestamp_type);
+        }
+
+        if state.borrow().split_panes {
+            title += " [split panes]";
+        }
+
+        let truncated_lines = text.truncated_lines();
+
+        if 0 < truncated_lines {
+            title += &format!(" ({} old lines dropped)", truncated_lines);
+        }
+
+        *config.title_mut() = title.into();
 
         config.render_block(text.can_activate() && is_active, is_mouse_over)
     }
 }
 
-fn add_blankline() -> impl Fn(&mut Window) -> EventResult {
+fn add_blankline(state: SharedLogState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        w.find_widget_mut(POD_LOG_WIDGET_ID).select_last();
+
+        append_log(w, &state, vec![LogEntry::default()]);
+
+        EventResult::Nop
+    }
+}
+
+/// タイムスタンプの表示形式を切り替え、受信済みのログを描画し直す
+fn toggle_timestamp(state: SharedLogState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let mut state = state.borrow_mut();
+
+        state.timestamp_type = state.timestamp_type.next();
+
+        let Some(tab) = w.find_tab_mut(POD_TAB_ID) else {
+            return EventResult::Nop;
+        };
+
+        let ids: Vec<String> = state.lines.keys().cloned().collect();
+
+        for id in ids {
+            let Some(widget) = tab.find_widget_mut(&id) else {
+                state.remove(&id);
+                continue;
+            };
+
+            let items = state.render(&id, widget.as_text().literal_lines());
+
+            widget.update_widget_item(Item::Array(items));
+        }
+
+        EventResult::Nop
+    }
+}
+
+/// Podごとのペイン表示を切り替え、表示中のクエリを再実行する
+fn toggle_split_panes(
+    tx: Sender<Message>,
+    state: SharedLogState,
+) -> impl Fn(&mut Window) -> EventResult {
     move |w: &mut Window| {
-        let w = w.find_widget_mut(POD_LOG_WIDGET_ID);
+        let mut state = state.borrow_mut();
+
+        state.split_panes = !state.split_panes;
+
+        if let Some(config) = state.config.clone() {
+            w.widget_clear(POD_LOG_WIDGET_ID);
 
-        w.select_last();
-        w.append_widget_item(Item::Single(Default::default()));
+            remove_log_panes(w);
+
+            state.lines.clear();
+
+            state.request(&tx, config);
+        }
 
         EventResult::Nop
     }
 }
+
+/// ログウィジェットをクリアし、Podごとのペインを削除する
+pub fn clear_log(w: &mut Window, state: &SharedLogState) {
+    w.widget_clear(POD_LOG_WIDGET_ID);
+
+    remove_log_panes(w);
+
+    state.borrow_mut().lines.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn timestamp(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().into()
+    }
+
+    fn entry(ts: &str, content: &str) -> LogEntry {
+        LogEntry {
+            timestamp: Some(timestamp(ts)),
+            content: content.to_string(),
+        }
+    }
+
+    fn items(items: Vec<LiteralItem>) -> Vec<String> {
+        items.into_iter().map(|item| item.item).collect()
+    }
+
+    #[test]
+    fn タイムスタンプ表示を順に切り替える() {
+        let actual: Vec<_> =
+            std::iter::successors(Some(LogTimestampType::None), |t| Some(t.next()))
+                .take(5)
+                .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                LogTimestampType::None,
+                LogTimestampType::Rfc3339,
+                LogTimestampType::Local,
+                LogTimestampType::Delta,
+                LogTimestampType::None,
+            ]
+        );
+    }
+
+    #[test]
+    fn タイムスタンプをrfc3339形式で表示する() {
+        let actual = LogTimestampType::Rfc3339.format(timestamp("2024-01-01T00:00:01.5Z"), None);
+
+        assert_eq!(actual, Some("2024-01-01T00:00:01.500000000Z".to_string()));
+    }
+
+    #[test]
+    fn タイムスタンプをローカル時刻で表示する() {
+        let dt = timestamp("2024-01-01T00:00:01.5Z");
+
+        let actual = LogTimestampType::Local.format(dt, None);
+
+        let expected = dt
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S%.3f")
+            .to_string();
+
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[test]
+    fn 直前の行からの経過時間を表示する() {
+        let prev = timestamp("2024-01-01T00:00:00Z");
+
+        let actual: Vec<_> = [
+            (timestamp("2024-01-01T00:00:00Z"), None),
+            (timestamp("2024-01-01T00:00:01.234Z"), Some(prev)),
+            (timestamp("2024-01-01T00:01:05Z"), Some(prev)),
+            (timestamp("2023-12-31T23:59:59Z"), Some(prev)),
+        ]
+        .into_iter()
+        .map(|(dt, prev)| LogTimestampType::Delta.format(dt, prev).unwrap())
+        .collect();
+
+        assert_eq!(
+            actual,
+            vec!["   +0.000s", "   +1.234s", "  +65.000s", "   +0.000s"]
+        );
+    }
+
+    #[test]
+    fn タイムスタンプ非表示の場合は何も返さない() {
+        assert_eq!(
+            LogTimestampType::None.format(timestamp("2024-01-01T00:00:00Z"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn 経過時間は複数のコンテナを合わせた直前の行から計算する() {
+        let mut state = LogState {
+            timestamp_type: LogTimestampType::Delta,
+            ..Default::default()
+        };
+
+        state.push(
+            POD_LOG_WIDGET_ID,
+            vec![entry("2024-01-01T00:00:00Z", "[a] 1")],
+        );
+
+        let actual = state.push(
+            POD_LOG_WIDGET_ID,
+            vec![
+                LogEntry::new("+ b"),
+                entry("2024-01-01T00:00:01Z", "[b] 1"),
+                entry("2024-01-01T00:00:03Z", "[a] 2"),
+            ],
+        );
+
+        let ts = |s: &str| Color::DarkGray.wrap(s);
+
+        assert_eq!(
+            items(actual),
+            vec![
+                "+ b".to_string(),
+                format!("{} [b] 1", ts("   +1.000s")),
+                format!("{} [a] 2", ts("   +2.000s")),
+            ]
+        );
+    }
+
+    #[test]
+    fn 表示形式を切り替えるとウィジェットの行を整形し直す() {
+        let mut state = LogState::default();
+
+        let actual = items(state.push(
+            POD_LOG_WIDGET_ID,
+            vec![entry("2024-01-01T00:00:00Z", "a"), LogEntry::new("\tb")],
+        ));
+
+        assert_eq!(actual, vec!["a", "        b"]);
+
+        state.timestamp_type = LogTimestampType::Rfc3339;
+
+        let actual = items(state.render(POD_LOG_WIDGET_ID, actual.iter().map(String::as_str)));
+
+        assert_eq!(
+            actual,
+            vec![
+                format!(
+                    "{} a",
+                    Color::DarkGray.wrap("2024-01-01T00:00:00.000000000Z")
+                ),
+                "        b".to_string(),
+            ]
+        );
+
+        state.timestamp_type = LogTimestampType::None;
+
+        let actual = items(state.render(POD_LOG_WIDGET_ID, actual.iter().map(String::as_str)));
+
+        assert_eq!(actual, vec!["a", "        b"]);
+    }
+
+    #[test]
+    fn 最大行数を超えた古い行のタイムスタンプは破棄する() {
+        let mut state = LogState::new(Some(2));
+
+        state.push(
+            POD_LOG_WIDGET_ID,
+            vec![
+                entry("2024-01-01T00:00:01Z", "1"),
+                entry("2024-01-01T00:00:02Z", "2"),
+                entry("2024-01-01T00:00:03Z", "3"),
+            ],
+        );
+
+        assert_eq!(state.lines[POD_LOG_WIDGET_ID].len(), 2);
+
+        state.timestamp_type = LogTimestampType::Delta;
+
+        let actual = items(state.render(POD_LOG_WIDGET_ID, ["2", "3"].into_iter()));
+
+        let ts = |s: &str| Color::DarkGray.wrap(s);
+
+        assert_eq!(
+            actual,
+            vec![
+                format!("{} 2", ts("   +0.000s")),
+                format!("{} 3", ts("   +1.000s")),
+            ]
+        );
+    }
+
+    #[test]
+    fn ウィジェットの行数に合わせて末尾から対応付ける() {
+        let mut state = LogState::default();
+
+        state.push(
+            POD_LOG_WIDGET_ID,
+            vec![
+                entry("2024-01-01T00:00:01Z", "1"),
+                entry("2024-01-01T00:00:02Z", "2"),
+            ],
+        );
+
+        state.timestamp_type = LogTimestampType::Rfc3339;
+
+        let actual = items(state.render(POD_LOG_WIDGET_ID, ["2"].into_iter()));
+
+        assert_eq!(
+            actual,
+            vec![format!(
+                "{} 2",
+                Color::DarkGray.wrap("2024-01-01T00:00:02.000000000Z")
+            )]
+        );
+
+        assert_eq!(state.lines[POD_LOG_WIDGET_ID].len(), 1);
+    }
+
+    #[test]
+    fn ペインを閉じるかログをクリアすると保持した行を破棄する() {
+        let mut state = LogState::default();
+
+        let pane = "pod-log-pane-app";
+
+        state.push(POD_LOG_WIDGET_ID, vec![entry("2024-01-01T00:00:00Z", "a")]);
+        state.push(pane, vec![entry("2024-01-01T00:00:00Z", "b")]);
+
+        state.remove(pane);
+
+        assert!(!state.lines.contains_key(pane));
+        assert!(state.lines.contains_key(POD_LOG_WIDGET_ID));
+
+        state.reset();
+
+        assert!(state.lines.is_empty());
+    }
+}
@@ -5,40 +5,61 @@ use ratatui::crossterm::event::KeyCode;
 
 use crate::{
     features::{
-        component_id::{POD_LOG_QUERY_HELP_POPUP_ID, POD_LOG_QUERY_WIDGET_ID, POD_LOG_WIDGET_ID},
-        pod::{
-            kube::{LogConfig, LogPrefixType},
-            message::LogMessage,
-        },
+        component_id::{POD_LOG_QUERY_HELP_POPUP_ID, POD_LOG_QUERY_WIDGET_ID, POD_TAB_ID},
+        pod::kube::{LogConfig, LogPrefixType},
     },
     kube::context::Namespace,
     message::{Message, UserEvent},
     ui::{
         event::EventResult,
         widget::{
-            config::WidgetConfig, input::InputFormBuilder, SelectedItem, Widget, WidgetTrait as _,
+            config::WidgetConfig, input::InputFormBuilder, Item, SelectedItem, Widget,
+            WidgetTrait as _,
         },
         Window,
     },
 };
 
+use super::{clear_log, SharedLogState};
+
 pub fn log_query_widget(
     tx: &Sender<Message>,
     namespaces: Rc<RefCell<Namespace>>,
+    log_state: SharedLogState,
 ) -> Widget<'static> {
     let tx = tx.clone();
 
     InputFormBuilder::default()
         .id(POD_LOG_QUERY_WIDGET_ID)
         .widget_config(WidgetConfig::builder().title("Log Query").build())
-        .actions(UserEvent::from(KeyCode::Enter), exec_query(tx, namespaces))
+        .actions(
+            UserEvent::from(KeyCode::Enter),
+            exec_query(tx, namespaces, log_state),
+        )
         .build()
         .into()
 }
 
+/// Podタブに移動してログクエリを実行する
+///
+/// 他のタブの操作からログを表示するときに使う
+pub fn run_log_query(w: &mut Window, query: &str) {
+    w.close_popup();
+
+    w.activate_tab_by_id(POD_TAB_ID);
+    w.activate_widget_by_id(POD_LOG_QUERY_WIDGET_ID);
+
+    w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID)
+        .update_widget_item(Item::Single(query.into()));
+
+    // 入力欄でEnterを押したときと同じくexec_queryでクエリを実行する
+    w.on_event(UserEvent::from(KeyCode::Enter));
+}
+
 fn exec_query(
     tx: Sender<Message>,
     namespaces: Rc<RefCell<Namespace>>,
+    log_state: SharedLogState,
 ) -> impl Fn(&mut Window) -> EventResult {
     move |w: &mut Window| {
         let widget = w.find_widget_mut(POD_LOG_QUERY_WIDGET_ID);
@@ -53,7 +74,7 @@ fn exec_query(
             return EventResult::Nop;
         }
 
-        w.widget_clear(POD_LOG_WIDGET_ID);
+        clear_log(w, &log_state);
 
         let namespaces = namespaces.borrow();
 
@@ -65,8 +86,7 @@ fn exec_query(
 
         let config = LogConfig::new(item, namespaces.to_owned(), prefix_type);
 
-        tx.send(LogMessage::Request(config).into())
-            .expect("Failed to send LogMessage::Request");
+        log_state.borrow_mut().request(&tx, config);
 
         EventResult::Ignore
     }
@@ -34,8 +34,27 @@ fn content() -> Vec<String> {
            !log:<regex>          (alias: !logs, !lo, !l)
            label:<selector>      (alias: labels)
            field:<selector>      (alias: fields)
+           json:<path>=<regex>
+           since:<duration|rfc3339>
+           tail:<number>
+           previous:
+           pretty:
+           reorder:[<duration>]
+           highlight:<regex>[=<color>]
            <resource>/<name>
 
+        Operators:
+           QUERY or QUERY        (alias: OR, |)
+           QUERY and QUERY       (alias: AND)
+           ( QUERY [ QUERY ]... )
+
+           Only pod, container, log and json queries can be combined.
+           Space-separated queries must all match, except that multiple
+           log queries match any of them, both inside and outside ( ).
+           `|` is `or` between spaces (pod:a | pod:b), or inside ( )
+           when a query follows it ((pod:a|pod:b)); otherwise it is
+           part of the regex (log:error|warn, log:a|pod:b).
+
         Resources:
            pod            (alias: pods, po)
            replicaset     (alias: replicasets, rs)
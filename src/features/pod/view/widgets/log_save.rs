@@ -0,0 +1,256 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    ansi::strip_ansi,
+    features::{
+        component_id::{
+            POD_LOG_PANE_WIDGET_ID, POD_LOG_QUERY_WIDGET_ID, POD_LOG_SAVE_POPUP_ID,
+            POD_LOG_SAVE_STATUS_POPUP_ID,
+        },
+        pod::message::{LogMessage, LogSaveRequest, LogSaveResponse},
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            config::WidgetConfig, input::InputFormBuilder, Item, LiteralItem, SelectedItem, Text,
+            Widget, WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+use super::SharedLogState;
+
+/// ファイル名に使うクエリの最大文字数
+const MAX_QUERY_FILE_NAME_LEN: usize = 64;
+
+/// 保存するログ
+///
+/// ファイル名の入力中に届いたログは含めず、保存操作をした時点の内容を保存する
+#[derive(Debug)]
+pub struct LogSnapshot {
+    lines: Vec<String>,
+    raw: bool,
+}
+
+pub fn log_save_popup_widget(tx: &Sender<Message>, state: SharedLogState) -> Widget<'static> {
+    InputFormBuilder::default()
+        .id(POD_LOG_SAVE_POPUP_ID)
+        .widget_config(WidgetConfig::builder().title("Save log as").build())
+        .actions(UserEvent::from(KeyCode::Enter), save(tx.clone(), state))
+        .build()
+        .into()
+}
+
+pub fn log_save_status_popup_widget() -> Widget<'static> {
+    Text::builder()
+        .id(POD_LOG_SAVE_STATUS_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Save Log").build())
+        .wrap()
+        .action(UserEvent::from(KeyCode::Enter), close_popup())
+        .build()
+        .into()
+}
+
+/// 選択中のログウィジェットの内容を保持し、保存先のファイル名の入力ポップアップを開く
+///
+/// ペイン分割時は選択中のペインのログを保存する。
+/// rawがfalseの場合はANSIエスケープシーケンスを取り除いて保存する
+pub fn save_log(raw: bool, state: SharedLogState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let id = w.active_widget_id().to_string();
+
+        let lines = w
+            .find_widget(&id)
+            .as_text()
+            .literal_lines()
+            .map(|line| {
+                if raw {
+                    line.to_string()
+                } else {
+                    strip_ansi(line)
+                }
+            })
+            .collect();
+
+        let name = match id
+            .strip_prefix(POD_LOG_PANE_WIDGET_ID)
+            .and_then(|id| id.strip_prefix(':'))
+        {
+            Some(pane) => pane.to_string(),
+            None => match w.find_widget(POD_LOG_QUERY_WIDGET_ID).widget_item() {
+                Some(SelectedItem::Literal { item, .. }) => item,
+                _ => String::default(),
+            },
+        };
+
+        state.borrow_mut().save = Some(LogSnapshot { lines, raw });
+
+        w.find_widget_mut(POD_LOG_SAVE_POPUP_ID)
+            .update_widget_item(Item::Single(default_file_name(&name, Local::now()).into()));
+
+        w.open_popup(POD_LOG_SAVE_POPUP_ID);
+
+        EventResult::Nop
+    }
+}
+
+/// 入力されたファイル名で保存をリクエストする
+fn save(tx: Sender<Message>, state: SharedLogState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::Literal { item, .. }) =
+            w.find_widget(POD_LOG_SAVE_POPUP_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let path = item.trim();
+
+        if path.is_empty() {
+            return EventResult::Ignore;
+        }
+
+        let Some(LogSnapshot { lines, raw }) = state.borrow_mut().save.take() else {
+            return EventResult::Ignore;
+        };
+
+        update_status(
+            w,
+            vec![format!("Saving {} lines to {} ...", lines.len(), path)],
+        );
+
+        w.open_popup(POD_LOG_SAVE_STATUS_POPUP_ID);
+
+        tx.send(
+            LogMessage::SaveRequest(LogSaveRequest {
+                path: PathBuf::from(path),
+                lines,
+                raw,
+            })
+            .into(),
+        )
+        .expect("Failed to send LogMessage::SaveRequest");
+
+        EventResult::Nop
+    }
+}
+
+/// 保存結果をポップアップに表示する
+pub fn show_log_save_result(w: &mut Window, res: LogSaveResponse) {
+    let LogSaveResponse {
+        path,
+        lines,
+        raw,
+        result,
+    } = res;
+
+    let message = match result {
+        Ok(_) => vec![
+            format!("Saved {} lines to {}", lines, path.display()),
+            String::default(),
+            format!(
+                "ANSI colors: {}",
+                if raw { "kept (raw)" } else { "stripped" }
+            ),
+        ],
+        Err(err) => vec![format!(
+            "\x1b[31mFailed to save log to {}: {:#}\x1b[39m",
+            path.display(),
+            err
+        )],
+    };
+
+    update_status(w, message);
+
+    w.open_popup(POD_LOG_SAVE_STATUS_POPUP_ID);
+}
+
+fn update_status(w: &mut Window, lines: Vec<String>) {
+    let items = lines
+        .into_iter()
+        .chain([
+            String::default(),
+            "Press \x1b[1mEnter\x1b[0m or \x1b[1mEsc\x1b[0m to close.".to_string(),
+        ])
+        .map(|line| LiteralItem::new(line, None))
+        .collect();
+
+    w.find_widget_mut(POD_LOG_SAVE_STATUS_POPUP_ID)
+        .update_widget_item(Item::Array(items));
+}
+
+/// クエリと時刻から `kubetui_<query>_<YYYYmmdd-HHMMSS>.log` 形式のファイル名を生成する
+///
+/// クエリに含まれる英数字、`-`、`.` 以外の文字は `_` に置き換える
+fn default_file_name(query: &str, now: DateTime<Local>) -> String {
+    let mut name = String::new();
+
+    for c in query.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+            name.push(c);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+
+    let name: String = name
+        .trim_matches(['_', '.'])
+        .chars()
+        .take(MAX_QUERY_FILE_NAME_LEN)
+        .collect();
+
+    let timestamp = now.format("%Y%m%d-%H%M%S");
+
+    if name.is_empty() {
+        format!("kubetui_{}.log", timestamp)
+    } else {
+        format!("kubetui_{}_{}.log", name, timestamp)
+    }
+}
+
+fn close_popup() -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        w.close_popup();
+        EventResult::Nop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone as _;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("pod/app-0", "kubetui_pod_app-0_20240102-030405.log")]
+    #[case(
+        "pod:api log:'error|panic'",
+        "kubetui_pod_api_log_error_panic_20240102-030405.log"
+    )]
+    #[case("../../etc", "kubetui_etc_20240102-030405.log")]
+    #[case("", "kubetui_20240102-030405.log")]
+    #[case("  ", "kubetui_20240102-030405.log")]
+    fn default_file_name(#[case] query: &str, #[case] expected: &str) {
+        let now = Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+        assert_eq!(super::default_file_name(query, now), expected);
+    }
+
+    #[test]
+    fn default_file_name_truncates_long_query() {
+        let now = Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+        let actual = super::default_file_name(&"a".repeat(100), now);
+
+        assert_eq!(
+            actual,
+            format!("kubetui_{}_20240102-030405.log", "a".repeat(64))
+        );
+    }
+}
@@ -1,15 +1,17 @@
 use crossbeam::channel::Sender;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::{
     features::{
-        component_id::{POD_LOG_QUERY_WIDGET_ID, POD_LOG_WIDGET_ID, POD_WIDGET_ID},
+        component_id::{POD_LOG_QUERY_WIDGET_ID, POD_WIDGET_ID},
         pod::{
             kube::{LogConfig, LogPrefixType},
-            message::LogMessage,
+            view::{request_debug, request_exec, SharedDebugState},
         },
+        port_forward::view::{request_port_forward, SharedPortForwardState},
     },
     kube::context::Namespace,
-    message::Message,
+    message::{Message, UserEvent},
     ui::{
         event::EventResult,
         widget::{config::WidgetConfig, Item, Table, TableItem, Widget, WidgetTrait as _},
@@ -17,7 +19,14 @@ use crate::{
     },
 };
 
-pub fn pod_widget(tx: &Sender<Message>) -> Widget<'static> {
+use super::{clear_log, SharedLogState};
+
+pub fn pod_widget(
+    tx: &Sender<Message>,
+    log_state: SharedLogState,
+    port_forward_state: SharedPortForwardState,
+    debug_state: SharedDebugState,
+) -> Widget<'static> {
     let tx = tx.clone();
 
     Table::builder()
@@ -25,7 +34,13 @@ pub fn pod_widget(tx: &Sender<Message>) -> Widget<'static> {
         .widget_config(&WidgetConfig::builder().title("Pod").build())
         .filtered_key("NAME")
         .block_injection(block_injection())
-        .on_select(on_select(tx))
+        .on_select(on_select(tx.clone(), log_state))
+        .action('e', request_exec(tx))
+        .action('f', request_port_forward(port_forward_state))
+        .action(
+            UserEvent::Key(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT)),
+            request_debug(debug_state),
+        )
         .build()
         .into()
 }
@@ -47,9 +62,12 @@ fn block_injection() -> impl Fn(&Table) -> WidgetConfig {
     }
 }
 
-fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventResult {
+fn on_select(
+    tx: Sender<Message>,
+    log_state: SharedLogState,
+) -> impl Fn(&mut Window, &TableItem) -> EventResult {
     move |w: &mut Window, v: &TableItem| {
-        w.widget_clear(POD_LOG_WIDGET_ID);
+        clear_log(w, &log_state);
 
         let Some(ref metadata) = v.metadata else {
             return EventResult::Ignore;
@@ -75,8 +93,7 @@ fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &TableItem) -> EventRe
             LogPrefixType::OnlyContainer,
         );
 
-        tx.send(LogMessage::Request(config).into())
-            .expect("Failed to send LogMessage::Request");
+        log_state.borrow_mut().request(&tx, config);
 
         EventResult::WindowAction(WindowAction::Continue)
     }
@@ -0,0 +1,5 @@
+mod forwarder;
+mod poller;
+
+pub use forwarder::*;
+pub use poller::*;
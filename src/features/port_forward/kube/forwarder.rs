@@ -0,0 +1,660 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use k8s_openapi::{
+    api::core::v1::{Pod, Service, ServicePort},
+    apimachinery::pkg::util::intstr::IntOrString,
+};
+use kube::{api::ListParams, Api, Client};
+use tokio::{
+    io::{self, AsyncRead, AsyncWrite, ReadBuf},
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+    task::{AbortHandle, JoinSet},
+};
+
+use crate::{
+    features::port_forward::message::{PortForwardKind, PortForwardTarget, PortMapping},
+    kube::table::{KubeTable, KubeTableRow},
+    logger,
+};
+
+/// ローカルで待ち受けるアドレス
+const LISTEN_ADDRESS: &str = "127.0.0.1";
+
+pub type SharedPortForwards = Arc<PortForwards>;
+
+/// 実行中のポートフォワードを管理する
+///
+/// 指定されたポートで受け付けた接続ごとにPodのportforwardサブリソースへ接続し、
+/// 転送量を計測しながら中継する
+#[derive(Debug, Default)]
+pub struct PortForwards {
+    context: RwLock<String>,
+    next_id: AtomicU64,
+    forwards: RwLock<Vec<PortForward>>,
+}
+
+#[derive(Debug)]
+struct PortForward {
+    id: u64,
+    target: PortForwardTarget,
+    ports: PortMapping,
+    stats: Arc<PortForwardStats>,
+    handle: AbortHandle,
+}
+
+impl Drop for PortForward {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[derive(Debug, Default)]
+struct PortForwardStats {
+    sent: AtomicU64,
+    received: AtomicU64,
+    connections: AtomicUsize,
+    status: Mutex<PortForwardStatus>,
+}
+
+impl PortForwardStats {
+    fn set_status(&self, status: PortForwardStatus) {
+        *self.status.lock().expect("Failed to lock status") = status;
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+enum PortForwardStatus {
+    #[default]
+    Starting,
+    Running,
+    Failed(String),
+}
+
+impl fmt::Display for PortForwardStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Starting => write!(f, "Starting"),
+            Self::Running => write!(f, "Running"),
+            Self::Failed(err) => write!(f, "\x1b[31mFailed: {}\x1b[39m", err),
+        }
+    }
+}
+
+impl PortForwards {
+    pub fn new(context: &str) -> Self {
+        Self {
+            context: RwLock::new(context.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// コンテキストを切り替える
+    ///
+    /// 実行中のポートフォワードは切り替え前のクラスタに接続しているため、全て停止する
+    pub async fn switch_context(&self, context: &str) {
+        let mut current = self.context.write().await;
+
+        if *current != context {
+            self.stop_all().await;
+
+            *current = context.to_string();
+        }
+    }
+
+    pub async fn start(&self, client: Client, target: PortForwardTarget, ports: PortMapping) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        logger!(
+            info,
+            "port-forward: {} {}:{}",
+            target,
+            ports.local,
+            ports.remote
+        );
+
+        let stats = Arc::new(PortForwardStats::default());
+
+        let handle =
+            tokio::spawn(forward(client, target.clone(), ports, stats.clone())).abort_handle();
+
+        self.forwards.write().await.push(PortForward {
+            id,
+            target,
+            ports,
+            stats,
+            handle,
+        });
+    }
+
+    pub async fn stop(&self, id: u64) {
+        self.forwards
+            .write()
+            .await
+            .retain(|forward| forward.id != id);
+    }
+
+    pub async fn stop_all(&self) {
+        self.forwards.write().await.clear();
+    }
+
+    pub async fn to_table(&self) -> KubeTable {
+        let forwards = self.forwards.read().await;
+
+        let rows = forwards
+            .iter()
+            .map(|forward| {
+                let PortForward {
+                    id,
+                    target,
+                    ports,
+                    stats,
+                    ..
+                } = forward;
+
+                let status = stats.status.lock().expect("Failed to lock status").clone();
+
+                KubeTableRow {
+                    namespace: target.namespace.to_string(),
+                    name: target.resource(),
+                    metadata: Some([("id".to_string(), id.to_string())].into()),
+                    row: vec![
+                        target.namespace.to_string(),
+                        target.resource(),
+                        format!("{}:{}", LISTEN_ADDRESS, ports.local),
+                        ports.remote.to_string(),
+                        stats.connections.load(Ordering::Relaxed).to_string(),
+                        format_bytes(stats.sent.load(Ordering::Relaxed)),
+                        format_bytes(stats.received.load(Ordering::Relaxed)),
+                        status.to_string(),
+                    ],
+                }
+            })
+            .collect();
+
+        KubeTable {
+            header: [
+                "NAMESPACE",
+                "TARGET",
+                "LOCAL",
+                "REMOTE",
+                "CONNECTIONS",
+                "SENT",
+                "RECEIVED",
+                "STATUS",
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+            rows,
+            ..Default::default()
+        }
+    }
+}
+
+/// 接続を転送するPodとポート
+#[derive(Clone)]
+struct Upstream {
+    api: Api<Pod>,
+    pod: String,
+    port: u16,
+}
+
+impl Upstream {
+    /// 転送先のPodとポートを決める
+    ///
+    /// Serviceの場合は `kubectl port-forward` と同じく、selectorに一致する実行中のPodを1つ選び、
+    /// Serviceのポートに対応するtargetPortに転送する
+    async fn resolve(client: Client, target: &PortForwardTarget, port: u16) -> Result<Self> {
+        let api: Api<Pod> = Api::namespaced(client.clone(), &target.namespace);
+
+        match target.kind {
+            PortForwardKind::Pod => Ok(Self {
+                api,
+                pod: target.name.to_string(),
+                port,
+            }),
+            PortForwardKind::Service => {
+                let service = Api::<Service>::namespaced(client, &target.namespace)
+                    .get(&target.name)
+                    .await?;
+
+                let spec = service.spec.unwrap_or_default();
+
+                let selector = spec
+                    .selector
+                    .filter(|selector| !selector.is_empty())
+                    .ok_or_else(|| anyhow!("{} has no selector", target.resource()))?;
+
+                let service_port = spec
+                    .ports
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|p| p.port == i32::from(port))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "{} does not have a service port {}",
+                            target.resource(),
+                            port
+                        )
+                    })?;
+
+                let label_selector = selector
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                let pods = api
+                    .list(&ListParams::default().labels(&label_selector))
+                    .await?;
+
+                let pod = running_pod(pods.items)
+                    .ok_or_else(|| anyhow!("No running pod found for {}", target.resource()))?;
+
+                let port = target_port(&service_port, &pod)?;
+
+                Ok(Self {
+                    api,
+                    pod: pod.metadata.name.unwrap_or_default(),
+                    port,
+                })
+            }
+        }
+    }
+}
+
+/// 実行中のPodを名前順で最初のものを選ぶ
+fn running_pod(pods: Vec<Pod>) -> Option<Pod> {
+    pods.into_iter()
+        .filter(|pod| {
+            pod.metadata.deletion_timestamp.is_none()
+                && pod
+                    .status
+                    .as_ref()
+                    .and_then(|status| status.phase.as_deref())
+                    == Some("Running")
+        })
+        .min_by(|a, b| a.metadata.name.cmp(&b.metadata.name))
+}
+
+/// Serviceのポートに対応するPodのポートを返す
+///
+/// targetPortが名前の場合はPodのコンテナのポート名から探す
+fn target_port(service_port: &ServicePort, pod: &Pod) -> Result<u16> {
+    let port = match &service_port.target_port {
+        None => service_port.port,
+        Some(IntOrString::Int(port)) => *port,
+        Some(IntOrString::String(name)) => pod
+            .spec
+            .iter()
+            .flat_map(|spec| &spec.containers)
+            .flat_map(|container| container.ports.iter().flatten())
+            .find(|port| port.name.as_ref() == Some(name))
+            .map(|port| port.container_port)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Pod {} does not have a named port {:?}",
+                    pod.metadata.name.as_deref().unwrap_or_default(),
+                    name
+                )
+            })?,
+    };
+
+    u16::try_from(port).with_context(|| format!("Invalid target port {}", port))
+}
+
+/// バイト数をKiB、MiB、GiB単位で表示する
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{:.1} {}", value, unit)
+}
+
+async fn forward(
+    client: Client,
+    target: PortForwardTarget,
+    ports: PortMapping,
+    stats: Arc<PortForwardStats>,
+) {
+    if let Err(err) = run_forward(client, target, ports, stats.clone()).await {
+        logger!(error, "port-forward: {:?}", err);
+
+        stats.set_status(PortForwardStatus::Failed(format!("{:#}", err)));
+    }
+}
+
+async fn run_forward(
+    client: Client,
+    target: PortForwardTarget,
+    ports: PortMapping,
+    stats: Arc<PortForwardStats>,
+) -> Result<()> {
+    let listener = TcpListener::bind((LISTEN_ADDRESS, ports.local))
+        .await
+        .with_context(|| format!("Failed to listen on {}:{}", LISTEN_ADDRESS, ports.local))?;
+
+    let upstream = Upstream::resolve(client, &target, ports.remote).await?;
+
+    stats.set_status(PortForwardStatus::Running);
+
+    // タスクが中断されると中継中の接続も破棄される
+    let mut tasks = JoinSet::new();
+
+    loop {
+        let (inbound, _) = listener.accept().await?;
+
+        tasks.spawn(relay(inbound, upstream.clone(), stats.clone()));
+
+        // 終了した接続のタスクを片付ける
+        while tasks.try_join_next().is_some() {}
+    }
+}
+
+/// 読み取ったバイト数を転送量に加算するストリーム
+///
+/// 長時間続く接続でも転送量を表示できるよう、読み取るたびに加算する
+struct CountingStream<S> {
+    inner: S,
+    stats: Arc<PortForwardStats>,
+    direction: Direction,
+}
+
+/// 転送の向き
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    /// ローカルからPodへ
+    Sent,
+    /// Podからローカルへ
+    Received,
+}
+
+impl<S> CountingStream<S> {
+    fn new(inner: S, stats: Arc<PortForwardStats>, direction: Direction) -> Self {
+        Self {
+            inner,
+            stats,
+            direction,
+        }
+    }
+
+    fn counter(&self) -> &AtomicU64 {
+        match self.direction {
+            Direction::Sent => &self.stats.sent,
+            Direction::Received => &self.stats.received,
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for CountingStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+
+        if let Poll::Ready(Ok(())) = poll {
+            let read = (buf.filled().len() - filled) as u64;
+
+            self.counter().fetch_add(read, Ordering::Relaxed);
+        }
+
+        poll
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for CountingStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// 受け付けた接続をPodのポートに中継する
+async fn relay(inbound: TcpStream, upstream: Upstream, stats: Arc<PortForwardStats>) {
+    stats.connections.fetch_add(1, Ordering::Relaxed);
+
+    let mut inbound = CountingStream::new(inbound, stats.clone(), Direction::Sent);
+
+    let result = async {
+        let mut forwarder = match upstream
+            .api
+            .portforward(&upstream.pod, &[upstream.port])
+            .await
+        {
+            Ok(forwarder) => {
+                stats.set_status(PortForwardStatus::Running);
+                forwarder
+            }
+            Err(err) => {
+                // Podが削除された場合などは接続できないため、状態に表示する
+                let err =
+                    anyhow!(err).context(format!("Failed to connect to pod/{}", upstream.pod));
+
+                stats.set_status(PortForwardStatus::Failed(format!("{:#}", err)));
+
+                return Err(err);
+            }
+        };
+
+        let outbound = forwarder
+            .take_stream(upstream.port)
+            .context("Failed to take the port-forward stream")?;
+
+        let mut outbound = CountingStream::new(outbound, stats.clone(), Direction::Received);
+
+        let error = forwarder.take_error(upstream.port);
+
+        // 転送量は読み取るたびに加算されるため、エラーで終わった接続の分も残る
+        let copied = io::copy_bidirectional(&mut inbound, &mut outbound).await;
+
+        drop(outbound);
+
+        if let Some(Some(err)) = match error {
+            Some(error) => Some(error.await),
+            None => None,
+        } {
+            logger!(warn, "port-forward: {}", err);
+        }
+
+        forwarder.join().await?;
+
+        copied?;
+
+        anyhow::Ok(())
+    }
+    .await;
+
+    if let Err(err) = result {
+        logger!(debug, "port-forward: connection closed: {:#}", err);
+    }
+
+    stats.connections.fetch_sub(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::{
+        api::core::v1::{Container, ContainerPort, PodSpec, PodStatus},
+        apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    };
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn pod(name: &str, phase: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "app".to_string(),
+                    ports: Some(vec![ContainerPort {
+                        name: Some("http".to_string()),
+                        container_port: 8080,
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                phase: Some(phase.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn 実行中のpodを名前順で選ぶ() {
+        let pods = vec![
+            pod("web-c", "Running"),
+            pod("web-a", "Pending"),
+            pod("web-b", "Running"),
+        ];
+
+        let actual = running_pod(pods).and_then(|pod| pod.metadata.name);
+
+        assert_eq!(actual, Some("web-b".to_string()));
+    }
+
+    #[test]
+    fn 実行中のpodがなければnoneを返す() {
+        let pods = vec![pod("web-a", "Pending"), pod("web-b", "Succeeded")];
+
+        assert!(running_pod(pods).is_none());
+    }
+
+    #[rstest]
+    #[case(None, Some(80))]
+    #[case(Some(IntOrString::Int(8000)), Some(8000))]
+    #[case(Some(IntOrString::String("http".to_string())), Some(8080))]
+    #[case(Some(IntOrString::String("metrics".to_string())), None)]
+    fn serviceのポートに対応するpodのポートを返す(
+        #[case] target: Option<IntOrString>,
+        #[case] expected: Option<u16>,
+    ) {
+        let service_port = ServicePort {
+            port: 80,
+            target_port: target,
+            ..Default::default()
+        };
+
+        let actual = target_port(&service_port, &pod("web", "Running")).ok();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(0, "0 B")]
+    #[case(1023, "1023 B")]
+    #[case(1024, "1.0 KiB")]
+    #[case(1536, "1.5 KiB")]
+    #[case(5 * 1024 * 1024, "5.0 MiB")]
+    #[case(3 * 1024 * 1024 * 1024, "3.0 GiB")]
+    fn format_bytes(#[case] bytes: u64, #[case] expected: &str) {
+        assert_eq!(super::format_bytes(bytes), expected);
+    }
+
+    #[tokio::test]
+    async fn 接続中も転送量を加算する() {
+        use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+        let stats = Arc::new(PortForwardStats::default());
+
+        let (mut client, local) = io::duplex(64);
+        let (remote, mut pod) = io::duplex(64);
+
+        let mut inbound = CountingStream::new(local, stats.clone(), Direction::Sent);
+        let mut outbound = CountingStream::new(remote, stats.clone(), Direction::Received);
+
+        let handle =
+            tokio::spawn(async move { io::copy_bidirectional(&mut inbound, &mut outbound).await });
+
+        let mut buf = [0; 5];
+
+        client.write_all(b"ping").await.unwrap();
+        pod.read_exact(&mut buf[..4]).await.unwrap();
+
+        pod.write_all(b"pong!").await.unwrap();
+        client.read_exact(&mut buf).await.unwrap();
+
+        assert!(!handle.is_finished());
+        assert_eq!(stats.sent.load(Ordering::Relaxed), 4);
+        assert_eq!(stats.received.load(Ordering::Relaxed), 5);
+
+        drop(client);
+        drop(pod);
+
+        handle.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn 別のコンテキストに切り替えたときだけ停止する() {
+        let forwards = PortForwards::new("kind");
+
+        forwards.forwards.write().await.push(PortForward {
+            id: 0,
+            target: PortForwardTarget {
+                kind: PortForwardKind::Pod,
+                namespace: "default".to_string(),
+                name: "app".to_string(),
+            },
+            ports: PortMapping {
+                local: 8080,
+                remote: 80,
+            },
+            stats: Arc::default(),
+            handle: tokio::spawn(std::future::pending::<()>()).abort_handle(),
+        });
+
+        forwards.switch_context("kind").await;
+
+        assert_eq!(forwards.forwards.read().await.len(), 1);
+
+        forwards.switch_context("prod").await;
+
+        assert!(forwards.forwards.read().await.is_empty());
+    }
+}
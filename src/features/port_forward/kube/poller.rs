@@ -0,0 +1,55 @@
+use std::time;
+
+use async_trait::async_trait;
+
+use crate::{
+    features::port_forward::message::PortForwardMessage,
+    workers::kube::{
+        WorkerResult, {PollerBase, Worker},
+    },
+};
+
+use super::SharedPortForwards;
+
+/// 実行中のポートフォワードの状態を定期的に送信する
+#[derive(Clone)]
+pub struct PortForwardPoller {
+    base: PollerBase,
+    port_forwards: SharedPortForwards,
+}
+
+impl PortForwardPoller {
+    pub fn new(base: PollerBase, port_forwards: SharedPortForwards) -> Self {
+        Self {
+            base,
+            port_forwards,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for PortForwardPoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let mut interval = tokio::time::interval(time::Duration::from_secs(1));
+
+        let Self {
+            base: PollerBase {
+                is_terminated, tx, ..
+            },
+            port_forwards,
+        } = self;
+
+        while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
+            interval.tick().await;
+
+            let table = port_forwards.to_table().await;
+
+            tx.send(PortForwardMessage::Response(table).into())
+                .expect("Failed to send PortForwardMessage::Response");
+        }
+
+        WorkerResult::Terminated
+    }
+}
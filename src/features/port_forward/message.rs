@@ -0,0 +1,136 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Context as _, Result};
+
+use crate::{kube::table::KubeTable, message::Message, workers::kube::message::Kube};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortForwardKind {
+    Pod,
+    Service,
+}
+
+impl PortForwardKind {
+    /// `pod/<name>` 形式のリソース名に使う名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pod => "pod",
+            Self::Service => "service",
+        }
+    }
+}
+
+/// ポートフォワードの転送先
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortForwardTarget {
+    pub kind: PortForwardKind,
+    pub namespace: String,
+    pub name: String,
+}
+
+impl PortForwardTarget {
+    /// `pod/<name>` 形式のリソース名
+    pub fn resource(&self) -> String {
+        format!("{}/{}", self.kind.as_str(), self.name)
+    }
+}
+
+impl fmt::Display for PortForwardTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.resource(), self.namespace)
+    }
+}
+
+/// ローカルのポートと転送先のポートの組み合わせ
+///
+/// `<local>:<remote>` 形式で指定する。ローカルのポートを省略した場合は転送先と同じポートを使う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    pub local: u16,
+    pub remote: u16,
+}
+
+impl FromStr for PortMapping {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parse = |port: &str| -> Result<u16> {
+            let port: u16 = port
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid port {:?}", port.trim()))?;
+
+            if port == 0 {
+                return Err(anyhow!("Port must be between 1 and 65535"));
+            }
+
+            Ok(port)
+        };
+
+        let (local, remote) = match s.split_once(':') {
+            Some((local, remote)) => (parse(local)?, parse(remote)?),
+            None => {
+                let port = parse(s)?;
+                (port, port)
+            }
+        };
+
+        Ok(Self { local, remote })
+    }
+}
+
+#[derive(Debug)]
+pub enum PortForwardRequest {
+    Start {
+        target: PortForwardTarget,
+        ports: PortMapping,
+    },
+    Stop(u64),
+}
+
+#[derive(Debug)]
+pub enum PortForwardMessage {
+    Request(PortForwardRequest),
+    Response(KubeTable),
+    /// ポートフォワードを開始できなかった
+    Rejected(PortForwardTarget, anyhow::Error),
+}
+
+impl From<PortForwardRequest> for Message {
+    fn from(req: PortForwardRequest) -> Self {
+        Message::Kube(Kube::PortForward(PortForwardMessage::Request(req)))
+    }
+}
+
+impl From<PortForwardMessage> for Message {
+    fn from(m: PortForwardMessage) -> Self {
+        Message::Kube(Kube::PortForward(m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("8080:80", 8080, 80)]
+    #[case("80", 80, 80)]
+    #[case(" 3000 : 8080 ", 3000, 8080)]
+    fn parse_port_mapping(#[case] input: &str, #[case] local: u16, #[case] remote: u16) {
+        let actual: PortMapping = input.parse().unwrap();
+
+        assert_eq!(actual, PortMapping { local, remote });
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("http")]
+    #[case("0")]
+    #[case("8080:")]
+    #[case("70000:80")]
+    fn parse_invalid_port_mapping(#[case] input: &str) {
+        assert!(input.parse::<PortMapping>().is_err());
+    }
+}
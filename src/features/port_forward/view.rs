@@ -0,0 +1,6 @@
+mod popup;
+mod tab;
+mod widget;
+
+pub use popup::*;
+pub use tab::*;
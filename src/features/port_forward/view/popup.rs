@@ -0,0 +1,151 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crossbeam::channel::Sender;
+use k8s_openapi::{
+    api::core::v1::{Pod, Service},
+    Resource as _,
+};
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::PORT_FORWARD_POPUP_ID,
+        port_forward::message::{
+            PortForwardKind, PortForwardRequest, PortForwardTarget, PortMapping,
+        },
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            config::WidgetConfig, input::InputFormBuilder, Item, SelectedItem, Widget,
+            WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+/// ポート入力待ちのポートフォワード先
+pub type SharedPortForwardState = Rc<RefCell<Option<PortForwardTarget>>>;
+
+pub struct PortForwardPopup {
+    pub popup: Widget<'static>,
+}
+
+impl PortForwardPopup {
+    pub fn new(tx: &Sender<Message>, state: SharedPortForwardState) -> Self {
+        Self {
+            popup: popup(tx.clone(), state),
+        }
+    }
+}
+
+fn popup(tx: Sender<Message>, state: SharedPortForwardState) -> Widget<'static> {
+    InputFormBuilder::default()
+        .id(PORT_FORWARD_POPUP_ID)
+        .widget_config(WidgetConfig::builder().title("Port Forward").build())
+        .actions(UserEvent::from(KeyCode::Enter), start(tx, state))
+        .build()
+        .into()
+}
+
+/// 選択中のPodまたはServiceのポートフォワードを開始するポップアップを開く
+pub fn request_port_forward(state: SharedPortForwardState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::TableRow {
+            metadata: Some(metadata),
+            ..
+        }) = w.active_tab().active_widget().widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let kind = match metadata.get("kind").map(String::as_str) {
+            Some(Pod::KIND) => PortForwardKind::Pod,
+            Some(Service::KIND) => PortForwardKind::Service,
+            _ => return EventResult::Ignore,
+        };
+
+        let (Some(namespace), Some(name)) = (metadata.get("namespace"), metadata.get("name"))
+        else {
+            return EventResult::Ignore;
+        };
+
+        let target = PortForwardTarget {
+            kind,
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        };
+
+        open_port_forward_popup(w, &state, target);
+
+        EventResult::Nop
+    }
+}
+
+fn open_port_forward_popup(
+    w: &mut Window,
+    state: &SharedPortForwardState,
+    target: PortForwardTarget,
+) {
+    let widget = w.find_widget_mut(PORT_FORWARD_POPUP_ID);
+
+    *widget.widget_config_mut().append_title_mut() =
+        Some(format!(" : {} ([local:]remote)", target).into());
+
+    widget.update_widget_item(Item::Single(String::default().into()));
+
+    *state.borrow_mut() = Some(target);
+
+    w.open_popup(PORT_FORWARD_POPUP_ID);
+}
+
+/// ポートフォワードを開始できなかったことをポップアップのタイトルに表示する
+pub fn show_port_forward_error(w: &mut Window, target: &PortForwardTarget, err: &anyhow::Error) {
+    let widget = w.find_widget_mut(PORT_FORWARD_POPUP_ID);
+
+    *widget.widget_config_mut().append_title_mut() =
+        Some(format!(" : {} ({})", target, err).into());
+
+    widget.update_widget_item(Item::Single(String::default().into()));
+
+    w.open_popup(PORT_FORWARD_POPUP_ID);
+}
+
+fn start(
+    tx: Sender<Message>,
+    state: SharedPortForwardState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::Literal { item, .. }) =
+            w.find_widget(PORT_FORWARD_POPUP_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let Some(target) = state.borrow().clone() else {
+            return EventResult::Ignore;
+        };
+
+        // 入力に誤りがある場合はポップアップを開いたままにする
+        let ports: PortMapping = match item.parse() {
+            Ok(ports) => ports,
+            Err(err) => {
+                *w.find_widget_mut(PORT_FORWARD_POPUP_ID)
+                    .widget_config_mut()
+                    .append_title_mut() = Some(format!(" : {} ({})", target, err).into());
+
+                return EventResult::Nop;
+            }
+        };
+
+        state.borrow_mut().take();
+
+        tx.send(PortForwardRequest::Start { target, ports }.into())
+            .expect("Failed to send PortForwardRequest::Start");
+
+        w.close_popup();
+
+        EventResult::Nop
+    }
+}
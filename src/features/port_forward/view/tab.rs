@@ -0,0 +1,37 @@
+use crossbeam::channel::Sender;
+use ratatui::layout::Constraint;
+
+use crate::{
+    features::component_id::PORT_FORWARD_TAB_ID,
+    message::Message,
+    ui::{
+        tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
+        Tab,
+    },
+};
+
+use super::widget::port_forward_widget;
+
+pub struct PortForwardTab {
+    pub tab: Tab<'static>,
+}
+
+impl PortForwardTab {
+    pub fn new(title: &'static str, tx: &Sender<Message>) -> Self {
+        let port_forward_widget = port_forward_widget(tx);
+
+        let layout = TabLayout::new(
+            |_| {
+                NestedWidgetLayout::default().nested_widget_layout([NestedLayoutElement(
+                    Constraint::Percentage(100),
+                    LayoutElement::WidgetIndex(0),
+                )])
+            },
+            Default::default(),
+        );
+
+        Self {
+            tab: Tab::new(PORT_FORWARD_TAB_ID, title, [port_forward_widget], layout),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::{component_id::PORT_FORWARD_WIDGET_ID, port_forward::message::PortForwardRequest},
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, SelectedItem, Table, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+pub fn port_forward_widget(tx: &Sender<Message>) -> Widget<'static> {
+    Table::builder()
+        .id(PORT_FORWARD_WIDGET_ID)
+        .widget_config(&WidgetConfig::builder().title("Port Forward").build())
+        .filtered_key("TARGET")
+        .block_injection(block_injection())
+        .action('d', stop(tx.clone()))
+        .build()
+        .into()
+}
+
+fn block_injection() -> impl Fn(&Table) -> WidgetConfig {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut widget_config = table.widget_config().clone();
+
+        *widget_config.append_title_mut() =
+            Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        widget_config
+    }
+}
+
+/// 選択中のポートフォワードを停止する
+fn stop(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::TableRow {
+            metadata: Some(metadata),
+            ..
+        }) = w.find_widget(PORT_FORWARD_WIDGET_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let Some(id) = metadata.get("id").and_then(|id| id.parse().ok()) else {
+            return EventResult::Ignore;
+        };
+
+        tx.send(PortForwardRequest::Stop(id).into())
+            .expect("Failed to send PortForwardRequest::Stop");
+
+        EventResult::Nop
+    }
+}
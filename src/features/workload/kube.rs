@@ -0,0 +1,5 @@
+mod action;
+mod workload;
+
+pub use action::*;
+pub use workload::*;
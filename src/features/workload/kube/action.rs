@@ -0,0 +1,398 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use k8s_openapi::{
+    api::{
+        apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet},
+        batch::v1::{CronJob, Job},
+    },
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    NamespaceResourceScope,
+};
+use kube::{
+    api::{Patch, PatchParams, PostParams},
+    Api, Resource as _, ResourceExt as _,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    features::{
+        dry_run::kube::DryRunDiff,
+        workload::message::{WorkloadKind, WorkloadRequest, WorkloadResponse, WorkloadTarget},
+    },
+    kube::KubeClient,
+    logger,
+};
+
+const FIELD_MANAGER: &str = "kubetui";
+
+/// `kubectl rollout restart` と同じアノテーション
+const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+
+/// `kubectl create job --from=cronjob/<name>` と同じアノテーション
+const INSTANTIATE_ANNOTATION: &str = "cronjob.kubernetes.io/instantiate";
+
+/// Job名の接頭辞に使うCronJob名の最大長
+///
+/// Job名はラベルの値（63文字まで）にも使われるため、`-manual-` とランダムな5文字の分を除く
+const JOB_NAME_PREFIX_MAX_LEN: usize = 50;
+
+/// ワークロードを操作し、結果を返す
+pub async fn execute_workload_request(
+    client: &KubeClient,
+    req: WorkloadRequest,
+) -> WorkloadResponse {
+    logger!(info, "Workload request {:?}", req);
+
+    match req {
+        WorkloadRequest::Restart(target) => {
+            WorkloadResponse::Action(restart(client, &target).await)
+        }
+        WorkloadRequest::Scale(target, replicas) => {
+            WorkloadResponse::Action(scale(client, &target, replicas).await)
+        }
+        WorkloadRequest::TogglePause(target) => {
+            WorkloadResponse::Action(toggle_pause(client, &target).await)
+        }
+        WorkloadRequest::Trigger(target) => WorkloadResponse::Triggered(
+            create_job(client, &target, false)
+                .await
+                .map(|job| job.name_any()),
+        ),
+    }
+}
+
+async fn restart(client: &KubeClient, target: &WorkloadTarget) -> Result<String> {
+    patch_workload(
+        client,
+        target,
+        Patch::Strategic(restart_patch(Local::now())),
+        false,
+    )
+    .await?;
+
+    Ok(format!("Restarted {}", target))
+}
+
+async fn scale(client: &KubeClient, target: &WorkloadTarget, replicas: i32) -> Result<String> {
+    patch_workload(client, target, Patch::Merge(scale_patch(replicas)), false).await?;
+
+    Ok(format!("Scaled {} to {} replicas", target, replicas))
+}
+
+async fn toggle_pause(client: &KubeClient, target: &WorkloadTarget) -> Result<String> {
+    let paused = is_paused(target.kind, &get_workload(client, target).await?);
+
+    patch_workload(
+        client,
+        target,
+        Patch::Merge(pause_patch(target.kind, !paused)),
+        false,
+    )
+    .await?;
+
+    match (paused, target.kind) {
+        (true, _) => Ok(format!("Resumed {}", target)),
+        (false, WorkloadKind::CronJob) => Ok(format!("Suspended {}", target)),
+        (false, _) => Ok(format!("Paused {}", target)),
+    }
+}
+
+/// ワークロードの操作をサーバーサイドのdry-runで実行し、現在のリソースとの差分を返す
+pub async fn dry_run_workload_request(
+    client: &KubeClient,
+    req: &WorkloadRequest,
+) -> Result<DryRunDiff> {
+    logger!(info, "Dry-run workload request {:?}", req);
+
+    let target = req.target();
+
+    let live = get_workload(client, target).await?;
+
+    let patch = match req {
+        WorkloadRequest::Restart(_) => Patch::Strategic(restart_patch(Local::now())),
+        WorkloadRequest::Scale(_, replicas) => Patch::Merge(scale_patch(*replicas)),
+        WorkloadRequest::TogglePause(_) => {
+            Patch::Merge(pause_patch(target.kind, !is_paused(target.kind, &live)))
+        }
+        WorkloadRequest::Trigger(_) => {
+            return DryRunDiff::from_created(&create_job(client, target, true).await?);
+        }
+    };
+
+    let dry_run = patch_workload(client, target, patch, true).await?;
+
+    DryRunDiff::from_resources(&live, &dry_run)
+}
+
+/// CronJobのテンプレートからJobを作成する
+///
+/// Job名はAPIサーバーが `<CronJob名>-manual-<ランダムな文字列>` の形式で生成する
+async fn create_job(client: &KubeClient, target: &WorkloadTarget, dry_run: bool) -> Result<Job> {
+    let cronjob: CronJob = Api::namespaced(client.to_client(), &target.namespace)
+        .get(&target.name)
+        .await?;
+
+    let api: Api<Job> = Api::namespaced(client.to_client(), &target.namespace);
+
+    let params = PostParams {
+        dry_run,
+        field_manager: Some(FIELD_MANAGER.to_string()),
+    };
+
+    let job = api.create(&params, &job_from_cronjob(&cronjob)).await?;
+
+    Ok(job)
+}
+
+fn job_from_cronjob(cronjob: &CronJob) -> Job {
+    let template = cronjob
+        .spec
+        .as_ref()
+        .map(|spec| spec.job_template.clone())
+        .unwrap_or_default();
+
+    let template_metadata = template.metadata.unwrap_or_default();
+
+    let mut annotations = template_metadata.annotations.unwrap_or_default();
+
+    annotations.insert(INSTANTIATE_ANNOTATION.to_string(), "manual".to_string());
+
+    let name = cronjob.name_any();
+
+    let prefix = match name.char_indices().nth(JOB_NAME_PREFIX_MAX_LEN) {
+        Some((i, _)) => &name[..i],
+        None => &name,
+    };
+
+    Job {
+        metadata: ObjectMeta {
+            generate_name: Some(format!("{}-manual-", prefix)),
+            namespace: cronjob.namespace(),
+            labels: template_metadata.labels,
+            annotations: Some(annotations),
+            owner_references: cronjob.controller_owner_ref(&()).map(|owner| vec![owner]),
+            ..Default::default()
+        },
+        spec: template.spec,
+        status: None,
+    }
+}
+
+/// Deploymentはロールアウトが、CronJobはスケジュールが一時停止しているかを返す
+fn is_paused(kind: WorkloadKind, workload: &Value) -> bool {
+    workload["spec"][pause_field(kind)]
+        .as_bool()
+        .unwrap_or_default()
+}
+
+fn pause_field(kind: WorkloadKind) -> &'static str {
+    match kind {
+        WorkloadKind::CronJob => "suspend",
+        _ => "paused",
+    }
+}
+
+async fn get_workload(client: &KubeClient, target: &WorkloadTarget) -> Result<Value> {
+    match target.kind {
+        WorkloadKind::Deployment => get_resource::<Deployment>(client, target).await,
+        WorkloadKind::StatefulSet => get_resource::<StatefulSet>(client, target).await,
+        WorkloadKind::DaemonSet => get_resource::<DaemonSet>(client, target).await,
+        WorkloadKind::ReplicaSet => get_resource::<ReplicaSet>(client, target).await,
+        WorkloadKind::CronJob => get_resource::<CronJob>(client, target).await,
+    }
+}
+
+async fn patch_workload(
+    client: &KubeClient,
+    target: &WorkloadTarget,
+    patch: Patch<Value>,
+    dry_run: bool,
+) -> Result<Value> {
+    let params = PatchParams {
+        dry_run,
+        field_manager: Some(FIELD_MANAGER.to_string()),
+        ..Default::default()
+    };
+
+    match target.kind {
+        WorkloadKind::Deployment => {
+            patch_resource::<Deployment>(client, target, &params, patch).await
+        }
+        WorkloadKind::StatefulSet => {
+            patch_resource::<StatefulSet>(client, target, &params, patch).await
+        }
+        WorkloadKind::DaemonSet => {
+            patch_resource::<DaemonSet>(client, target, &params, patch).await
+        }
+        WorkloadKind::ReplicaSet => {
+            patch_resource::<ReplicaSet>(client, target, &params, patch).await
+        }
+        WorkloadKind::CronJob => patch_resource::<CronJob>(client, target, &params, patch).await,
+    }
+}
+
+async fn get_resource<K>(client: &KubeClient, target: &WorkloadTarget) -> Result<Value>
+where
+    K: kube::Resource<Scope = NamespaceResourceScope>,
+    <K as kube::Resource>::DynamicType: Default,
+    K: DeserializeOwned + Serialize + Clone + std::fmt::Debug,
+{
+    let api: Api<K> = Api::namespaced(client.to_client(), &target.namespace);
+
+    let resource = api.get(&target.name).await?;
+
+    Ok(serde_json::to_value(resource)?)
+}
+
+async fn patch_resource<K>(
+    client: &KubeClient,
+    target: &WorkloadTarget,
+    params: &PatchParams,
+    patch: Patch<Value>,
+) -> Result<Value>
+where
+    K: kube::Resource<Scope = NamespaceResourceScope>,
+    <K as kube::Resource>::DynamicType: Default,
+    K: DeserializeOwned + Serialize + Clone + std::fmt::Debug,
+{
+    let api: Api<K> = Api::namespaced(client.to_client(), &target.namespace);
+
+    let resource = api.patch(&target.name, params, &patch).await?;
+
+    Ok(serde_json::to_value(resource)?)
+}
+
+fn restart_patch(now: DateTime<Local>) -> Value {
+    json!({
+        "spec": {
+            "template": {
+                "metadata": {
+                    "annotations": {
+                        RESTARTED_AT_ANNOTATION: now.to_rfc3339()
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn scale_patch(replicas: i32) -> Value {
+    json!({ "spec": { "replicas": replicas } })
+}
+
+fn pause_patch(kind: WorkloadKind, paused: bool) -> Value {
+    json!({ "spec": { pause_field(kind): paused } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone as _;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn restart_patch() {
+        let now = Local.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+        let actual = super::restart_patch(now);
+
+        assert_eq!(
+            actual["spec"]["template"]["metadata"]["annotations"][RESTARTED_AT_ANNOTATION],
+            json!(now.to_rfc3339())
+        );
+    }
+
+    #[test]
+    fn scale_patch() {
+        assert_eq!(super::scale_patch(3), json!({ "spec": { "replicas": 3 } }));
+    }
+
+    #[test]
+    fn pausedが未設定なら一時停止していない() {
+        assert!(!is_paused(
+            WorkloadKind::Deployment,
+            &json!({ "spec": { "replicas": 1 } })
+        ));
+        assert!(is_paused(
+            WorkloadKind::Deployment,
+            &json!({ "spec": { "paused": true } })
+        ));
+        assert!(is_paused(
+            WorkloadKind::CronJob,
+            &json!({ "spec": { "suspend": true } })
+        ));
+    }
+
+    #[test]
+    fn pause_patch() {
+        assert_eq!(
+            super::pause_patch(WorkloadKind::Deployment, true),
+            json!({ "spec": { "paused": true } })
+        );
+        assert_eq!(
+            super::pause_patch(WorkloadKind::CronJob, false),
+            json!({ "spec": { "suspend": false } })
+        );
+    }
+
+    #[test]
+    fn cronjobのテンプレートからjobを生成する() {
+        let cronjob: CronJob = serde_json::from_value(json!({
+            "metadata": { "name": "cron-job", "namespace": "default", "uid": "uid" },
+            "spec": {
+                "schedule": "*/1 * * * *",
+                "jobTemplate": {
+                    "metadata": { "labels": { "app": "cron-job" } },
+                    "spec": {
+                        "template": {
+                            "spec": {
+                                "restartPolicy": "Never",
+                                "containers": [{ "name": "cron-job", "image": "busybox" }],
+                            },
+                        },
+                    },
+                },
+            },
+        }))
+        .unwrap();
+
+        let actual = serde_json::to_value(job_from_cronjob(&cronjob)).unwrap();
+
+        assert_eq!(
+            actual["metadata"],
+            json!({
+                "generateName": "cron-job-manual-",
+                "namespace": "default",
+                "labels": { "app": "cron-job" },
+                "annotations": { INSTANTIATE_ANNOTATION: "manual" },
+                "ownerReferences": [{
+                    "apiVersion": "batch/v1",
+                    "kind": "CronJob",
+                    "name": "cron-job",
+                    "uid": "uid",
+                    "controller": true,
+                }],
+            })
+        );
+        assert_eq!(
+            actual["spec"]["template"]["spec"]["containers"][0]["image"],
+            "busybox"
+        );
+    }
+
+    #[test]
+    fn job名の接頭辞を切り詰める() {
+        let cronjob: CronJob = serde_json::from_value(json!({
+            "metadata": { "name": "a".repeat(52) },
+        }))
+        .unwrap();
+
+        let job = job_from_cronjob(&cronjob);
+
+        assert_eq!(
+            job.metadata.generate_name,
+            Some(format!("{}-manual-", "a".repeat(JOB_NAME_PREFIX_MAX_LEN)))
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::future::try_join_all;
+
+use crate::{
+    features::{
+        component_id::WORKLOAD_TAB_ID,
+        workload::message::{WorkloadKind, WorkloadResponse},
+    },
+    kube::{
+        apis::v1_table::TableRow,
+        informer::{ResourcePath, TableInformers},
+        table::{get_resource_per_namespace, insert_ns, KubeTable, KubeTableRow, ResourceRows},
+    },
+    workers::kube::{
+        WorkerResult, {PollerBase, Worker},
+    },
+};
+
+#[derive(Clone)]
+pub struct WorkloadPoller {
+    base: PollerBase,
+}
+
+impl WorkloadPoller {
+    pub fn new(base: PollerBase) -> Self {
+        Self { base }
+    }
+}
+
+#[async_trait]
+impl Worker for WorkloadPoller {
+    type Output = WorkerResult;
+
+    async fn run(&self) -> Self::Output {
+        let Self {
+            base:
+                PollerBase {
+                    is_terminated,
+                    tx,
+                    shared_target_namespaces,
+                    informers,
+                    polling,
+                    health,
+                    ..
+                },
+        } = self;
+
+        let mut changes = informers.changes(polling.ticker(WORKLOAD_TAB_ID));
+
+        while !is_terminated.load(std::sync::atomic::Ordering::Relaxed) {
+            changes.wait().await;
+            health.wait_backoff().await;
+
+            let target_namespaces = shared_target_namespaces.read().await;
+
+            let table = fetch_workloads(changes.informers(), &target_namespaces).await;
+
+            tx.send(WorkloadResponse::Table(table).into())
+                .expect("Failed to send WorkloadResponse::Table");
+        }
+
+        WorkerResult::Terminated
+    }
+}
+
+impl WorkloadKind {
+    fn resource_path(&self) -> ResourcePath {
+        let (group, resource) = match self {
+            Self::Deployment => ("apps", "deployments"),
+            Self::StatefulSet => ("apps", "statefulsets"),
+            Self::DaemonSet => ("apps", "daemonsets"),
+            Self::ReplicaSet => ("apps", "replicasets"),
+            Self::CronJob => ("batch", "cronjobs"),
+        };
+
+        ResourcePath::new(group, "v1", resource)
+    }
+
+    /// サーバーサイドのテーブルから取得する列
+    ///
+    /// DeploymentとStatefulSetはReady列が `<ready>/<desired>` 形式になっている
+    fn columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::Deployment | Self::StatefulSet => &["Name", "Ready", "Age"],
+            Self::DaemonSet | Self::ReplicaSet => &["Name", "Ready", "Age", "Desired"],
+            Self::CronJob => &["Name", "Active", "Age", "Suspend"],
+        }
+    }
+}
+
+async fn fetch_workloads_per_namespace(
+    informers: &TableInformers,
+    namespaces: &[String],
+    kind: WorkloadKind,
+) -> Result<ResourceRows> {
+    let insert_ns = insert_ns(namespaces);
+
+    get_resource_per_namespace(
+        informers,
+        &kind.resource_path(),
+        namespaces,
+        kind.columns(),
+        |ns: &str, row: &TableRow, indexes: &[usize]| {
+            let name = row.cells[indexes[0]].to_string();
+
+            let ready = match (kind, indexes.get(3)) {
+                (WorkloadKind::CronJob, Some(suspend)) => cronjob_status(
+                    &row.cells[indexes[1]].to_string(),
+                    &row.cells[*suspend].to_string(),
+                ),
+                (_, Some(desired)) => {
+                    format!("{}/{}", row.cells[indexes[1]], row.cells[*desired])
+                }
+                (_, None) => row.cells[indexes[1]].to_string(),
+            };
+
+            let mut metadata = BTreeMap::from([("kind".to_string(), kind.to_string())]);
+
+            if let Some((_, desired)) = ready.split_once('/') {
+                metadata.insert("replicas".to_string(), desired.to_string());
+            }
+
+            let mut row = vec![
+                kind.to_string(),
+                name.to_string(),
+                ready,
+                row.cells[indexes[2]].to_string(),
+            ];
+
+            if insert_ns {
+                row.insert(0, ns.to_string())
+            }
+
+            KubeTableRow {
+                namespace: ns.to_string(),
+                name,
+                row,
+                metadata: Some(metadata),
+            }
+        },
+    )
+    .await
+}
+
+/// CronJobのREADY列には実行中のJob数、または一時停止中であることを表示する
+fn cronjob_status(active: &str, suspend: &str) -> String {
+    if suspend == "True" {
+        "Suspended".to_string()
+    } else {
+        format!("{} active", active)
+    }
+}
+
+async fn fetch_workloads(informers: &TableInformers, namespaces: &[String]) -> Result<KubeTable> {
+    let mut table = KubeTable {
+        header: if namespaces.len() == 1 {
+            ["KIND", "NAME", "READY", "AGE"]
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        } else {
+            ["NAMESPACE", "KIND", "NAME", "READY", "AGE"]
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        },
+        ..Default::default()
+    };
+
+    let jobs = try_join_all(
+        WorkloadKind::ALL.map(|kind| fetch_workloads_per_namespace(informers, namespaces, kind)),
+    )
+    .await?;
+
+    table.update_resource_rows(jobs.into_iter().collect());
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cronjobは実行中のjob数か一時停止中であることを表示する() {
+        assert_eq!(cronjob_status("2", "False"), "2 active");
+        assert_eq!(cronjob_status("0", "True"), "Suspended");
+    }
+
+    #[test]
+    fn cronjobはbatch_apiから取得する() {
+        assert_eq!(
+            WorkloadKind::CronJob.resource_path().namespaced("default"),
+            "apis/batch/v1/namespaces/default/cronjobs"
+        );
+        assert_eq!(
+            WorkloadKind::Deployment
+                .resource_path()
+                .namespaced("default"),
+            "apis/apps/v1/namespaces/default/deployments"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use k8s_openapi::{
+    api::{
+        apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet},
+        batch::v1::CronJob,
+    },
+    Resource as _,
+};
+
+use crate::{
+    features::dry_run::kube::DryRunDiff, kube::table::KubeTable, message::Message,
+    workers::kube::message::Kube,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkloadKind {
+    Deployment,
+    StatefulSet,
+    DaemonSet,
+    ReplicaSet,
+    CronJob,
+}
+
+impl WorkloadKind {
+    pub const ALL: [Self; 5] = [
+        Self::Deployment,
+        Self::StatefulSet,
+        Self::DaemonSet,
+        Self::ReplicaSet,
+        Self::CronJob,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deployment => Deployment::KIND,
+            Self::StatefulSet => StatefulSet::KIND,
+            Self::DaemonSet => DaemonSet::KIND,
+            Self::ReplicaSet => ReplicaSet::KIND,
+            Self::CronJob => CronJob::KIND,
+        }
+    }
+
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == kind)
+    }
+
+    /// Podテンプレートを持ち、ロールアウトを再実行できるかどうか
+    pub fn can_restart(&self) -> bool {
+        !matches!(self, Self::ReplicaSet | Self::CronJob)
+    }
+
+    /// レプリカ数を変更できるかどうか
+    pub fn can_scale(&self) -> bool {
+        !matches!(self, Self::DaemonSet | Self::CronJob)
+    }
+
+    /// 一時停止できるかどうか
+    ///
+    /// Deploymentはロールアウトを、CronJobはスケジュールを一時停止する
+    pub fn can_pause(&self) -> bool {
+        matches!(self, Self::Deployment | Self::CronJob)
+    }
+
+    /// Jobを手動で作成できるかどうか
+    pub fn can_trigger(&self) -> bool {
+        matches!(self, Self::CronJob)
+    }
+}
+
+impl std::fmt::Display for WorkloadKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkloadTarget {
+    pub kind: WorkloadKind,
+    pub namespace: String,
+    pub name: String,
+}
+
+impl std::fmt::Display for WorkloadTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}/{}", self.kind, self.namespace, self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkloadRequest {
+    /// Podテンプレートのアノテーションを更新してPodを再作成する
+    Restart(WorkloadTarget),
+    Scale(WorkloadTarget, i32),
+    /// ロールアウト、またはCronJobのスケジュールの一時停止と再開を切り替える
+    TogglePause(WorkloadTarget),
+    /// CronJobのテンプレートからJobを作成する
+    Trigger(WorkloadTarget),
+}
+
+impl WorkloadRequest {
+    pub fn target(&self) -> &WorkloadTarget {
+        match self {
+            Self::Restart(target)
+            | Self::Scale(target, _)
+            | Self::TogglePause(target)
+            | Self::Trigger(target) => target,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WorkloadResponse {
+    Table(Result<KubeTable>),
+    /// 操作の結果（成功時は表示するメッセージ）
+    Action(Result<String>),
+    /// サーバーサイドのdry-runの結果
+    DryRun(WorkloadRequest, Result<DryRunDiff>),
+    /// CronJobから作成したJobの名前
+    Triggered(Result<String>),
+}
+
+#[derive(Debug)]
+pub enum WorkloadMessage {
+    Request(WorkloadRequest),
+    /// 操作をサーバーサイドのdry-runで実行する
+    DryRun(WorkloadRequest),
+    Response(WorkloadResponse),
+}
+
+impl From<WorkloadMessage> for Message {
+    fn from(m: WorkloadMessage) -> Self {
+        Self::Kube(Kube::Workload(m))
+    }
+}
+
+impl From<WorkloadRequest> for Message {
+    fn from(req: WorkloadRequest) -> Self {
+        WorkloadMessage::Request(req).into()
+    }
+}
+
+impl From<WorkloadResponse> for Message {
+    fn from(res: WorkloadResponse) -> Self {
+        WorkloadMessage::Response(res).into()
+    }
+}
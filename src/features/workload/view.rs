@@ -0,0 +1,7 @@
+mod tab;
+mod widgets;
+
+pub use tab::*;
+pub use widgets::{
+    show_action_dry_run_result, show_action_result, show_triggered_job, SharedWorkloadActionState,
+};
@@ -0,0 +1,50 @@
+use crossbeam::channel::Sender;
+use ratatui::layout::Constraint;
+
+use crate::{
+    features::component_id::WORKLOAD_TAB_ID,
+    message::Message,
+    ui::{
+        tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
+        widget::Widget,
+        Tab,
+    },
+};
+
+use super::widgets::{
+    action_popup_widget, scale_popup_widget, workload_widget, SharedWorkloadActionState,
+};
+
+pub struct WorkloadTab {
+    pub tab: Tab<'static>,
+    pub scale_popup: Widget<'static>,
+    pub action_popup: Widget<'static>,
+}
+
+impl WorkloadTab {
+    pub fn new(
+        title: &'static str,
+        tx: &Sender<Message>,
+        state: SharedWorkloadActionState,
+    ) -> Self {
+        let workload_widget = workload_widget(tx, state.clone());
+        let scale_popup = scale_popup_widget(tx, state.clone());
+        let action_popup = action_popup_widget(tx, state);
+
+        let layout = TabLayout::new(
+            |_| {
+                NestedWidgetLayout::default().nested_widget_layout([NestedLayoutElement(
+                    Constraint::Percentage(100),
+                    LayoutElement::WidgetIndex(0),
+                )])
+            },
+            Default::default(),
+        );
+
+        Self {
+            tab: Tab::new(WORKLOAD_TAB_ID, title, [workload_widget], layout),
+            scale_popup,
+            action_popup,
+        }
+    }
+}
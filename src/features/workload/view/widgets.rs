@@ -0,0 +1,10 @@
+mod action;
+mod scale;
+mod workload;
+
+pub(super) use action::action_popup_widget;
+pub use action::{
+    show_action_dry_run_result, show_action_result, show_triggered_job, SharedWorkloadActionState,
+};
+pub(super) use scale::*;
+pub(super) use workload::*;
@@ -0,0 +1,224 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::WORKLOAD_ACTION_POPUP_ID,
+        dry_run::{kube::DryRunDiff, view::diff_lines},
+        pod::view::run_log_query,
+        workload::message::{WorkloadKind, WorkloadMessage, WorkloadRequest, WorkloadTarget},
+    },
+    logger,
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, Item, LiteralItem, Text, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+/// 確認待ち、または入力待ちの操作
+#[derive(Debug, Default)]
+pub struct WorkloadActionState {
+    /// 確認待ちの操作
+    pub confirm: Option<WorkloadRequest>,
+    /// レプリカ数の入力対象
+    pub scale_target: Option<WorkloadTarget>,
+}
+
+pub type SharedWorkloadActionState = Rc<RefCell<WorkloadActionState>>;
+
+pub fn action_popup_widget(
+    tx: &Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> Widget<'static> {
+    Text::builder()
+        .id(WORKLOAD_ACTION_POPUP_ID)
+        .widget_config(&WidgetConfig::builder().title("Workload").build())
+        .wrap()
+        .action('y', confirm(tx.clone(), state.clone()))
+        .action('n', cancel(state.clone()))
+        .action(UserEvent::from(KeyCode::Enter), cancel(state))
+        .build()
+        .into()
+}
+
+/// 操作の確認ポップアップを開く
+///
+/// 操作をサーバーサイドのdry-runで実行し、結果の差分を [`show_action_dry_run_result`] で表示する
+pub fn open_confirm_popup(
+    w: &mut Window,
+    tx: &Sender<Message>,
+    state: &SharedWorkloadActionState,
+    req: WorkloadRequest,
+) {
+    update_popup(
+        w,
+        confirm_lines(
+            &req,
+            vec!["\x1b[90mRunning server-side dry-run ...\x1b[39m".to_string()],
+        ),
+    );
+
+    state.borrow_mut().confirm = Some(req.clone());
+
+    tx.send(WorkloadMessage::DryRun(req).into())
+        .expect("Failed to send WorkloadMessage::DryRun");
+
+    w.open_popup(WORKLOAD_ACTION_POPUP_ID);
+}
+
+/// dry-runの結果の差分を確認ポップアップに表示する
+///
+/// 確認待ちの操作が変わっていた場合は何もしない
+pub fn show_action_dry_run_result(
+    w: &mut Window,
+    state: &SharedWorkloadActionState,
+    req: WorkloadRequest,
+    result: Result<DryRunDiff>,
+) {
+    if state.borrow().confirm.as_ref() != Some(&req) {
+        return;
+    }
+
+    let body = match result {
+        Ok(diff) if diff.is_changed() => diff_lines(&diff.live, &diff.dry_run),
+        Ok(_) => vec!["The server reports no changes.".to_string()],
+        Err(err) => {
+            logger!(error, "Dry-run failed for {:?}: {:?}", req, err);
+
+            ["Dry-run failed:".to_string()]
+                .into_iter()
+                .chain(format!("{:?}", err).lines().map(String::from))
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    update_popup(w, confirm_lines(&req, body));
+}
+
+fn confirm_lines(req: &WorkloadRequest, body: Vec<String>) -> Vec<String> {
+    let (question, note) = match req {
+        WorkloadRequest::Restart(target) => (
+            format!("Restart {}?", target),
+            "Pods are recreated by updating the restartedAt annotation of the pod template.",
+        ),
+        WorkloadRequest::TogglePause(target) if target.kind == WorkloadKind::CronJob => (
+            format!("Suspend or resume {}?", target),
+            "A suspended CronJob does not create Jobs on its schedule.",
+        ),
+        WorkloadRequest::TogglePause(target) => (
+            format!("Pause or resume the rollout of {}?", target),
+            "A paused Deployment does not roll out changes to its pod template.",
+        ),
+        WorkloadRequest::Trigger(target) => (
+            format!("Create a Job from {}?", target),
+            "The logs of the new Job are shown in the Pod tab.",
+        ),
+        WorkloadRequest::Scale(target, replicas) => {
+            (format!("Scale {} to {} replicas?", target, replicas), "")
+        }
+    };
+
+    [question, note.to_string(), String::default()]
+        .into_iter()
+        .chain(body)
+        .chain([
+            String::default(),
+            "Press \x1b[1my\x1b[0m to continue, \x1b[1mn\x1b[0m or \x1b[1mEsc\x1b[0m to cancel."
+                .to_string(),
+        ])
+        .collect()
+}
+
+/// 操作を送信し、結果を待つ
+fn send_request(w: &mut Window, tx: &Sender<Message>, req: WorkloadRequest) {
+    update_popup(w, vec!["Sending request ...".to_string()]);
+
+    tx.send(req.into()).expect("Failed to send WorkloadRequest");
+
+    w.open_popup(WORKLOAD_ACTION_POPUP_ID);
+}
+
+/// 操作できないワークロードを選択した場合などのメッセージを表示する
+pub fn show_message(w: &mut Window, message: String) {
+    update_popup(w, vec![message, String::default(), close_hint()]);
+
+    w.open_popup(WORKLOAD_ACTION_POPUP_ID);
+}
+
+/// 操作の結果をポップアップに表示する
+pub fn show_action_result(w: &mut Window, result: Result<String>) {
+    let lines = match result {
+        Ok(message) => vec![message],
+        Err(err) => {
+            logger!(error, "Workload request failed: {:?}", err);
+
+            format!("{:?}", err)
+                .lines()
+                .map(|line| format!("\x1b[31m{}\x1b[39m", line))
+                .collect()
+        }
+    };
+
+    update_popup(
+        w,
+        lines
+            .into_iter()
+            .chain([String::default(), close_hint()])
+            .collect(),
+    );
+
+    w.open_popup(WORKLOAD_ACTION_POPUP_ID);
+}
+
+/// CronJobから作成したJobのログをPodタブに表示する
+pub fn show_triggered_job(w: &mut Window, result: Result<String>) {
+    match result {
+        Ok(job) => run_log_query(w, &format!("job/{}", job)),
+        Err(err) => show_action_result(w, Err(err)),
+    }
+}
+
+fn update_popup(w: &mut Window, lines: Vec<String>) {
+    let items = lines
+        .into_iter()
+        .map(|line| LiteralItem::new(line, None))
+        .collect();
+
+    w.find_widget_mut(WORKLOAD_ACTION_POPUP_ID)
+        .update_widget_item(Item::Array(items));
+}
+
+fn close_hint() -> String {
+    "Press \x1b[1mEnter\x1b[0m or \x1b[1mEsc\x1b[0m to close.".to_string()
+}
+
+fn confirm(
+    tx: Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(req) = state.borrow_mut().confirm.take() else {
+            return EventResult::Nop;
+        };
+
+        send_request(w, &tx, req);
+
+        EventResult::Nop
+    }
+}
+
+fn cancel(state: SharedWorkloadActionState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        state.borrow_mut().confirm.take();
+
+        w.close_popup();
+
+        EventResult::Nop
+    }
+}
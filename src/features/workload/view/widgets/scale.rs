@@ -0,0 +1,104 @@
+use crossbeam::channel::Sender;
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    features::{
+        component_id::WORKLOAD_SCALE_POPUP_ID,
+        workload::message::{WorkloadRequest, WorkloadTarget},
+    },
+    message::{Message, UserEvent},
+    ui::{
+        event::EventResult,
+        widget::{
+            config::WidgetConfig, input::InputFormBuilder, Item, SelectedItem, Widget,
+            WidgetTrait as _,
+        },
+        Window,
+    },
+};
+
+use super::action::{open_confirm_popup, show_message, SharedWorkloadActionState};
+
+pub fn scale_popup_widget(
+    tx: &Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> Widget<'static> {
+    InputFormBuilder::default()
+        .id(WORKLOAD_SCALE_POPUP_ID)
+        .widget_config(WidgetConfig::builder().title("Replicas").build())
+        .actions(UserEvent::from(KeyCode::Enter), scale(tx.clone(), state))
+        .build()
+        .into()
+}
+
+/// レプリカ数の入力ポップアップを開く
+///
+/// 入力欄には現在のレプリカ数を表示する
+pub fn open_scale_popup(
+    w: &mut Window,
+    state: &SharedWorkloadActionState,
+    target: WorkloadTarget,
+    replicas: Option<&str>,
+) {
+    let widget = w.find_widget_mut(WORKLOAD_SCALE_POPUP_ID);
+
+    *widget.widget_config_mut().append_title_mut() = Some(format!(" : {}", target).into());
+
+    widget.update_widget_item(Item::Single(replicas.unwrap_or_default().into()));
+
+    state.borrow_mut().scale_target = Some(target);
+
+    w.open_popup(WORKLOAD_SCALE_POPUP_ID);
+}
+
+fn scale(
+    tx: Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(SelectedItem::Literal { item, .. }) =
+            w.find_widget(WORKLOAD_SCALE_POPUP_ID).widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let Some(target) = state.borrow_mut().scale_target.take() else {
+            return EventResult::Ignore;
+        };
+
+        match parse_replicas(&item) {
+            Some(replicas) => {
+                open_confirm_popup(w, &tx, &state, WorkloadRequest::Scale(target, replicas))
+            }
+            None => show_message(
+                w,
+                format!(
+                    "\x1b[31mInvalid replicas: {:?}. Enter a non-negative integer.\x1b[39m",
+                    item
+                ),
+            ),
+        }
+
+        EventResult::Nop
+    }
+}
+
+fn parse_replicas(s: &str) -> Option<i32> {
+    s.trim().parse().ok().filter(|n| 0 <= *n)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("3", Some(3))]
+    #[case(" 0 ", Some(0))]
+    #[case("-1", None)]
+    #[case("three", None)]
+    #[case("", None)]
+    fn parse_replicas(#[case] input: &str, #[case] expected: Option<i32>) {
+        assert_eq!(super::parse_replicas(input), expected);
+    }
+}
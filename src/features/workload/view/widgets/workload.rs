@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::{
+        component_id::WORKLOAD_WIDGET_ID,
+        workload::message::{WorkloadKind, WorkloadRequest, WorkloadTarget},
+    },
+    message::Message,
+    ui::{
+        event::EventResult,
+        widget::{config::WidgetConfig, SelectedItem, Table, Widget, WidgetTrait as _},
+        Window,
+    },
+};
+
+use super::{
+    action::{open_confirm_popup, show_message, SharedWorkloadActionState},
+    scale::open_scale_popup,
+};
+
+pub fn workload_widget(tx: &Sender<Message>, state: SharedWorkloadActionState) -> Widget<'static> {
+    Table::builder()
+        .id(WORKLOAD_WIDGET_ID)
+        .widget_config(&WidgetConfig::builder().title("Workload").build())
+        .filtered_key("NAME")
+        .block_injection(block_injection())
+        .action('r', restart(tx.clone(), state.clone()))
+        .action('s', scale(state.clone()))
+        .action('p', toggle_pause(tx.clone(), state.clone()))
+        .action('t', trigger(tx.clone(), state))
+        .build()
+        .into()
+}
+
+fn block_injection() -> impl Fn(&Table) -> WidgetConfig {
+    |table: &Table| {
+        let index = if let Some(index) = table.state().selected() {
+            index + 1
+        } else {
+            0
+        };
+
+        let mut widget_config = table.widget_config().clone();
+
+        *widget_config.append_title_mut() =
+            Some(format!(" [{}/{}]", index, table.items().len()).into());
+
+        widget_config
+    }
+}
+
+fn restart(
+    tx: Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some((target, _)) = selected_workload(w) else {
+            return EventResult::Ignore;
+        };
+
+        if target.kind.can_restart() {
+            open_confirm_popup(w, &tx, &state, WorkloadRequest::Restart(target));
+        } else {
+            show_message(w, format!("{} cannot be restarted", target.kind));
+        }
+
+        EventResult::Nop
+    }
+}
+
+fn scale(state: SharedWorkloadActionState) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some((target, metadata)) = selected_workload(w) else {
+            return EventResult::Ignore;
+        };
+
+        if target.kind.can_scale() {
+            let replicas = metadata.get("replicas").map(String::as_str);
+
+            open_scale_popup(w, &state, target, replicas);
+        } else {
+            show_message(w, format!("{} cannot be scaled", target.kind));
+        }
+
+        EventResult::Nop
+    }
+}
+
+fn toggle_pause(
+    tx: Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some((target, _)) = selected_workload(w) else {
+            return EventResult::Ignore;
+        };
+
+        if target.kind.can_pause() {
+            open_confirm_popup(w, &tx, &state, WorkloadRequest::TogglePause(target));
+        } else {
+            show_message(
+                w,
+                format!(
+                    "Only Deployments and CronJobs can be paused, not {}",
+                    target.kind
+                ),
+            );
+        }
+
+        EventResult::Nop
+    }
+}
+
+fn trigger(
+    tx: Sender<Message>,
+    state: SharedWorkloadActionState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some((target, _)) = selected_workload(w) else {
+            return EventResult::Ignore;
+        };
+
+        if target.kind.can_trigger() {
+            open_confirm_popup(w, &tx, &state, WorkloadRequest::Trigger(target));
+        } else {
+            show_message(
+                w,
+                format!(
+                    "Jobs can only be created from CronJobs, not {}",
+                    target.kind
+                ),
+            );
+        }
+
+        EventResult::Nop
+    }
+}
+
+/// 選択中の行のワークロードとメタデータを取得する
+fn selected_workload(w: &Window) -> Option<(WorkloadTarget, BTreeMap<String, String>)> {
+    let Some(SelectedItem::TableRow {
+        metadata: Some(metadata),
+        ..
+    }) = w.find_widget(WORKLOAD_WIDGET_ID).widget_item()
+    else {
+        return None;
+    };
+
+    let target = WorkloadTarget {
+        kind: WorkloadKind::from_kind(metadata.get("kind")?)?,
+        namespace: metadata.get("namespace")?.to_string(),
+        name: metadata.get("name")?.to_string(),
+    };
+
+    Some((target, metadata))
+}
@@ -3,15 +3,16 @@ mod not_namespace;
 mod single_namespace;
 
 use anyhow::{anyhow, Result};
-use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ListMeta, ObjectMeta};
 use serde::Deserialize;
 
 use crate::{
     features::{
         api_resources::kube::{ApiResource, ApiResources},
-        yaml::message::YamlResourceList,
+        yaml::message::{YamlResourceList, YamlResourceListItem},
     },
-    kube::KubeClientRequest,
+    kube::{list_page_path, KubeClientRequest},
+    logger,
     workers::kube::TargetNamespaces,
 };
 
@@ -24,6 +25,8 @@ use self::{
 #[derive(Default, Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct List {
+    #[serde(default)]
+    metadata: ListMeta,
     items: Vec<Item>,
 }
 
@@ -63,7 +66,11 @@ impl<'a, C: KubeClientRequest> FetchResourceList<'a, C> {
     /// ネームスペースが２つ以上のとき
     ///   ネームスペースを頭につけたリソース一覧を返す
     ///
-    pub async fn fetch(&self) -> Result<YamlResourceList> {
+    /// 一覧はページごとに取得し、残りのページがある間は取得済みの一覧を `on_progress` に渡す
+    pub async fn fetch<F>(&self, on_progress: F) -> Result<YamlResourceList>
+    where
+        F: Fn(YamlResourceList),
+    {
         let kind = &self.req;
 
         let api = self
@@ -72,6 +79,9 @@ impl<'a, C: KubeClientRequest> FetchResourceList<'a, C> {
             .find(|api| *api == kind)
             .ok_or_else(|| anyhow!("Can't get {} from API resource", kind))?;
 
+        let on_page =
+            |items: &[YamlResourceListItem]| on_progress(YamlResourceList::partial(items.to_vec()));
+
         let kind = &api.name();
         let list = if api.is_namespaced() {
             if self.target_namespaces.len() == 1 {
@@ -81,7 +91,7 @@ impl<'a, C: KubeClientRequest> FetchResourceList<'a, C> {
                     api,
                     kind,
                 )
-                .fetch()
+                .fetch(on_page)
                 .await?
             } else {
                 FetchResourceListMultipleNamespaces::new(
@@ -90,15 +100,61 @@ impl<'a, C: KubeClientRequest> FetchResourceList<'a, C> {
                     api,
                     kind,
                 )
-                .fetch()
+                .fetch(|items| on_progress(YamlResourceList::partial(items)))
                 .await?
             }
         } else {
             FetchResourceListNotNamespaced::new(self.client, api, kind)
-                .fetch()
+                .fetch(on_page)
                 .await?
         };
 
         Ok(YamlResourceList::new(list))
     }
 }
+
+/// limitとcontinueでページごとにリソースの一覧を取得する
+///
+/// 残りのページがある間は、取得済みの一覧を `on_page` に渡す
+async fn fetch_pages<C, F>(
+    client: &C,
+    path: &str,
+    namespace: &str,
+    api: &ApiResource,
+    on_page: F,
+) -> Result<Vec<YamlResourceListItem>>
+where
+    C: KubeClientRequest,
+    F: Fn(&[YamlResourceListItem]),
+{
+    let mut items = Vec::new();
+
+    let mut continue_token: Option<String> = None;
+
+    loop {
+        let page_path = list_page_path(path, continue_token.as_deref())?;
+
+        logger!(info, "Fetching resource [{}]", page_path);
+
+        let res: List = client.request(&page_path).await?;
+
+        logger!(info, "Fetched resource - {:?}", res);
+
+        items.extend(res.items.into_iter().filter_map(|item| {
+            item.metadata.name.map(|name| YamlResourceListItem {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+                kind: api.clone(),
+                value: name,
+            })
+        }));
+
+        continue_token = res.metadata.continue_.filter(|token| !token.is_empty());
+
+        if continue_token.is_none() {
+            return Ok(items);
+        }
+
+        on_page(&items);
+    }
+}
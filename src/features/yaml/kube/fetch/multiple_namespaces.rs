@@ -1,3 +1,5 @@
+use std::sync::Mutex;
+
 use anyhow::Result;
 use futures::future::try_join_all;
 use unicode_segmentation::UnicodeSegmentation;
@@ -31,14 +33,11 @@ impl<'a, C: KubeClientRequest> FetchResourceListMultipleNamespaces<'a, C> {
         }
     }
 
-    pub(super) async fn fetch(&self) -> Result<Vec<YamlResourceListItem>> {
-        let jobs = try_join_all(self.namespaces.iter().map(|ns| async move {
-            FetchResourceListSingleNamespace::new(self.client, ns, self.api, self.kind)
-                .fetch()
-                .await
-        }))
-        .await?;
-
+    /// 各ネームスペースのページを受け取るたびに、取得済みの一覧を `on_progress` に渡す
+    pub(super) async fn fetch<F>(&self, on_progress: F) -> Result<Vec<YamlResourceListItem>>
+    where
+        F: Fn(Vec<YamlResourceListItem>),
+    {
         let namespace_digit = self
             .namespaces
             .iter()
@@ -46,24 +45,50 @@ impl<'a, C: KubeClientRequest> FetchResourceListMultipleNamespaces<'a, C> {
             .max()
             .unwrap_or(0);
 
-        let list = jobs
-            .into_iter()
-            .flat_map(|items| {
-                items
-                    .into_iter()
-                    .map(|mut item| {
-                        item.value = format!(
-                            "{:digit$}  {}",
-                            item.namespace,
-                            item.name,
-                            digit = namespace_digit
-                        );
-                        item
-                    })
-                    .collect::<Vec<YamlResourceListItem>>()
-            })
-            .collect();
+        let fetched: Mutex<Vec<Vec<YamlResourceListItem>>> =
+            Mutex::new(vec![Vec::new(); self.namespaces.len()]);
+
+        // i番目のネームスペースの一覧を置き換え、すべてのネームスペースの一覧を返す
+        let update = |i: usize, items: &[YamlResourceListItem]| {
+            let mut fetched = fetched.lock().expect("Failed to lock fetched resources");
+
+            fetched[i] = items
+                .iter()
+                .cloned()
+                .map(|mut item| {
+                    item.value = format!(
+                        "{:digit$}  {}",
+                        item.namespace,
+                        item.name,
+                        digit = namespace_digit
+                    );
+                    item
+                })
+                .collect();
+
+            fetched.concat()
+        };
+
+        try_join_all(self.namespaces.iter().enumerate().map(|(i, ns)| {
+            let update = &update;
+            let on_progress = &on_progress;
+
+            async move {
+                let items =
+                    FetchResourceListSingleNamespace::new(self.client, ns, self.api, self.kind)
+                        .fetch(|items| on_progress(update(i, items)))
+                        .await?;
+
+                on_progress(update(i, &items));
+
+                Ok::<_, anyhow::Error>(())
+            }
+        }))
+        .await?;
 
-        Ok(list)
+        Ok(fetched
+            .into_inner()
+            .expect("Failed to lock fetched resources")
+            .concat())
     }
 }
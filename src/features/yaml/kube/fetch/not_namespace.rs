@@ -3,10 +3,9 @@ use anyhow::Result;
 use crate::{
     features::{api_resources::kube::ApiResource, yaml::message::YamlResourceListItem},
     kube::KubeClientRequest,
-    logger,
 };
 
-use super::List;
+use super::fetch_pages;
 
 pub(super) struct FetchResourceListNotNamespaced<'a, C: KubeClientRequest> {
     client: &'a C,
@@ -19,25 +18,12 @@ impl<'a, C: KubeClientRequest> FetchResourceListNotNamespaced<'a, C> {
         Self { client, api, kind }
     }
 
-    pub(super) async fn fetch(&self) -> Result<Vec<YamlResourceListItem>> {
+    pub(super) async fn fetch<F>(&self, on_page: F) -> Result<Vec<YamlResourceListItem>>
+    where
+        F: Fn(&[YamlResourceListItem]),
+    {
         let path = format!("{}/{}", self.api.group_version_url(), self.kind);
-        logger!(info, "Fetching resource [{}]", path);
 
-        let res: List = self.client.request(&path).await?;
-
-        logger!(info, "Fetched resource - {:?}", res);
-
-        Ok(res
-            .items
-            .into_iter()
-            .filter_map(|item| {
-                item.metadata.name.map(|name| YamlResourceListItem {
-                    namespace: "".to_string(),
-                    name: name.to_string(),
-                    kind: self.api.clone(),
-                    value: name,
-                })
-            })
-            .collect())
+        fetch_pages(self.client, &path, "", self.api, on_page).await
     }
 }
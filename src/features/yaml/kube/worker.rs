@@ -7,9 +7,10 @@ use serde_yaml::Value;
 use crate::{
     features::{
         api_resources::kube::{ApiResource, ApiResources, SharedApiResources},
+        component_id::YAML_TAB_ID,
         yaml::message::YamlResponse,
     },
-    kube::KubeClientRequest,
+    kube::{polling::Polling, KubeClientRequest},
     logger,
     message::Message,
     workers::kube::AbortWorker,
@@ -32,6 +33,7 @@ where
     client: C,
     req: YamlTarget,
     shared_api_resources: SharedApiResources,
+    polling: Polling,
 }
 
 impl<C: KubeClientRequest> YamlWorker<C> {
@@ -41,6 +43,7 @@ impl<C: KubeClientRequest> YamlWorker<C> {
         client: C,
         shared_api_resources: SharedApiResources,
         req: YamlTarget,
+        polling: Polling,
     ) -> Self {
         Self {
             is_terminated,
@@ -48,6 +51,7 @@ impl<C: KubeClientRequest> YamlWorker<C> {
             client,
             req,
             shared_api_resources,
+            polling,
         }
     }
 }
@@ -55,7 +59,7 @@ impl<C: KubeClientRequest> YamlWorker<C> {
 #[async_trait::async_trait]
 impl<C: KubeClientRequest> AbortWorker for YamlWorker<C> {
     async fn run(&self) {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3));
+        let mut ticker = self.polling.description_ticker(YAML_TAB_ID);
 
         let YamlTarget {
             kind,
@@ -67,7 +71,7 @@ impl<C: KubeClientRequest> AbortWorker for YamlWorker<C> {
             .is_terminated
             .load(std::sync::atomic::Ordering::Relaxed)
         {
-            interval.tick().await;
+            ticker.tick().await;
 
             let api_resources = self.shared_api_resources.read().await;
 
@@ -127,17 +131,21 @@ async fn fetch_resource_yaml<C: KubeClientRequest>(
 
     logger!(info, "Fetched resource - {}", res);
 
-    // yaml dataに変換
-    let mut yaml_data: serde_yaml::Value = serde_json::from_str(&res)?;
-
-    if let Some(Value::Mapping(md)) = yaml_data.get_mut("metadata") {
-        md.remove("managedFields");
-    }
-
-    let yaml_string = serde_yaml::to_string(&yaml_data)?
+    let yaml_string = resource_json_to_yaml(&res)?
         .lines()
         .map(ToString::to_string)
         .collect();
 
     Ok(yaml_string)
 }
+
+/// APIサーバーから取得したJSONをmanagedFieldsを除いたyamlに変換する
+pub fn resource_json_to_yaml(json: &str) -> Result<String> {
+    let mut yaml_data: serde_yaml::Value = serde_json::from_str(json)?;
+
+    if let Some(Value::Mapping(md)) = yaml_data.get_mut("metadata") {
+        md.remove("managedFields");
+    }
+
+    Ok(serde_yaml::to_string(&yaml_data)?)
+}
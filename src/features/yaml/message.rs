@@ -17,11 +17,24 @@ pub struct YamlResourceListItem {
 #[derive(Debug, Clone)]
 pub struct YamlResourceList {
     pub items: Vec<YamlResourceListItem>,
+    /// 残りのページを取得中か
+    pub loading: bool,
 }
 
 impl YamlResourceList {
     pub fn new(items: Vec<YamlResourceListItem>) -> Self {
-        YamlResourceList { items }
+        YamlResourceList {
+            items,
+            loading: false,
+        }
+    }
+
+    /// 残りのページを取得中の一覧
+    pub fn partial(items: Vec<YamlResourceListItem>) -> Self {
+        YamlResourceList {
+            items,
+            loading: true,
+        }
     }
 }
 
@@ -1,12 +1,15 @@
 use crossbeam::channel::Sender;
 
 use crate::{
-    features::{component_id::YAML_KIND_POPUP_ID, yaml::message::YamlRequest},
+    features::{
+        component_id::{YAML_KIND_POPUP_ID, YAML_NAME_POPUP_ID},
+        yaml::message::YamlRequest,
+    },
     logger,
     message::Message,
     ui::{
         event::EventResult,
-        widget::{config::WidgetConfig, LiteralItem, SingleSelect, Widget},
+        widget::{config::WidgetConfig, Item, LiteralItem, SingleSelect, Widget, WidgetTrait as _},
         Window,
     },
 };
@@ -43,6 +46,14 @@ fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &LiteralItem) -> Event
         tx.send(YamlRequest::Resource(kind).into())
             .expect("Failed to send YamlRequest::Resource");
 
+        // 一覧はページごとに届くため、取得中の表示にしてから名前の選択画面を開く
+        let widget = w.find_widget_mut(YAML_NAME_POPUP_ID);
+
+        widget.update_widget_item(Item::Array(Vec::new()));
+        widget.widget_config_mut().set_loading(true);
+
+        w.open_popup(YAML_NAME_POPUP_ID);
+
         EventResult::Nop
     }
 }
@@ -1,3 +1,5 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crossbeam::channel::Sender;
 use ratatui::crossterm::event::KeyCode;
 
@@ -15,19 +17,25 @@ use crate::{
     },
 };
 
-pub fn name_popup(tx: &Sender<Message>) -> Widget<'static> {
+pub fn name_popup(
+    tx: &Sender<Message>,
+    current_target: Rc<RefCell<Option<YamlTarget>>>,
+) -> Widget<'static> {
     let tx = tx.clone();
 
     SingleSelect::builder()
         .id(YAML_NAME_POPUP_ID)
         .widget_config(&WidgetConfig::builder().title("Name").build())
-        .on_select(on_select(tx))
+        .on_select(on_select(tx, current_target))
         .action(KeyCode::Esc, open_kind_popup())
         .build()
         .into()
 }
 
-fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
+fn on_select(
+    tx: Sender<Message>,
+    current_target: Rc<RefCell<Option<YamlTarget>>>,
+) -> impl Fn(&mut Window, &LiteralItem) -> EventResult {
     move |w, v| {
         logger!(info, "Select Item: {:?}", v);
 
@@ -53,15 +61,16 @@ fn on_select(tx: Sender<Message>) -> impl Fn(&mut Window, &LiteralItem) -> Event
             unreachable!()
         };
 
-        tx.send(
-            YamlRequest::Yaml(YamlTarget {
-                kind,
-                name: name.to_string(),
-                namespace: namespace.to_string(),
-            })
-            .into(),
-        )
-        .expect("Failed to send YamlRequest::Yaml");
+        let target = YamlTarget {
+            kind,
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+        };
+
+        *current_target.borrow_mut() = Some(target.clone());
+
+        tx.send(YamlRequest::Yaml(target).into())
+            .expect("Failed to send YamlRequest::Yaml");
 
         EventResult::Nop
     }
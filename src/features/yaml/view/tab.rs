@@ -5,7 +5,9 @@ use ratatui::prelude::Constraint;
 
 use crate::{
     clipboard::Clipboard,
-    features::component_id::YAML_TAB_ID,
+    features::{
+        component_id::YAML_TAB_ID, delete::view::SharedDeleteState, yaml::message::YamlTarget,
+    },
     message::Message,
     ui::{
         tab::{LayoutElement, NestedLayoutElement, NestedWidgetLayout, TabLayout},
@@ -31,8 +33,12 @@ impl YamlTab {
         title: &'static str,
         tx: &Sender<Message>,
         clipboard: &Option<Rc<RefCell<Clipboard>>>,
+        delete_state: &SharedDeleteState,
     ) -> Self {
-        let yaml_widget = yaml_widget(tx, clipboard);
+        // 表示中のリソース
+        let target: Rc<RefCell<Option<YamlTarget>>> = Default::default();
+
+        let yaml_widget = yaml_widget(tx, clipboard, target.clone(), delete_state.clone());
 
         let layout = TabLayout::new(
             |_| {
@@ -47,7 +53,7 @@ impl YamlTab {
         YamlTab {
             tab: Tab::new(YAML_TAB_ID, title, [yaml_widget], layout),
             kind_popup: kind_popup(tx),
-            name_popup: name_popup(tx),
+            name_popup: name_popup(tx, target),
             not_found_popup: not_found_popup(),
         }
     }
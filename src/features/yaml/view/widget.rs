@@ -7,7 +7,12 @@ use crate::{
     clipboard::Clipboard,
     features::{
         component_id::{YAML_KIND_POPUP_ID, YAML_WIDGET_ID},
-        yaml::message::YamlRequest,
+        delete::{
+            message::DeleteTarget,
+            view::{open_delete_popup, SharedDeleteState},
+        },
+        edit::{message::EditTarget, view::request_edit},
+        yaml::message::{YamlRequest, YamlTarget},
     },
     message::Message,
     ui::{
@@ -20,6 +25,8 @@ use crate::{
 pub fn yaml_widget(
     tx: &Sender<Message>,
     clipboard: &Option<Rc<RefCell<Clipboard>>>,
+    current_target: Rc<RefCell<Option<YamlTarget>>>,
+    delete_state: SharedDeleteState,
 ) -> Widget<'static> {
     let tx = tx.clone();
 
@@ -27,7 +34,12 @@ pub fn yaml_widget(
         .id(YAML_WIDGET_ID)
         .widget_config(&WidgetConfig::builder().title("Yaml").build())
         .block_injection(block_injection())
-        .action('f', open_kind_popup(tx))
+        .action('f', open_kind_popup(tx.clone()))
+        .action(
+            'd',
+            open_delete(tx.clone(), current_target.clone(), delete_state),
+        )
+        .action('e', open_edit(tx, current_target))
         .wrap();
 
     if let Some(cb) = clipboard {
@@ -48,6 +60,53 @@ fn open_kind_popup(tx: Sender<Message>) -> impl Fn(&mut Window) -> EventResult {
     }
 }
 
+/// 表示中のリソースを削除する確認ポップアップを開く
+fn open_delete(
+    tx: Sender<Message>,
+    current_target: Rc<RefCell<Option<YamlTarget>>>,
+    delete_state: SharedDeleteState,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(YamlTarget {
+            kind,
+            name,
+            namespace,
+        }) = current_target.borrow().clone()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let target = DeleteTarget::from_api_resource(&kind, &namespace, &name);
+
+        open_delete_popup(w, &tx, &delete_state, target);
+
+        EventResult::Nop
+    }
+}
+
+/// 表示中のリソースをエディタで編集する
+fn open_edit(
+    tx: Sender<Message>,
+    current_target: Rc<RefCell<Option<YamlTarget>>>,
+) -> impl Fn(&mut Window) -> EventResult {
+    move |w: &mut Window| {
+        let Some(YamlTarget {
+            kind,
+            name,
+            namespace,
+        }) = current_target.borrow().clone()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let target = EditTarget::from_api_resource(&kind, &namespace, &name);
+
+        request_edit(w, &tx, target);
+
+        EventResult::Nop
+    }
+}
+
 fn block_injection() -> impl Fn(&Text, bool, bool) -> Block<'static> {
     |text: &Text, is_active: bool, is_mouse_over: bool| {
         let (index, size) = text.state();
@@ -1,6 +1,9 @@
 pub mod apis;
 mod client;
 pub mod context;
+pub mod guard;
+pub mod informer;
+pub mod polling;
 pub mod table;
 
 pub use client::*;
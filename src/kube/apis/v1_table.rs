@@ -162,6 +162,20 @@ impl Table {
             .collect()
     }
 
+    /// 残りのページを取得するためのcontinueトークン
+    pub fn continue_token(&self) -> Option<&str> {
+        self.metadata
+            .as_ref()?
+            .continue_
+            .as_deref()
+            .filter(|token| !token.is_empty())
+    }
+
+    /// 残りのページを取得中の一覧か
+    pub fn is_partial(&self) -> bool {
+        self.continue_token().is_some()
+    }
+
     #[allow(dead_code)]
     pub fn sort_rows_by_time(&mut self, time_index: usize) {
         self.rows
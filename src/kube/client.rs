@@ -1,10 +1,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::Stream;
 use http::header::{HeaderValue, ACCEPT};
-use k8s_openapi::NamespaceResourceScope;
 use kube::{
-    api::{GetParams, Request},
-    Api, Client, Resource,
+    api::{DeleteParams, GetParams, ListParams, PostParams, Request, WatchEvent, WatchParams},
+    Client,
 };
 use serde::de::DeserializeOwned;
 
@@ -12,6 +12,14 @@ use crate::logger;
 
 use super::apis::v1_table::Table;
 
+/// watchのタイムアウト（秒）
+///
+/// タイムアウトで切れた場合は、最後に受け取ったresourceVersionからwatchし直す
+const WATCH_TIMEOUT_SECONDS: u32 = 290;
+
+/// listで1回に取得する件数（kubectlの--chunk-sizeと同じ）
+pub const LIST_PAGE_LIMIT: u32 = 500;
+
 const TABLE_REQUEST_HEADER: &str = "application/json;as=Table;v=v1;g=meta.k8s.io,application/json;as=Table;v=v1beta1;g=meta.k8s.io,application/json";
 
 fn remove_slash(path: &str) -> &str {
@@ -22,6 +30,22 @@ fn remove_slash(path: &str) -> &str {
     }
 }
 
+/// listの1ページ分を取得するパス
+///
+/// 一覧をlimitの件数ごとに分けて取得する。
+/// 2ページ目以降は前のページで受け取ったcontinueトークンを指定する
+pub fn list_page_path(path: &str, continue_token: Option<&str>) -> Result<String> {
+    let mut params = ListParams::default().limit(LIST_PAGE_LIMIT);
+
+    if let Some(token) = continue_token {
+        params = params.continue_token(token);
+    }
+
+    let request = Request::new(format!("/{}", remove_slash(path))).list(&params)?;
+
+    Ok(remove_slash(&request.uri().to_string()).to_string())
+}
+
 #[derive(Clone)]
 pub struct KubeClient {
     client: Client,
@@ -71,20 +95,48 @@ impl KubeClient {
 
         ret.map_err(Into::into)
     }
+
+    /// 指定したresourceVersion以降のリソースの変更をTable形式で受け取る
+    ///
+    /// Table形式のブックマークは行を持たないため、ブックマークは無効にする
+    pub async fn table_watch(
+        &self,
+        path: &str,
+        resource_version: &str,
+    ) -> Result<impl Stream<Item = kube::Result<WatchEvent<Table>>>> {
+        let request = Request::new(format!("{}/{}", self.server_url, remove_slash(path)));
+
+        let params = WatchParams::default()
+            .timeout(WATCH_TIMEOUT_SECONDS)
+            .disable_bookmarks();
+
+        let mut request = request.watch(&params, resource_version)?;
+
+        request
+            .headers_mut()
+            .insert(ACCEPT, HeaderValue::from_static(TABLE_REQUEST_HEADER));
+
+        logger!(debug, "HTTP request {:?}", request);
+
+        let stream = self.client.request_events::<Table>(request).await?;
+
+        Ok(stream)
+    }
 }
 
 #[async_trait]
 pub trait KubeClientRequest: Send + Sync {
     async fn table_request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
 
-    async fn table_namespaced<K>(&self, namespace: &str) -> Result<Table>
-    where
-        K: Resource<DynamicType = (), Scope = NamespaceResourceScope> + 'static;
-
     async fn request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
 
     async fn request_text(&self, path: &str) -> Result<String>;
 
+    /// 削除したリソース（dry-runの場合は削除されるリソース）のjsonを返す
+    async fn delete(&self, path: &str, params: &DeleteParams) -> Result<String>;
+
+    async fn replace(&self, path: &str, params: &PostParams, data: Vec<u8>) -> Result<String>;
+
     fn client(&self) -> &Client;
 }
 
@@ -94,16 +146,6 @@ impl KubeClientRequest for KubeClient {
         self.inner_request(path, TABLE_REQUEST_HEADER).await
     }
 
-    async fn table_namespaced<K>(&self, namespace: &str) -> Result<Table>
-    where
-        K: Resource<DynamicType = (), Scope = NamespaceResourceScope> + 'static,
-    {
-        let api: Api<K> = Api::namespaced(self.client.clone(), namespace);
-
-        self.inner_request(api.resource_url(), TABLE_REQUEST_HEADER)
-            .await
-    }
-
     async fn request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T> {
         self.inner_request(path, "application/json").await
     }
@@ -124,6 +166,30 @@ impl KubeClientRequest for KubeClient {
         ret.map_err(Into::into)
     }
 
+    async fn delete(&self, path: &str, params: &DeleteParams) -> Result<String> {
+        let request = Request::new(&self.server_url);
+
+        let request = request.delete(remove_slash(path), params)?;
+
+        logger!(debug, "HTTP request {:?}", request);
+
+        let res = self.client.request_text(request).await?;
+
+        Ok(res)
+    }
+
+    async fn replace(&self, path: &str, params: &PostParams, data: Vec<u8>) -> Result<String> {
+        let request = Request::new(&self.server_url);
+
+        let request = request.replace(remove_slash(path), params, data)?;
+
+        logger!(debug, "HTTP request {:?}", request);
+
+        let res = self.client.request_text(request).await?;
+
+        Ok(res)
+    }
+
     fn client(&self) -> &Client {
         &self.client
     }
@@ -131,9 +197,8 @@ impl KubeClientRequest for KubeClient {
 
 #[cfg(test)]
 pub mod mock {
-    use super::{DeserializeOwned, KubeClientRequest, Result, Table};
-    use k8s_openapi::NamespaceResourceScope;
-    use kube::Resource;
+    use super::{DeserializeOwned, KubeClientRequest, Result};
+    use kube::api::{DeleteParams, PostParams};
     use mockall::mock;
 
     mock! {
@@ -145,9 +210,10 @@ pub mod mock {
         #[async_trait::async_trait]
         impl KubeClientRequest for TestKubeClient {
             async fn table_request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
-            async fn table_namespaced<K: Resource<DynamicType=(), Scope = NamespaceResourceScope> + 'static>(&self, ns: &str) -> Result<Table>;
             async fn request<T: DeserializeOwned + 'static>(&self, path: &str) -> Result<T>;
             async fn request_text(&self, path: &str) -> Result<String>;
+            async fn delete(&self, path: &str, params: &DeleteParams) -> Result<String>;
+            async fn replace(&self, path: &str, params: &PostParams, data: Vec<u8>) -> Result<String>;
             fn client(&self) -> &kube::Client;
         }
     }
@@ -181,3 +247,27 @@ pub mod mock {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("api/v1/pods", None, "api/v1/pods?&limit=500")]
+    #[case("/api/v1/pods", None, "api/v1/pods?&limit=500")]
+    #[case(
+        "apis/apps/v1/namespaces/default/deployments",
+        Some("eyJ2IjoibWV0YS5rOHMuaW8vdjEiLCJydiI6MTAwfQ=="),
+        "apis/apps/v1/namespaces/default/deployments?&limit=500&continue=eyJ2IjoibWV0YS5rOHMuaW8vdjEiLCJydiI6MTAwfQ%3D%3D"
+    )]
+    fn listの1ページ分を取得するパスを生成する(
+        #[case] path: &str,
+        #[case] continue_token: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(list_page_path(path, continue_token).unwrap(), expected);
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{Context as _, Result};
+use regex::Regex;
+
+use crate::config::ContextsConfig;
+
+/// コンテキストごとに書き込み操作を許可するかを判定する
+#[derive(Debug, Default, Clone)]
+pub struct WriteGuard {
+    /// `--readonly` を指定した場合はすべてのコンテキストで書き込み操作を無効にする
+    readonly: bool,
+    deny: Vec<Regex>,
+    allow: Vec<Regex>,
+    production: Vec<Regex>,
+}
+
+impl WriteGuard {
+    pub fn new(readonly: bool, config: &ContextsConfig) -> Result<Self> {
+        Ok(Self {
+            readonly,
+            deny: compile(&config.deny)?,
+            allow: compile(&config.allow)?,
+            production: compile(&config.production)?,
+        })
+    }
+
+    /// 書き込み操作を無効にするかどうか
+    ///
+    /// `deny` に一致し、`allow` に一致しない場合に無効にする
+    pub fn is_readonly(&self, context: &str) -> bool {
+        self.readonly || (is_match(&self.deny, context) && !is_match(&self.allow, context))
+    }
+
+    /// 本番環境のコンテキストかどうか
+    pub fn is_production(&self, context: &str) -> bool {
+        is_match(&self.production, context)
+    }
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).with_context(|| format!("Invalid context pattern {:?}", pattern))
+        })
+        .collect()
+}
+
+fn is_match(patterns: &[Regex], context: &str) -> bool {
+    patterns.iter().any(|re| re.is_match(context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn guard(readonly: bool) -> WriteGuard {
+        WriteGuard::new(
+            readonly,
+            &ContextsConfig {
+                deny: vec!["^prod-".to_string()],
+                allow: vec!["^prod-sandbox$".to_string()],
+                production: vec!["^prod-".to_string()],
+            },
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case("kind-kind", false)]
+    #[case("prod-tokyo", true)]
+    #[case("prod-sandbox", false)]
+    fn denyに一致しallowに一致しないコンテキストは読み取り専用にする(
+        #[case] context: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(guard(false).is_readonly(context), expected);
+    }
+
+    #[test]
+    fn readonlyフラグを指定するとすべてのコンテキストを読み取り専用にする() {
+        assert!(guard(true).is_readonly("kind-kind"));
+        assert!(guard(true).is_readonly("prod-sandbox"));
+    }
+
+    #[test]
+    fn productionに一致するコンテキストを判定する() {
+        assert!(guard(false).is_production("prod-sandbox"));
+        assert!(!guard(false).is_production("kind-kind"));
+    }
+
+    #[test]
+    fn 不正な正規表現はエラーとする() {
+        let config = ContextsConfig {
+            deny: vec!["(".to_string()],
+            ..Default::default()
+        };
+
+        assert!(WriteGuard::new(false, &config).is_err());
+    }
+}
@@ -0,0 +1,1177 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    future,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use futures::{
+    future::{select_all, try_join_all},
+    StreamExt as _, TryStreamExt as _,
+};
+use k8s_openapi::{
+    api::authorization::v1::{
+        ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+    },
+    apimachinery::pkg::apis::meta::v1::ListMeta,
+};
+use kube::{
+    api::{PostParams, WatchEvent},
+    Api,
+};
+use serde_json::Value as JsonValue;
+use tokio::{sync::watch, task::AbortHandle};
+
+use crate::{logger, workers::kube::health::ConnectionHealth};
+
+use super::{
+    apis::v1_table::{Table, TableColumnDefinition, TableRow, Value},
+    list_page_path,
+    polling::PollTicker,
+    KubeClient, KubeClientRequest as _,
+};
+
+/// listに失敗した場合や、watchが切れた場合に再試行するまでの時間
+///
+/// APIサーバーに接続できない場合は、接続状態の待ち時間が過ぎるまで待つ
+const RETRY_INTERVAL: Duration = Duration::from_secs(3);
+
+/// listの失敗が続いた場合に再試行するまでの最大の時間
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 参照されなくなったwatchを止めるまでの時間
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 連続した更新をまとめて通知するための待ち時間
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// この数以上のnamespaceを選択した場合は、クラスター全体をlistしてnamespaceで絞り込む
+const CLUSTER_LIST_THRESHOLD: usize = 5;
+
+/// クラスター全体をlist・watchできるかの確認結果を保持する時間
+const ACCESS_REVIEW_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// 経過時間を表示する列
+///
+/// サーバーは取得した時点の値を返すため、キャッシュから取り出すときに受け取ってからの経過時間を加算する
+const ELAPSED_COLUMNS: [&str; 2] = ["Age", "Last Seen"];
+
+/// リソースのパスごとにlistとwatchで更新するTableのキャッシュ
+///
+/// 同じパスのリソースは複数のタブで共有し、APIサーバーへのリクエストを1つのwatchにまとめる。
+/// 一定時間参照されなかったキャッシュはwatchを止めて破棄する。
+#[derive(Clone)]
+pub struct TableInformers {
+    client: KubeClient,
+    informers: Arc<Mutex<HashMap<String, Informer>>>,
+    /// [`TableInformers::changes`] で作った場合に、読み取ったパスの更新通知を登録する
+    reads: Option<SharedReads>,
+    /// listやwatchの結果から接続状態を判定する
+    health: ConnectionHealth,
+    /// クラスター全体のパスごとに、list・watchできるかと確認した時刻
+    cluster_access: Arc<Mutex<HashMap<String, (bool, Instant)>>>,
+}
+
+/// 読み取ったパスごとのキャッシュの更新通知
+type SharedReads = Arc<Mutex<HashMap<String, watch::Receiver<()>>>>;
+
+struct Informer {
+    cache: SharedTableCache,
+    /// キャッシュの更新通知
+    changed: Arc<watch::Sender<()>>,
+    /// 最初のページを受け取ったか
+    synced: watch::Receiver<bool>,
+    last_used: Instant,
+    handle: AbortHandle,
+}
+
+impl Drop for Informer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl TableInformers {
+    pub fn new(client: KubeClient, health: ConnectionHealth) -> Self {
+        Self {
+            client,
+            informers: Default::default(),
+            reads: None,
+            health,
+            cluster_access: Default::default(),
+        }
+    }
+
+    /// キャッシュからTableを取得する
+    ///
+    /// 初めて参照するパスの場合はwatchを開始し、最初のページを受け取るまで待つ。
+    /// 残りのページを取得中の場合は、continueトークンを持つTableを返す
+    pub async fn table(&self, path: &str) -> Result<Table> {
+        let path = path.trim_start_matches('/');
+
+        let (cache, mut synced) = {
+            let mut informers = self.informers.lock().expect("Failed to lock informers");
+
+            informers.retain(|path, informer| {
+                let is_used = informer.last_used.elapsed() < IDLE_TIMEOUT;
+
+                if !is_used {
+                    logger!(info, "Stop watching {}", path);
+                }
+
+                is_used
+            });
+
+            let informer = informers
+                .entry(path.to_string())
+                .or_insert_with(|| self.spawn(path));
+
+            informer.last_used = Instant::now();
+
+            // キャッシュを読み取る前に登録し、読み取った後の更新を取りこぼさない
+            if let Some(reads) = &self.reads {
+                reads
+                    .lock()
+                    .expect("Failed to lock reads")
+                    .entry(path.to_string())
+                    .or_insert_with(|| informer.changed.subscribe());
+            }
+
+            (informer.cache.clone(), informer.synced.clone())
+        };
+
+        synced
+            .wait_for(|synced| *synced)
+            .await
+            .map_err(|_| anyhow!("Stopped watching {}", path))?;
+
+        let cache = cache.read().expect("Failed to read table cache");
+
+        cache.to_table(Instant::now())
+    }
+
+    /// 選択したnamespaceごとのTableを取得する
+    ///
+    /// 選択したnamespaceが多い場合や、すべてのnamespaceを選択した場合は、
+    /// クラスター全体をlistしてnamespaceで絞り込む。
+    /// クラスター全体のlistが許可されていない場合はnamespaceごとに取得する。
+    pub async fn tables(
+        &self,
+        resource: &ResourcePath,
+        namespaces: &[String],
+    ) -> Result<Vec<(String, Table)>> {
+        if self.prefers_cluster_list(namespaces).await && self.can_watch_cluster(resource).await {
+            match self.table(&resource.cluster()).await {
+                Ok(table) => return Ok(split_by_namespace(table, namespaces)),
+                Err(err) => {
+                    logger!(
+                        warn,
+                        "Fall back to per-namespace list of {}: {:?}",
+                        resource.cluster(),
+                        err
+                    );
+
+                    self.set_cluster_access(resource, false);
+                }
+            }
+        }
+
+        try_join_all(namespaces.iter().map(|ns| async move {
+            let table = self.table(&resource.namespaced(ns)).await?;
+
+            Ok((ns.to_string(), table))
+        }))
+        .await
+    }
+
+    /// 読み取ったパスのキャッシュが更新されるまで待つ [`TableChanges`] を返す
+    ///
+    /// [`TableChanges::informers`] から読み取ったパスだけを待つため、
+    /// 他のタブが参照するパスの更新では起こされない
+    pub fn changes(&self, ticker: PollTicker) -> TableChanges {
+        let reads = SharedReads::default();
+
+        TableChanges {
+            informers: Self {
+                reads: Some(reads.clone()),
+                ..self.clone()
+            },
+            reads,
+            ticker,
+        }
+    }
+
+    async fn prefers_cluster_list(&self, namespaces: &[String]) -> bool {
+        if namespaces.len() <= 1 {
+            return false;
+        }
+
+        if CLUSTER_LIST_THRESHOLD <= namespaces.len() {
+            return true;
+        }
+
+        let resource = ResourcePath::new("", "v1", "namespaces");
+
+        if !self.can_watch_cluster(&resource).await {
+            return false;
+        }
+
+        let Ok(table) = self.table(&resource.cluster()).await else {
+            return false;
+        };
+
+        covers_all_namespaces(namespaces, &table)
+    }
+
+    /// クラスター全体をlist・watchできるかをSelfSubjectAccessReviewで確認する
+    async fn can_watch_cluster(&self, resource: &ResourcePath) -> bool {
+        let path = resource.cluster();
+
+        if let Some((allowed, checked_at)) = self
+            .cluster_access
+            .lock()
+            .expect("Failed to lock cluster access")
+            .get(&path)
+        {
+            if checked_at.elapsed() < ACCESS_REVIEW_TTL {
+                return *allowed;
+            }
+        }
+
+        let allowed = match review_cluster_access(&self.client, resource).await {
+            Ok(allowed) => allowed,
+            Err(err) => {
+                logger!(warn, "Failed to review access to {}: {:?}", path, err);
+                false
+            }
+        };
+
+        self.set_cluster_access(resource, allowed);
+
+        allowed
+    }
+
+    fn set_cluster_access(&self, resource: &ResourcePath, allowed: bool) {
+        self.cluster_access
+            .lock()
+            .expect("Failed to lock cluster access")
+            .insert(resource.cluster(), (allowed, Instant::now()));
+    }
+
+    fn spawn(&self, path: &str) -> Informer {
+        logger!(info, "Start watching {}", path);
+
+        let cache = SharedTableCache::default();
+
+        let (synced_tx, synced) = watch::channel(false);
+
+        let changed = Arc::new(watch::channel(()).0);
+
+        let handle = tokio::spawn(run_informer(
+            self.client.clone(),
+            path.to_string(),
+            cache.clone(),
+            synced_tx,
+            changed.clone(),
+            self.health.clone(),
+        ))
+        .abort_handle();
+
+        Informer {
+            cache,
+            changed,
+            synced,
+            last_used: Instant::now(),
+            handle,
+        }
+    }
+}
+
+/// namespaceスコープのリソースのAPIパス
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourcePath {
+    group: String,
+    version: String,
+    /// 複数形のリソース名（例: `pods`）
+    resource: String,
+}
+
+impl ResourcePath {
+    pub fn new(
+        group: impl Into<String>,
+        version: impl Into<String>,
+        resource: impl Into<String>,
+    ) -> Self {
+        Self {
+            group: group.into(),
+            version: version.into(),
+            resource: resource.into(),
+        }
+    }
+
+    pub fn of<K: k8s_openapi::Resource>() -> Self {
+        Self::new(K::GROUP, K::VERSION, K::URL_PATH_SEGMENT)
+    }
+
+    pub fn namespaced(&self, namespace: &str) -> String {
+        format!(
+            "{}/namespaces/{}/{}",
+            self.group_version(),
+            namespace,
+            self.resource
+        )
+    }
+
+    pub fn cluster(&self) -> String {
+        format!("{}/{}", self.group_version(), self.resource)
+    }
+
+    fn group_version(&self) -> String {
+        if self.group.is_empty() {
+            format!("api/{}", self.version)
+        } else {
+            format!("apis/{}/{}", self.group, self.version)
+        }
+    }
+}
+
+/// キャッシュの更新を待つ
+pub struct TableChanges {
+    informers: TableInformers,
+    reads: SharedReads,
+    ticker: PollTicker,
+}
+
+impl TableChanges {
+    /// 読み取ったパスを記録する [`TableInformers`]
+    pub fn informers(&self) -> &TableInformers {
+        &self.informers
+    }
+
+    /// 前回から読み取ったパスのキャッシュが更新されるまで待つ
+    ///
+    /// 経過時間の列を更新するため、更新がなくても一定間隔で返る。
+    /// タブを表示していない間はキャッシュの更新を待たず、バックグラウンドの間隔で返る。
+    pub async fn wait(&mut self) {
+        let is_active = self.ticker.is_active();
+
+        // namespaceの切り替えなどで読み取らなくなったパスを待たないよう、読み取るたびに登録し直す
+        let mut reads: Vec<_> =
+            std::mem::take(&mut *self.reads.lock().expect("Failed to lock reads"))
+                .into_values()
+                .collect();
+
+        tokio::select! {
+            _ = self.ticker.tick() => {}
+            _ = wait_any_change(&mut reads), if is_active => {
+                tokio::time::sleep(DEBOUNCE).await;
+
+                self.ticker.reset();
+            }
+        }
+    }
+}
+
+/// いずれかのキャッシュが更新されるまで待つ
+///
+/// watchを止めたパスは更新されないため、待ち続ける
+async fn wait_any_change(receivers: &mut [watch::Receiver<()>]) {
+    if receivers.is_empty() {
+        return future::pending().await;
+    }
+
+    select_all(receivers.iter_mut().map(|rx| {
+        Box::pin(async move {
+            if rx.changed().await.is_err() {
+                future::pending::<()>().await;
+            }
+        })
+    }))
+    .await;
+}
+
+type SharedTableCache = Arc<RwLock<TableCache>>;
+
+#[derive(Debug, Default)]
+struct TableCache {
+    column_definitions: Vec<TableColumnDefinition>,
+    /// `namespace/name` をキーにした行（listと同じ順序になる）
+    rows: BTreeMap<String, CachedRow>,
+    /// 最初のlistで残りのページを取得中の場合のcontinueトークン
+    continue_token: Option<String>,
+    /// listに失敗した場合のエラー
+    error: Option<CachedError>,
+}
+
+/// listに失敗した場合のエラー
+///
+/// 接続エラーかどうかを判定できるように、元のエラーを保持する
+#[derive(Debug, Clone)]
+struct CachedError(Arc<anyhow::Error>);
+
+impl std::fmt::Display for CachedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl std::error::Error for CachedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        let err: &(dyn std::error::Error + Send + Sync) = (*self.0).as_ref();
+
+        Some(err)
+    }
+}
+
+#[derive(Debug)]
+struct CachedRow {
+    row: TableRow,
+    received: Instant,
+}
+
+impl TableCache {
+    /// listの結果で置き換える
+    fn replace(&mut self, table: Table, now: Instant) {
+        self.rows.clear();
+        self.extend(table, now);
+    }
+
+    /// listで受け取った次のページの行を追加する
+    fn extend(&mut self, table: Table, now: Instant) {
+        self.continue_token = table.continue_token().map(ToString::to_string);
+        self.column_definitions = table.column_definitions;
+        self.rows.extend(
+            table
+                .rows
+                .into_iter()
+                .map(|row| (row_key(&row), CachedRow { row, received: now })),
+        );
+        self.error = None;
+    }
+
+    /// watchで受け取った行を反映し、最後の行のresourceVersionを返す
+    fn apply(&mut self, event: WatchEvent<Table>, now: Instant) -> Result<Option<String>> {
+        let (table, is_deleted) = match event {
+            WatchEvent::Added(table) | WatchEvent::Modified(table) => (table, false),
+            WatchEvent::Deleted(table) => (table, true),
+            WatchEvent::Bookmark(bookmark) => return Ok(Some(bookmark.metadata.resource_version)),
+            WatchEvent::Error(err) => return Err(anyhow!("Watch error: {}", err)),
+        };
+
+        let mut resource_version = table.metadata.and_then(|m| m.resource_version);
+
+        for row in table.rows {
+            if let Some(version) = row_metadata(&row, "resourceVersion") {
+                resource_version = Some(version.to_string());
+            }
+
+            let key = row_key(&row);
+
+            if is_deleted {
+                self.rows.remove(&key);
+            } else {
+                self.rows.insert(key, CachedRow { row, received: now });
+            }
+        }
+
+        Ok(resource_version)
+    }
+
+    fn to_table(&self, now: Instant) -> Result<Table> {
+        if let Some(err) = &self.error {
+            return Err(err.clone().into());
+        }
+
+        let elapsed_indexes: Vec<usize> = self
+            .column_definitions
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| ELAPSED_COLUMNS.contains(&column.name.as_str()))
+            .map(|(i, _)| i)
+            .collect();
+
+        let rows = self
+            .rows
+            .values()
+            .map(|cached| {
+                let mut row = cached.row.clone();
+
+                let elapsed = now.saturating_duration_since(cached.received);
+
+                for i in &elapsed_indexes {
+                    let Some(cell) = row.cells.get_mut(*i) else {
+                        continue;
+                    };
+
+                    if let Some(duration) = cell.as_str().and_then(parse_duration) {
+                        *cell = Value(JsonValue::String(human_duration(duration + elapsed)));
+                    }
+                }
+
+                row
+            })
+            .collect();
+
+        Ok(Table {
+            metadata: self.continue_token.as_ref().map(|token| ListMeta {
+                continue_: Some(token.clone()),
+                ..Default::default()
+            }),
+            column_definitions: self.column_definitions.clone(),
+            rows,
+            ..Default::default()
+        })
+    }
+}
+
+/// listで一覧を取得し、以降はwatchで差分を反映する
+///
+/// listはlimitとcontinueでページごとに取得する。
+/// watchが切れた場合は最後に受け取ったresourceVersionから再開し、
+/// resourceVersionが古くなった場合（410 Gone）などはlistからやり直す。
+/// listとwatchの結果は接続状態に記録する
+async fn run_informer(
+    client: KubeClient,
+    path: String,
+    cache: SharedTableCache,
+    synced: watch::Sender<bool>,
+    changed: Arc<watch::Sender<()>>,
+    health: ConnectionHealth,
+) {
+    let notify = || {
+        synced.send_replace(true);
+        changed.send_replace(());
+    };
+
+    let mut retry_interval = RETRY_INTERVAL;
+
+    loop {
+        let result = list(&client, &path, &cache, &notify).await;
+
+        health.record(&result);
+
+        let resource_version = match result {
+            Ok(resource_version) => {
+                retry_interval = RETRY_INTERVAL;
+
+                resource_version
+            }
+            Err(err) => {
+                logger!(error, "Failed to list {}: {:?}", path, err);
+
+                cache.write().expect("Failed to write table cache").error =
+                    Some(CachedError(Arc::new(err)));
+
+                notify();
+
+                tokio::time::sleep(retry_interval).await;
+                health.wait_backoff().await;
+
+                retry_interval = (retry_interval * 2).min(MAX_RETRY_INTERVAL);
+
+                continue;
+            }
+        };
+
+        // resourceVersionを返さないAPIはwatchできないため、listを繰り返す
+        if let Some(mut resource_version) = resource_version {
+            let result = watch(&client, &path, &mut resource_version, &cache, &changed).await;
+
+            health.record(&result);
+
+            if let Err(err) = result {
+                logger!(warn, "Restart watching {}: {:?}", path, err);
+            }
+        }
+
+        tokio::time::sleep(RETRY_INTERVAL).await;
+        health.wait_backoff().await;
+    }
+}
+
+/// ページごとにlistし、最後のページのresourceVersionを返す
+///
+/// キャッシュが空の場合は受け取ったページから順に反映し、取得中でも行を表示できるようにする。
+/// キャッシュに行がある場合は、すべてのページを受け取ってから置き換える。
+/// 途中でcontinueトークンの期限が切れた場合（410 Gone）は、最初のページからやり直す
+async fn list(
+    client: &KubeClient,
+    path: &str,
+    cache: &SharedTableCache,
+    notify: impl Fn(),
+) -> Result<Option<String>> {
+    let progressive = cache
+        .read()
+        .expect("Failed to read table cache")
+        .rows
+        .is_empty();
+
+    let mut staged = TableCache::default();
+
+    let mut continue_token: Option<String> = None;
+
+    loop {
+        let page_path = list_page_path(path, continue_token.as_deref())?;
+
+        let table = match client.table_request::<Table>(&page_path).await {
+            Ok(table) => table,
+            Err(err) if continue_token.is_some() && is_expired(&err) => {
+                logger!(warn, "Restart listing {}: {:?}", path, err);
+
+                continue_token = None;
+
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        let is_first_page = continue_token.is_none();
+
+        continue_token = table.continue_token().map(ToString::to_string);
+
+        let resource_version = table
+            .metadata
+            .as_ref()
+            .and_then(|m| m.resource_version.clone());
+
+        if progressive {
+            let mut cache = cache.write().expect("Failed to write table cache");
+
+            if is_first_page {
+                cache.replace(table, Instant::now());
+            } else {
+                cache.extend(table, Instant::now());
+            }
+
+            drop(cache);
+
+            notify();
+        } else if is_first_page {
+            staged.replace(table, Instant::now());
+        } else {
+            staged.extend(table, Instant::now());
+        }
+
+        if continue_token.is_none() {
+            if !progressive {
+                *cache.write().expect("Failed to write table cache") = staged;
+
+                notify();
+            }
+
+            return Ok(resource_version);
+        }
+    }
+}
+
+/// continueトークンやresourceVersionの期限が切れたことによるエラーかどうか
+fn is_expired(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<kube::Error>(),
+        Some(kube::Error::Api(response)) if response.code == 410
+    )
+}
+
+/// watchで受け取った変更をキャッシュに反映する
+///
+/// タイムアウトでwatchが切れた場合は、最後に受け取ったresourceVersionからwatchし直す。
+/// エラーが起きるまで返らない
+async fn watch(
+    client: &KubeClient,
+    path: &str,
+    resource_version: &mut String,
+    cache: &SharedTableCache,
+    changed: &watch::Sender<()>,
+) -> Result<()> {
+    loop {
+        let mut stream = client.table_watch(path, resource_version).await?.boxed();
+
+        while let Some(event) = stream.try_next().await? {
+            let version = cache
+                .write()
+                .expect("Failed to write table cache")
+                .apply(event, Instant::now())?;
+
+            if let Some(version) = version {
+                *resource_version = version;
+            }
+
+            changed.send_replace(());
+        }
+    }
+}
+
+async fn review_cluster_access(client: &KubeClient, resource: &ResourcePath) -> Result<bool> {
+    let api: Api<SelfSubjectAccessReview> = Api::all(client.to_client());
+
+    for verb in ["list", "watch"] {
+        let review = SelfSubjectAccessReview {
+            spec: SelfSubjectAccessReviewSpec {
+                resource_attributes: Some(ResourceAttributes {
+                    group: Some(resource.group.clone()),
+                    version: Some(resource.version.clone()),
+                    resource: Some(resource.resource.clone()),
+                    verb: Some(verb.to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let review = api.create(&PostParams::default(), &review).await?;
+
+        if !review.status.is_some_and(|status| status.allowed) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// 選択したnamespaceがすべてのnamespaceを含むか
+fn covers_all_namespaces(namespaces: &[String], all_namespaces: &Table) -> bool {
+    all_namespaces
+        .rows
+        .iter()
+        .filter_map(|row| row_metadata(row, "name"))
+        .all(|ns| namespaces.iter().any(|selected| selected == ns))
+}
+
+/// クラスター全体のTableを選択したnamespaceごとに分ける
+fn split_by_namespace(table: Table, namespaces: &[String]) -> Vec<(String, Table)> {
+    let mut rows: HashMap<String, Vec<TableRow>> = HashMap::new();
+
+    for row in table.rows {
+        if let Some(ns) = row_metadata(&row, "namespace") {
+            rows.entry(ns.to_string()).or_default().push(row);
+        }
+    }
+
+    namespaces
+        .iter()
+        .map(|ns| {
+            let table = Table {
+                metadata: table.metadata.clone(),
+                column_definitions: table.column_definitions.clone(),
+                rows: rows.remove(ns).unwrap_or_default(),
+                ..Default::default()
+            };
+
+            (ns.to_string(), table)
+        })
+        .collect()
+}
+
+fn row_metadata<'a>(row: &'a TableRow, key: &str) -> Option<&'a str> {
+    row.object.as_ref()?.0.get("metadata")?.get(key)?.as_str()
+}
+
+fn row_key(row: &TableRow) -> String {
+    let name = row_metadata(row, "name")
+        .map(ToString::to_string)
+        .or_else(|| row.cells.first().map(ToString::to_string))
+        .unwrap_or_default();
+
+    format!(
+        "{}/{}",
+        row_metadata(row, "namespace").unwrap_or_default(),
+        name
+    )
+}
+
+/// kubectlの経過時間の表記（例: `5m10s`, `3d4h`）を解析する
+fn parse_duration(s: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    let mut number = String::new();
+
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            'y' => 60 * 60 * 24 * 365,
+            _ => return None,
+        };
+
+        seconds += number.parse::<u64>().ok()? * unit;
+
+        number.clear();
+    }
+
+    if !number.is_empty() || s.is_empty() {
+        return None;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// kubectlと同じ形式で経過時間を表示する（k8s.io/apimachinery/pkg/util/duration.HumanDuration）
+fn human_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let minutes = seconds / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    let years = days / 365;
+
+    if seconds < 60 * 2 {
+        format!("{}s", seconds)
+    } else if minutes < 10 {
+        match seconds % 60 {
+            0 => format!("{}m", minutes),
+            s => format!("{}m{}s", minutes, s),
+        }
+    } else if minutes < 60 * 3 {
+        format!("{}m", minutes)
+    } else if hours < 8 {
+        match minutes % 60 {
+            0 => format!("{}h", hours),
+            m => format!("{}h{}m", hours, m),
+        }
+    } else if hours < 48 {
+        format!("{}h", hours)
+    } else if hours < 24 * 8 {
+        match hours % 24 {
+            0 => format!("{}d", days),
+            h => format!("{}d{}h", days, h),
+        }
+    } else if days < 365 * 2 {
+        format!("{}d", days)
+    } else if days < 365 * 8 {
+        match days % 365 {
+            0 => format!("{}y", years),
+            d => format!("{}y{}d", years, d),
+        }
+    } else {
+        format!("{}y", years)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("45s", Some(45))]
+    #[case("5m10s", Some(310))]
+    #[case("3d4h", Some(273_600))]
+    #[case("2y10d", Some(63_936_000))]
+    #[case("<unknown>", None)]
+    #[case("", None)]
+    #[case("10", None)]
+    fn 経過時間を解析する(#[case] input: &str, #[case] expected: Option<u64>) {
+        assert_eq!(parse_duration(input), expected.map(Duration::from_secs));
+    }
+
+    #[rstest]
+    #[case(119, "119s")]
+    #[case(310, "5m10s")]
+    #[case(600, "10m")]
+    #[case(3 * 3600 + 5 * 60, "3h5m")]
+    #[case(30 * 3600, "30h")]
+    #[case(3 * 86400 + 4 * 3600, "3d4h")]
+    #[case(400 * 86400, "400d")]
+    #[case(3 * 365 * 86400, "3y")]
+    fn kubectlと同じ形式で経過時間を表示する(
+        #[case] seconds: u64,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(human_duration(Duration::from_secs(seconds)), expected);
+    }
+
+    fn table(rows: &str) -> Table {
+        serde_json::from_str(&format!(
+            indoc! {r#"
+                {{
+                    "kind": "Table",
+                    "apiVersion": "meta.k8s.io/v1",
+                    "metadata": {{ "resourceVersion": "100" }},
+                    "columnDefinitions": [
+                        {{ "name": "Name", "type": "string", "format": "name", "description": "", "priority": 0 }},
+                        {{ "name": "Status", "type": "string", "format": "", "description": "", "priority": 0 }},
+                        {{ "name": "Age", "type": "string", "format": "", "description": "", "priority": 0 }}
+                    ],
+                    "rows": [{}]
+                }}
+            "#},
+            rows
+        ))
+        .unwrap()
+    }
+
+    fn row(name: &str, status: &str, age: &str, resource_version: &str) -> String {
+        format!(
+            r#"{{
+                "cells": ["{name}", "{status}", "{age}"],
+                "object": {{
+                    "kind": "PartialObjectMetadata",
+                    "metadata": {{ "name": "{name}", "namespace": "default", "resourceVersion": "{resource_version}" }}
+                }}
+            }}"#
+        )
+    }
+
+    fn cells(table: &Table) -> Vec<Vec<String>> {
+        table
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(ToString::to_string).collect())
+            .collect()
+    }
+
+    #[test]
+    fn watchで受け取った変更をキャッシュに反映する() {
+        let now = Instant::now();
+
+        let mut cache = TableCache::default();
+
+        cache.replace(
+            table(
+                &[
+                    row("app-0", "Running", "5m", "1"),
+                    row("app-1", "Running", "5m", "2"),
+                ]
+                .join(","),
+            ),
+            now,
+        );
+
+        let version = cache
+            .apply(
+                WatchEvent::Modified(table(&row("app-0", "Terminating", "5m", "101"))),
+                now,
+            )
+            .unwrap();
+
+        assert_eq!(version.as_deref(), Some("101"));
+
+        cache
+            .apply(
+                WatchEvent::Added(table(&row("app-2", "Pending", "0s", "102"))),
+                now,
+            )
+            .unwrap();
+
+        cache
+            .apply(
+                WatchEvent::Deleted(table(&row("app-1", "Running", "5m", "103"))),
+                now,
+            )
+            .unwrap();
+
+        assert_eq!(
+            cells(&cache.to_table(now).unwrap()),
+            vec![
+                vec!["app-0", "Terminating", "5m"],
+                vec!["app-2", "Pending", "0s"],
+            ]
+        );
+    }
+
+    #[test]
+    fn ページごとに受け取った行を追加する() {
+        let now = Instant::now();
+
+        let mut cache = TableCache::default();
+
+        let mut first = table(&row("app-0", "Running", "5m", "1"));
+        first.metadata.as_mut().unwrap().continue_ = Some("next".to_string());
+
+        cache.replace(first, now);
+
+        let partial = cache.to_table(now).unwrap();
+
+        assert!(partial.is_partial());
+        assert_eq!(cells(&partial), vec![vec!["app-0", "Running", "5m"]]);
+
+        cache.extend(table(&row("app-1", "Pending", "0s", "2")), now);
+
+        let table = cache.to_table(now).unwrap();
+
+        assert!(!table.is_partial());
+        assert_eq!(
+            cells(&table),
+            vec![
+                vec!["app-0", "Running", "5m"],
+                vec!["app-1", "Pending", "0s"],
+            ]
+        );
+    }
+
+    #[test]
+    fn 期限切れのエラーを判定する() {
+        let expired = anyhow::Error::from(kube::Error::Api(kube::core::ErrorResponse {
+            status: "Failure".to_string(),
+            message: "The provided continue parameter is too old".to_string(),
+            reason: "Expired".to_string(),
+            code: 410,
+        }));
+
+        assert!(is_expired(&expired));
+        assert!(!is_expired(&anyhow!("error")));
+    }
+
+    #[test]
+    fn 経過時間の列に受け取ってからの時間を加算する() {
+        let now = Instant::now();
+
+        let mut cache = TableCache::default();
+
+        cache.replace(table(&row("app-0", "Running", "4m50s", "1")), now);
+
+        assert_eq!(
+            cells(&cache.to_table(now + Duration::from_secs(20)).unwrap()),
+            vec![vec!["app-0", "Running", "5m10s"]]
+        );
+    }
+
+    #[test]
+    fn クラスター全体のテーブルを選択したnamespaceごとに分ける() {
+        let mut table = table(
+            &[
+                row("app-0", "Running", "5m", "1"),
+                row("app-1", "Running", "5m", "2"),
+            ]
+            .join(","),
+        );
+
+        table.rows[1].object.as_mut().unwrap().0["metadata"]["namespace"] = "kube-system".into();
+
+        let actual: Vec<(String, Vec<Vec<String>>)> = split_by_namespace(
+            table,
+            &[
+                "kube-system".to_string(),
+                "default".to_string(),
+                "empty".to_string(),
+            ],
+        )
+        .into_iter()
+        .map(|(ns, table)| (ns, cells(&table)))
+        .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "kube-system".to_string(),
+                    vec![vec![
+                        "app-1".to_string(),
+                        "Running".to_string(),
+                        "5m".to_string()
+                    ]]
+                ),
+                (
+                    "default".to_string(),
+                    vec![vec![
+                        "app-0".to_string(),
+                        "Running".to_string(),
+                        "5m".to_string()
+                    ]]
+                ),
+                ("empty".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn 選択したnamespaceがすべてのnamespaceを含むかを判定する() {
+        let all = table(
+            &[
+                row("default", "Active", "5m", "1"),
+                row("kube-system", "Active", "5m", "2"),
+            ]
+            .join(","),
+        );
+
+        assert!(covers_all_namespaces(
+            &["kube-system".to_string(), "default".to_string()],
+            &all
+        ));
+        assert!(!covers_all_namespaces(&["default".to_string()], &all));
+    }
+
+    #[test]
+    fn リソースのapiパスを生成する() {
+        let pods = ResourcePath::new("", "v1", "pods");
+
+        assert_eq!(pods.namespaced("default"), "api/v1/namespaces/default/pods");
+        assert_eq!(pods.cluster(), "api/v1/pods");
+
+        let deployments = ResourcePath::new("apps", "v1", "deployments");
+
+        assert_eq!(
+            deployments.namespaced("default"),
+            "apis/apps/v1/namespaces/default/deployments"
+        );
+        assert_eq!(deployments.cluster(), "apis/apps/v1/deployments");
+    }
+
+    #[test]
+    fn listのエラーは元のエラーを保持する() {
+        let cache = TableCache {
+            error: Some(CachedError(Arc::new(
+                kube::Error::Service("connection refused".into()).into(),
+            ))),
+            ..Default::default()
+        };
+
+        let err = cache.to_table(Instant::now()).unwrap_err();
+
+        assert!(err
+            .chain()
+            .any(|cause| matches!(cause.downcast_ref(), Some(kube::Error::Service(_)))));
+    }
+
+    #[test]
+    fn watchのエラーはエラーを返す() {
+        let mut cache = TableCache::default();
+
+        let event = serde_json::from_str::<WatchEvent<Table>>(indoc! {r#"
+            {
+                "type": "ERROR",
+                "object": { "status": "Failure", "message": "too old resource version", "reason": "Expired", "code": 410 }
+            }
+        "#})
+        .unwrap();
+
+        assert!(cache.apply(event, Instant::now()).is_err());
+    }
+
+    #[test]
+    fn 待っているパスの更新だけで起こされる() {
+        use futures::FutureExt as _;
+
+        let (pods, _) = watch::channel(());
+        let (secrets, _) = watch::channel(());
+        let (stopped, _) = watch::channel(());
+
+        let mut receivers = vec![pods.subscribe(), stopped.subscribe()];
+
+        drop(stopped);
+        secrets.send_replace(());
+
+        assert!(wait_any_change(&mut receivers).now_or_never().is_none());
+
+        pods.send_replace(());
+
+        assert!(wait_any_change(&mut receivers).now_or_never().is_some());
+    }
+
+    #[test]
+    fn 読み取るパスがない場合は起こされない() {
+        use futures::FutureExt as _;
+
+        assert!(wait_any_change(&mut []).now_or_never().is_none());
+    }
+}
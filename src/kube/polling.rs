@@ -0,0 +1,206 @@
+//! タブの表示状態に応じたポーリング間隔
+//!
+//! 表示中のタブのポーラーは通常の間隔でポーリングし、
+//! 表示していないタブのポーラーはバックグラウンドの間隔まで遅くするか停止する。
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::{sync::watch, time::Instant};
+
+/// ポーリング間隔の設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollIntervals {
+    /// 表示中のタブの一覧を更新する間隔
+    pub interval: Duration,
+    /// 表示中のタブの詳細（YAMLなど）を更新する間隔
+    pub description_interval: Duration,
+    /// 表示していないタブを更新する間隔（Noneの場合は停止する）
+    pub background_interval: Option<Duration>,
+}
+
+impl Default for PollIntervals {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            description_interval: Duration::from_secs(3),
+            background_interval: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+/// 表示中のタブをポーラー間で共有する
+#[derive(Debug, Clone)]
+pub struct Polling {
+    intervals: PollIntervals,
+    active_tab: Arc<watch::Sender<String>>,
+}
+
+impl Polling {
+    pub fn new(intervals: PollIntervals) -> Self {
+        Self {
+            intervals,
+            active_tab: Arc::new(watch::channel(String::new()).0),
+        }
+    }
+
+    /// 表示中のタブを切り替える
+    pub fn activate(&self, tab_id: &str) {
+        self.active_tab.send_if_modified(|active| {
+            if active == tab_id {
+                false
+            } else {
+                *active = tab_id.to_string();
+                true
+            }
+        });
+    }
+
+    /// タブの一覧を更新する [`PollTicker`] を返す
+    pub fn ticker(&self, tab_id: &'static str) -> PollTicker {
+        self.ticker_with(tab_id, self.intervals.interval)
+    }
+
+    /// タブの詳細を更新する [`PollTicker`] を返す
+    pub fn description_ticker(&self, tab_id: &'static str) -> PollTicker {
+        self.ticker_with(tab_id, self.intervals.description_interval)
+    }
+
+    fn ticker_with(&self, tab_id: &'static str, interval: Duration) -> PollTicker {
+        PollTicker {
+            tab_id,
+            interval,
+            background_interval: self.intervals.background_interval,
+            active_tab: self.active_tab.subscribe(),
+            last_tick: None,
+        }
+    }
+}
+
+/// タブの表示状態に応じた間隔で返る
+pub struct PollTicker {
+    tab_id: &'static str,
+    interval: Duration,
+    background_interval: Option<Duration>,
+    active_tab: watch::Receiver<String>,
+    last_tick: Option<Instant>,
+}
+
+impl PollTicker {
+    /// タブを表示中かどうか
+    pub fn is_active(&self) -> bool {
+        *self.active_tab.borrow() == self.tab_id
+    }
+
+    /// 次のポーリングまで待つ
+    ///
+    /// 初回はすぐに返る。タブが表示されたときは間隔を待たずに返る。
+    pub async fn tick(&mut self) {
+        loop {
+            let was_active = *self.active_tab.borrow_and_update() == self.tab_id;
+
+            let deadline = self.next_deadline(was_active);
+
+            tokio::select! {
+                _ = sleep_until(deadline) => break,
+                Ok(()) = self.active_tab.changed() => {
+                    if !was_active && self.is_active() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.reset();
+    }
+
+    /// 最後にポーリングした時刻を現在時刻にする
+    pub fn reset(&mut self) {
+        self.last_tick = Some(Instant::now());
+    }
+
+    fn next_deadline(&self, is_active: bool) -> Option<Instant> {
+        let period = if is_active {
+            Some(self.interval)
+        } else {
+            self.background_interval
+        }?;
+
+        Some(
+            self.last_tick
+                .map_or_else(Instant::now, |last| last + period),
+        )
+    }
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAB: &str = "pod_tab";
+
+    fn polling(background_interval: Option<Duration>) -> Polling {
+        Polling::new(PollIntervals {
+            interval: Duration::from_secs(1),
+            description_interval: Duration::from_secs(3),
+            background_interval,
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn 表示中のタブは通常の間隔で返る() {
+        let polling = polling(Some(Duration::from_secs(10)));
+        polling.activate(TAB);
+
+        let mut ticker = polling.ticker(TAB);
+        ticker.tick().await;
+
+        let start = Instant::now();
+        ticker.tick().await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn 表示していないタブはバックグラウンドの間隔で返る() {
+        let polling = polling(Some(Duration::from_secs(10)));
+        polling.activate("config_tab");
+
+        let mut ticker = polling.ticker(TAB);
+        ticker.tick().await;
+
+        let start = Instant::now();
+        ticker.tick().await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn 停止中のタブは表示されたときに返る() {
+        let polling = polling(None);
+        polling.activate("config_tab");
+
+        let mut ticker = polling.ticker(TAB);
+
+        let start = Instant::now();
+
+        let activate = {
+            let polling = polling.clone();
+            async move {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                polling.activate(TAB);
+            }
+        };
+
+        tokio::join!(ticker.tick(), activate);
+
+        assert_eq!(start.elapsed(), Duration::from_secs(60));
+        assert!(ticker.is_active());
+    }
+}
@@ -4,7 +4,7 @@ use anyhow::Result;
 
 use crate::kube::{
     apis::v1_table::{Table, TableRow},
-    KubeClient, KubeClientRequest as _,
+    informer::{ResourcePath, TableInformers},
 };
 
 #[derive(Debug, Default)]
@@ -19,6 +19,26 @@ pub struct KubeTableRow {
 pub struct KubeTable {
     pub header: Vec<String>,
     pub rows: Vec<KubeTableRow>,
+    /// 残りのページを取得中か
+    pub loading: bool,
+}
+
+/// 選択したnamespaceから取得した行
+#[derive(Debug, Default)]
+pub struct ResourceRows {
+    pub rows: Vec<KubeTableRow>,
+    /// 残りのページを取得中か
+    pub loading: bool,
+}
+
+impl FromIterator<ResourceRows> for ResourceRows {
+    fn from_iter<I: IntoIterator<Item = ResourceRows>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::default(), |mut acc, rows| {
+            acc.rows.extend(rows.rows);
+            acc.loading |= rows.loading;
+            acc
+        })
+    }
 }
 
 #[allow(dead_code)]
@@ -58,6 +78,12 @@ impl KubeTable {
 
         self.rows = rows;
     }
+
+    /// 取得した行で更新する
+    pub fn update_resource_rows(&mut self, rows: ResourceRows) {
+        self.loading = rows.loading;
+        self.update_rows(rows.rows);
+    }
 }
 
 #[allow(dead_code)]
@@ -73,22 +99,36 @@ pub fn insert_ns(namespaces: &[String]) -> bool {
     namespaces.len() != 1
 }
 
+/// 選択したnamespaceのリソースを取得し、namespaceの順に行を返す
+///
+/// 残りのページを取得中の場合は、それまでに受け取った行を返す
 pub async fn get_resource_per_namespace<F>(
-    client: &KubeClient,
-    path: String,
+    informers: &TableInformers,
+    resource: &ResourcePath,
+    namespaces: &[String],
     target_values: &[&str],
     create_cells: F,
-) -> Result<Vec<KubeTableRow>>
+) -> Result<ResourceRows>
 where
-    F: Fn(&TableRow, &[usize]) -> KubeTableRow,
+    F: Fn(&str, &TableRow, &[usize]) -> KubeTableRow,
 {
-    let table: Table = client.table_request(&path).await?;
-
-    let indexes = table.find_indexes(target_values);
+    let tables: Vec<(String, Table)> = informers.tables(resource, namespaces).await?;
 
-    Ok(table
-        .rows
+    let rows = tables
         .iter()
-        .map(|row| (create_cells)(row, &indexes))
-        .collect())
+        .flat_map(|(ns, table)| {
+            let indexes = table.find_indexes(target_values);
+
+            table
+                .rows
+                .iter()
+                .map(|row| (create_cells)(ns, row, &indexes))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Ok(ResourceRows {
+        rows,
+        loading: tables.iter().any(|(_, table)| table.is_partial()),
+    })
 }
@@ -2,6 +2,7 @@ mod ansi;
 mod app;
 mod clipboard;
 mod cmd;
+mod config;
 mod features;
 mod kube;
 mod logging;
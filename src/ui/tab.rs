@@ -35,12 +35,11 @@ impl<'a> Tab<'a> {
     ) -> Self {
         let widgets: Vec<_> = widgets.into();
 
-        let activatable_widget_indices = widgets
-            .iter()
-            .enumerate()
-            .filter(|(_, w)| w.can_activate())
-            .map(|(i, _)| i)
-            .collect();
+        let activatable_widget_indices = Self::activatable_widget_indices(&widgets);
+
+        let mut layout = layout;
+
+        layout.update_widgets(&widgets);
 
         Self {
             id: id.into(),
@@ -54,6 +53,15 @@ impl<'a> Tab<'a> {
         }
     }
 
+    fn activatable_widget_indices(widgets: &[Widget<'a>]) -> Vec<usize> {
+        widgets
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.can_activate())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
@@ -161,6 +169,40 @@ impl<'a> Tab<'a> {
         self.layout
             .toggle_split_direction(self.chunk, &mut self.widgets);
     }
+
+    /// ウィジェットを末尾に追加し、レイアウトを再計算する
+    pub fn push_widget(&mut self, widget: Widget<'a>) {
+        self.widgets.push(widget);
+
+        self.update_widgets();
+    }
+
+    /// ウィジェットを削除し、レイアウトを再計算する
+    ///
+    /// 削除したウィジェットがアクティブだった場合は直前のウィジェットをアクティブにする
+    pub fn remove_widget_by_id(&mut self, id: &str) {
+        let Some(index) = self.widgets.iter().position(|w| w.id() == id) else {
+            return;
+        };
+
+        self.widgets.remove(index);
+
+        if index <= self.active_widget_index && 0 < self.active_widget_index {
+            self.active_widget_index -= 1;
+        }
+
+        self.update_widgets();
+    }
+
+    fn update_widgets(&mut self) {
+        self.clear_mouse_over();
+
+        self.activatable_widget_indices = Self::activatable_widget_indices(&self.widgets);
+
+        self.layout.update_widgets(&self.widgets);
+
+        self.layout.update_chunk(self.chunk, &mut self.widgets);
+    }
 }
 
 impl<'a> Tab<'a> {
@@ -175,6 +217,95 @@ impl<'a> Tab<'a> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::layout::{Constraint, Direction};
+
+    fn text_widget(id: &str) -> Widget<'static> {
+        Text::builder().id(id).build().into()
+    }
+
+    fn tab() -> Tab<'static> {
+        let layout = TabLayout::dynamic(
+            |direction, widget_ids: &[&str]| {
+                NestedWidgetLayout::default()
+                    .direction(direction)
+                    .nested_widget_layout(
+                        (0..widget_ids.len())
+                            .map(|i| {
+                                NestedLayoutElement(
+                                    Constraint::Ratio(1, widget_ids.len() as u32),
+                                    LayoutElement::WidgetIndex(i),
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+            },
+            Direction::Horizontal,
+        );
+
+        let mut tab = Tab::new("tab", "Tab", [text_widget("a"), text_widget("b")], layout);
+
+        tab.update_chunk(Rect::new(0, 0, 90, 10));
+
+        tab
+    }
+
+    #[test]
+    fn ウィジェットを追加するとレイアウトが再計算される() {
+        let mut tab = tab();
+
+        tab.push_widget(text_widget("c"));
+
+        let chunks: Vec<_> = tab
+            .as_ref_widgets()
+            .iter()
+            .map(|w| (w.id().to_string(), w.chunk().width))
+            .collect();
+
+        assert_eq!(
+            chunks,
+            vec![
+                ("a".to_string(), 30),
+                ("b".to_string(), 30),
+                ("c".to_string(), 30)
+            ]
+        );
+
+        tab.activate_next_widget();
+        tab.activate_next_widget();
+
+        assert_eq!(tab.active_widget_id(), "c");
+    }
+
+    #[test]
+    fn アクティブなウィジェットを削除すると直前のウィジェットがアクティブになる() {
+        let mut tab = tab();
+
+        tab.push_widget(text_widget("c"));
+
+        tab.activate_widget_by_id("c");
+
+        tab.remove_widget_by_id("c");
+
+        assert_eq!(tab.active_widget_id(), "b");
+        assert_eq!(tab.find_widget("b").unwrap().chunk().width, 45);
+    }
+
+    #[test]
+    fn 前にあるウィジェットを削除してもアクティブなウィジェットは変わらない() {
+        let mut tab = tab();
+
+        tab.activate_widget_by_id("b");
+
+        tab.remove_widget_by_id("a");
+
+        assert_eq!(tab.active_widget_id(), "b");
+    }
+}
+
 mod layout {
     use std::rc::Rc;
 
@@ -182,10 +313,15 @@ mod layout {
 
     use super::{Widget, WidgetTrait as _};
 
+    type LayoutFn = dyn Fn(Direction, &[&str]) -> NestedWidgetLayout;
+
     pub struct TabLayout {
         /// Callback to generate the nested widget layout.
-        /// The callback takes the current direction and returns the nested widget layout.
-        layout_fn: Rc<dyn Fn(Direction) -> NestedWidgetLayout>,
+        /// The callback takes the current direction and the widget ids, and returns the nested widget layout.
+        layout_fn: Rc<LayoutFn>,
+
+        /// The ids of the widgets in the tab.
+        widget_ids: Vec<String>,
 
         /// The current direction of the layout.
         current_direction: Direction,
@@ -199,15 +335,30 @@ mod layout {
         where
             T: Fn(Direction) -> NestedWidgetLayout + 'static,
         {
-            let current_layout = (layout_fn)(direction);
+            Self::dynamic(move |direction, _: &[&str]| layout_fn(direction), direction)
+        }
+
+        /// ウィジェットの増減に合わせてレイアウトを組み替える
+        pub fn dynamic<T>(layout_fn: T, direction: Direction) -> Self
+        where
+            T: Fn(Direction, &[&str]) -> NestedWidgetLayout + 'static,
+        {
+            let current_layout = (layout_fn)(direction, &[]);
 
             Self {
                 layout_fn: Rc::new(layout_fn),
+                widget_ids: Vec::new(),
                 current_direction: direction,
                 current_layout,
             }
         }
 
+        pub fn update_widgets(&mut self, widgets: &[Widget<'_>]) {
+            self.widget_ids = widgets.iter().map(|w| w.id().to_string()).collect();
+
+            self.current_layout = self.update_layout();
+        }
+
         pub fn toggle_split_direction(&mut self, chunk: Rect, widgets: &mut [Widget<'_>]) {
             self.current_direction = match self.current_direction {
                 Direction::Horizontal => Direction::Vertical,
@@ -228,7 +379,9 @@ mod layout {
         }
 
         fn update_layout(&self) -> NestedWidgetLayout {
-            (self.layout_fn)(self.current_direction)
+            let widget_ids: Vec<&str> = self.widget_ids.iter().map(String::as_str).collect();
+
+            (self.layout_fn)(self.current_direction, &widget_ids)
         }
     }
 
@@ -6,6 +6,9 @@ use ratatui::{
     widgets::{Block, BorderType, Borders},
 };
 
+/// 取得中にタイトルの末尾に表示する文字列
+const LOADING_TITLE: &str = " (loading...)";
+
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct WidgetConfigBuilder(WidgetConfig);
 
@@ -16,6 +19,8 @@ pub struct WidgetConfig {
     append_title: Option<Title>,
     block: Block<'static>,
     can_activate: bool,
+    /// 内容を取得中であることをタイトルに表示する
+    loading: bool,
 }
 
 impl Default for WidgetConfig {
@@ -27,6 +32,7 @@ impl Default for WidgetConfig {
                 .border_type(BorderType::Plain)
                 .borders(Borders::ALL),
             can_activate: true,
+            loading: false,
         }
     }
 }
@@ -90,6 +96,14 @@ impl WidgetConfig {
         &mut self.append_title
     }
 
+    pub fn loading(&self) -> bool {
+        self.loading
+    }
+
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
     pub fn render_title(&self, is_active: bool) -> Vec<Span<'static>> {
         if self.title.to_string() == "" {
             return Vec::new();
@@ -101,6 +115,10 @@ impl WidgetConfig {
             title.append(&mut append.spans().spans);
         }
 
+        if self.loading {
+            title.push(LOADING_TITLE.into());
+        }
+
         title.push(" ".into());
 
         if self.can_activate {
@@ -269,4 +287,28 @@ mod tests {
             title
         )
     }
+
+    #[test]
+    fn render_title_with_loading() {
+        let mut wc = WidgetConfig::builder()
+            .title("Title")
+            .append_title(" [1/500]")
+            .disable_activation()
+            .build();
+
+        wc.set_loading(true);
+
+        let title = wc.render_title(false);
+
+        assert_eq!(
+            vec![
+                Span::raw(" "),
+                Span::raw("Title"),
+                Span::raw(" [1/500]"),
+                Span::raw(" (loading...)"),
+                Span::raw(" "),
+            ],
+            title
+        )
+    }
 }
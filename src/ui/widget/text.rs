@@ -164,6 +164,7 @@ pub struct TextBuilder {
     item: Vec<LiteralItem>,
     wrap: bool,
     follow: bool,
+    max_lines: Option<usize>,
     #[derivative(Debug = "ignore")]
     block_injection: Option<RenderBlockInjection>,
     #[derivative(Debug = "ignore")]
@@ -202,6 +203,14 @@ impl TextBuilder {
         self
     }
 
+    /// 保持する最大行数を設定する
+    ///
+    /// 超過した場合は古い行から削除する
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
     pub fn action<F, E>(mut self, ev: E, cb: F) -> Self
     where
         E: Into<UserEvent>,
@@ -225,17 +234,22 @@ impl TextBuilder {
     }
 
     pub fn build(self) -> Text {
-        Text {
+        let mut text = Text {
             id: self.id,
             widget_config: self.widget_config,
             item: TextItem::new(self.item, None),
             wrap: self.wrap,
             follow: self.follow,
+            max_lines: self.max_lines,
             actions: self.actions,
             block_injection: self.block_injection,
             clipboard: self.clipboard,
             ..Default::default()
-        }
+        };
+
+        text.truncate();
+
+        text
     }
 }
 
@@ -248,6 +262,9 @@ pub struct Text {
     chunk: Rect,
     wrap: bool,
     follow: bool,
+    max_lines: Option<usize>,
+    /// max_linesを超過して削除した行数
+    truncated_lines: usize,
     scroll: Scroll,
     search_widget: SearchForm,
     /// 検索中、検索ワード入力中、オフの3つのモード
@@ -436,6 +453,50 @@ impl Text {
         (self.scroll.y, self.scroll_y_last_index())
     }
 
+    /// max_linesを超過して削除した行数
+    pub fn truncated_lines(&self) -> usize {
+        self.truncated_lines
+    }
+
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    pub fn clipboard(&self) -> Option<&Rc<RefCell<Clipboard>>> {
+        self.clipboard.as_ref()
+    }
+
+    /// max_linesを超過した古い行を削除し、表示位置を削除した分だけずらす
+    fn truncate(&mut self) {
+        let Some(max_lines) = self.max_lines else {
+            return;
+        };
+
+        let len = self.item.len();
+
+        if len <= max_lines {
+            return;
+        }
+
+        let removed_line_number = self.item.truncate_front(len - max_lines);
+
+        self.truncated_lines += len - max_lines;
+
+        self.scroll.y = self.scroll.y.saturating_sub(removed_line_number);
+
+        if let Some(highlight_content) = &mut self.highlight_content {
+            let area = &mut highlight_content.area;
+
+            area.start.y = area.start.y.saturating_sub(removed_line_number);
+            area.end.y = area.end.y.saturating_sub(removed_line_number);
+        }
+    }
+
+    /// 表示している全行をANSIエスケープシーケンスを含む元の文字列で返す
+    pub fn literal_lines(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.item.literal_lines()
+    }
+
     fn match_action(&self, ev: UserEvent) -> Option<&Callback> {
         self.actions
             .iter()
@@ -513,6 +574,8 @@ impl WidgetTrait for Text {
             }
         }
 
+        self.truncate();
+
         if self.follow && is_bottom {
             self.select_last()
         }
@@ -524,6 +587,8 @@ impl WidgetTrait for Text {
         let item = item.array();
         self.item.update(item);
 
+        self.truncate();
+
         if self.follow && is_bottom {
             self.select_last()
         }
@@ -776,6 +841,7 @@ impl WidgetTrait for Text {
         };
 
         self.item = TextItem::new(vec![], wrap_width);
+        self.truncated_lines = 0;
         self.search_cancel();
 
         *(self.widget_config.append_title_mut()) = None;
@@ -938,4 +1004,68 @@ mod tests {
             assert_eq!(text.scroll.x, 7);
         }
     }
+
+    mod 最大行数 {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        fn items(range: std::ops::Range<usize>) -> Item {
+            Item::Array(
+                range
+                    .map(|i| LiteralItem::new(i.to_string(), None))
+                    .collect(),
+            )
+        }
+
+        #[test]
+        fn 最大行数を超えたとき古い行を削除する() {
+            let mut text = Text::builder().max_lines(5).build();
+
+            text.update_chunk(Rect::new(0, 0, 10, 5));
+
+            text.append_widget_item(items(0..8));
+
+            assert_eq!(
+                text.literal_lines().collect::<Vec<_>>(),
+                vec!["3", "4", "5", "6", "7"]
+            );
+            assert_eq!(text.truncated_lines(), 3);
+
+            text.clear();
+
+            assert_eq!(text.truncated_lines(), 0);
+        }
+
+        #[test]
+        fn followが有効で末尾を表示しているとき末尾を表示し続ける() {
+            let mut text = Text::builder().follow().max_lines(5).build();
+
+            text.update_chunk(Rect::new(0, 0, 10, 5));
+
+            text.append_widget_item(items(0..5));
+            text.select_last();
+            text.append_widget_item(items(5..8));
+
+            assert_eq!(text.scroll.y, text.scroll_y_last_index());
+        }
+
+        #[test]
+        fn 末尾を表示していないとき削除した行数分だけ表示位置をずらす() {
+            let mut text = Text::builder().follow().max_lines(10).build();
+
+            text.update_chunk(Rect::new(0, 0, 10, 5));
+
+            text.append_widget_item(items(0..10));
+            text.select_first();
+            text.select_next(4);
+
+            assert_eq!(text.scroll.y, 4);
+
+            text.append_widget_item(items(10..13));
+
+            assert_eq!(text.scroll.y, 1);
+            assert_eq!(text.item.wrapped_lines()[text.scroll.y].index(), 1);
+            assert_eq!(text.literal_lines().nth(1), Some("4"));
+        }
+    }
 }
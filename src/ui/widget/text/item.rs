@@ -101,7 +101,10 @@ impl TextItem {
         let mut new = Self::new(item, wrap_width);
 
         if let Some(highlights) = highlights {
-            let prev_line_number = highlights.item[highlights.selected_index].line_number;
+            let prev_line_number = highlights
+                .item
+                .get(highlights.selected_index)
+                .map_or(0, |hl| hl.line_number);
 
             new.highlight(&highlights.word);
 
@@ -278,6 +281,71 @@ impl TextItem {
     pub fn max_chars(&self) -> usize {
         self.max_chars
     }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// 先頭から指定した行数を削除する
+    ///
+    /// 残った行のインデックスとハイライト情報を詰め直し、
+    /// 削除した折り返し後の行数を返す
+    pub fn truncate_front(&mut self, count: usize) -> usize {
+        let count = count.min(self.lines.len());
+
+        if count == 0 {
+            return 0;
+        }
+
+        let removed_line_number = self.lines[count - 1].wrapped_lines.end;
+
+        self.lines.drain(..count);
+        self.wrapped_lines.drain(..removed_line_number);
+
+        self.lines.iter_mut().for_each(|line| {
+            line.line_index -= count;
+            line.line_number -= removed_line_number;
+            line.wrapped_lines = (line.wrapped_lines.start - removed_line_number)
+                ..(line.wrapped_lines.end - removed_line_number);
+        });
+
+        self.wrapped_lines
+            .iter_mut()
+            .for_each(|wrapped_line| wrapped_line.line_index -= count);
+
+        if let Some(highlights) = &mut self.highlights {
+            let removed_highlights = highlights
+                .item
+                .iter()
+                .take_while(|hl| hl.line_index < count)
+                .count();
+
+            highlights.item.drain(..removed_highlights);
+
+            highlights.item.iter_mut().for_each(|hl| {
+                hl.line_index -= count;
+                hl.line_number -= removed_line_number;
+            });
+
+            if removed_highlights <= highlights.selected_index {
+                highlights.selected_index -= removed_highlights;
+            } else {
+                // 選択中のハイライトが削除された場合は先頭のハイライトを選択する
+                highlights.selected_index = 0;
+
+                self.highlight_color(0);
+            }
+        }
+
+        removed_line_number
+    }
+
+    /// 折り返し前の元の文字列（ANSIエスケープシーケンスを含む）を返す
+    pub fn literal_lines(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.lines
+            .iter()
+            .map(|line| line.literal_item.item.as_str())
+    }
 }
 
 impl TextItem {
@@ -317,7 +385,9 @@ impl TextItem {
 
     fn highlight_normal(&mut self, index: usize) {
         if let Some(highlights) = &mut self.highlights {
-            let hl = &highlights.item[index];
+            let Some(hl) = highlights.item.get(index) else {
+                return;
+            };
 
             let line = &mut self.lines[hl.line_index];
             let graphemes = &mut line.graphemes[hl.range.clone()];
@@ -330,7 +400,7 @@ impl TextItem {
 
     fn highlight_color(&mut self, index: usize) -> Option<usize> {
         if let Some(highlights) = &mut self.highlights {
-            let hl = &highlights.item[index];
+            let hl = highlights.item.get(index)?;
 
             let line = &mut self.lines[hl.line_index];
             let graphemes = &mut line.graphemes[hl.range.clone()];
@@ -375,7 +445,7 @@ impl TextItem {
 
             self.highlight_normal(index);
 
-            let index = (index + 1) % item_len;
+            let index = (index + 1) % item_len.max(1);
 
             self.highlight_color(index)
         } else {
@@ -404,17 +474,19 @@ impl TextItem {
     }
 
     pub fn highlight_status(&self) -> (usize, usize) {
-        if let Some(highlights) = &self.highlights {
-            (highlights.selected_index + 1, highlights.item.len())
-        } else {
-            (0, 0)
+        match &self.highlights {
+            Some(highlights) if !highlights.item.is_empty() => {
+                (highlights.selected_index + 1, highlights.item.len())
+            }
+            _ => (0, 0),
         }
     }
 
     pub fn highlight_selected_line_number(&self) -> Option<usize> {
         self.highlights
             .as_ref()
-            .map(|h| h.item[h.selected_index].line_number)
+            .and_then(|h| h.item.get(h.selected_index))
+            .map(|hl| hl.line_number)
     }
 }
 
@@ -488,7 +560,6 @@ struct Line {
     /// ベースとなる１行分の文字列データ
     ///
     /// この文字列のポインターを駆使していく
-    #[allow(dead_code)]
     literal_item: LiteralItem,
 
     /// 目でみたときの１文字ずつに分割した配列
@@ -840,6 +911,88 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn truncate_front() {
+            let mut item = TextItem::new(
+                vec![
+                    LiteralItem::new("0123456789", None),
+                    LiteralItem::new("01234", None),
+                    LiteralItem::new("0123456789", None),
+                ],
+                Some(5),
+            );
+
+            let removed = item.truncate_front(2);
+
+            assert_eq!(removed, 3);
+
+            let lines = &item.lines;
+            let wrapped_lines = &item.wrapped_lines;
+
+            assert_eq!(lines.len(), 1);
+            assert_eq!(lines[0].line_index, 0);
+            assert_eq!(lines[0].line_number, 0);
+            assert_eq!(lines[0].wrapped_lines, 0..2);
+
+            assert_eq!(wrapped_lines.len(), 2);
+            assert_eq!(wrapped_lines[0].line_index, 0);
+            assert_eq!(wrapped_lines[0].slice_ptr, &lines[0].graphemes[..5]);
+            assert_eq!(wrapped_lines[1].line_index, 0);
+            assert_eq!(wrapped_lines[1].slice_ptr, &lines[0].graphemes[5..]);
+        }
+
+        #[test]
+        fn truncate_front_keeps_highlights() {
+            let mut item = TextItem::new(
+                vec![
+                    LiteralItem::new("hello world", None),
+                    LiteralItem::new("hoge", None),
+                    LiteralItem::new("foo world", None),
+                    LiteralItem::new("bar world", None),
+                ],
+                Some(5),
+            );
+
+            item.highlight("world");
+            item.select_next_highlight();
+            item.select_next_highlight();
+
+            assert_eq!(item.highlight_status(), (3, 3));
+
+            item.truncate_front(2);
+
+            assert_eq!(item.highlight_status(), (2, 2));
+            assert_eq!(item.highlight_selected_line_number(), Some(2));
+
+            item.push(LiteralItem::new("baz world", None));
+
+            assert_eq!(item.highlight_status(), (2, 3));
+            assert_eq!(item.select_next_highlight(), Some(4));
+        }
+
+        #[test]
+        fn truncate_front_removes_selected_highlight() {
+            let mut item = TextItem::new(
+                vec![
+                    LiteralItem::new("hello world", None),
+                    LiteralItem::new("hoge", None),
+                ],
+                None,
+            );
+
+            item.highlight("world");
+
+            item.truncate_front(1);
+
+            assert_eq!(item.highlight_status(), (0, 0));
+            assert_eq!(item.highlight_selected_line_number(), None);
+            assert_eq!(item.select_next_highlight(), None);
+
+            item.push(LiteralItem::new("foo world", None));
+
+            assert_eq!(item.highlight_status(), (1, 1));
+        }
+
         mod max_chars {
             use super::*;
             use pretty_assertions::assert_eq;
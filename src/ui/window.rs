@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{any::Any, rc::Rc};
 
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
@@ -276,6 +276,10 @@ impl<'a> Window<'a> {
         &mut self.tabs[self.active_tab_index]
     }
 
+    pub fn find_tab_mut(&mut self, id: &str) -> Option<&mut Tab<'a>> {
+        self.tabs.iter_mut().find(|tab| tab.id() == id)
+    }
+
     pub fn activate_tab_by_index(&mut self, index: usize) {
         if self.tabs.get(index).is_some() {
             self.active_tab_index = index;
@@ -429,6 +433,10 @@ pub enum WindowAction {
     CloseWindow,
     Continue,
     UpdateContents(Kube),
+    /// 画面を一時停止して端末を明け渡す
+    ///
+    /// 明け渡し先（コンテナへの接続やエディタなど）は各機能が定義し、描画ループが解釈する
+    Suspend(Box<dyn Any>),
 }
 
 // Event
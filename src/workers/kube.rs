@@ -1,6 +1,8 @@
 pub mod color;
 mod config;
 mod controller;
+mod guard;
+pub mod health;
 pub mod message;
 mod store;
 mod worker;
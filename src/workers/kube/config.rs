@@ -3,6 +3,8 @@ use std::{ops::Deref, path::PathBuf};
 use anyhow::{anyhow, Result};
 use kube::config::{Kubeconfig, KubeconfigError};
 
+use crate::kube::{guard::WriteGuard, polling::PollIntervals};
+
 use super::TargetNamespaces;
 
 #[derive(Debug, Default, Clone)]
@@ -11,6 +13,8 @@ pub struct KubeWorkerConfig {
     pub target_namespaces: Option<TargetNamespaces>,
     pub context: Option<String>,
     pub all_namespaces: bool,
+    pub write_guard: WriteGuard,
+    pub poll_intervals: PollIntervals,
 }
 
 pub struct Context(String);
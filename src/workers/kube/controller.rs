@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -13,7 +14,7 @@ use futures::future::select_all;
 use k8s_openapi::api::core::v1::Namespace;
 use kube::{api::ListParams, config::Kubeconfig, Api, ResourceExt as _};
 use tokio::{
-    sync::RwLock,
+    sync::{watch, RwLock},
     task::{self, AbortHandle, JoinHandle},
 };
 
@@ -28,6 +29,14 @@ use crate::{
             message::ConfigMessage,
         },
         context::message::{ContextMessage, ContextRequest, ContextResponse},
+        delete::{
+            kube::{delete_resource, dry_run_delete},
+            message::{DeleteMessage, DeleteResponse},
+        },
+        edit::{
+            kube::{apply_edit, dry_run_edit, fetch_edit_yaml},
+            message::{EditMessage, EditRequest, EditResponse},
+        },
         event::kube::EventPoller,
         get::{kube::yaml::GetYamlWorker, message::GetMessage},
         namespace::message::{NamespaceMessage, NamespaceRequest, NamespaceResponse},
@@ -35,16 +44,31 @@ use crate::{
             kube::{NetworkDescriptionWorker, NetworkPoller},
             message::NetworkMessage,
         },
+        node::{
+            kube::{cordon_node, dry_run_node_request, DrainWorker, NodePoller},
+            message::{NodeMessage, NodeRequest, NodeResponse},
+        },
         pod::{
-            kube::{LogWorker, PodPoller},
-            message::LogMessage,
+            kube::{
+                dry_run_debug, exec_session, fetch_containers, save_log, DebugWorker, LogWorker,
+                PodPoller,
+            },
+            message::{DebugMessage, ExecMessage, LogMessage},
+        },
+        port_forward::{
+            kube::{PortForwardPoller, PortForwards, SharedPortForwards},
+            message::{PortForwardMessage, PortForwardRequest},
+        },
+        workload::{
+            kube::{dry_run_workload_request, execute_workload_request, WorkloadPoller},
+            message::{WorkloadMessage, WorkloadResponse},
         },
         yaml::{
             kube::{FetchResourceList, YamlWorker},
             message::{YamlMessage, YamlRequest, YamlResponse},
         },
     },
-    kube::KubeClient,
+    kube::{guard::WriteGuard, informer::TableInformers, polling::Polling, KubeClient},
     logger,
     message::Message,
     workers::kube::message::Kube,
@@ -52,6 +76,8 @@ use crate::{
 
 use super::{
     config::{read_kubeconfig, Context, KubeWorkerConfig},
+    guard::reject_write_request,
+    health::ConnectionHealth,
     store::{KubeState, KubeStore},
     worker::Worker,
     AbortWorker as _,
@@ -77,6 +103,12 @@ pub struct PollerBase {
     pub tx: Sender<Message>,
     pub shared_target_namespaces: SharedTargetNamespaces,
     pub kube_client: KubeClient,
+    /// タブ間で共有するリソースのキャッシュ
+    pub informers: TableInformers,
+    /// 表示中のタブに応じたポーリング間隔
+    pub polling: Polling,
+    /// コンテキストの接続状態
+    pub health: ConnectionHealth,
 }
 
 #[derive(Clone)]
@@ -92,6 +124,9 @@ pub struct KubeController {
     kubeconfig: Kubeconfig,
     context: String,
     store: KubeStore,
+    port_forwards: SharedPortForwards,
+    write_guard: WriteGuard,
+    polling: Polling,
 }
 
 impl KubeController {
@@ -106,12 +141,16 @@ impl KubeController {
             target_namespaces,
             context,
             all_namespaces,
+            write_guard,
+            poll_intervals,
         } = config;
 
         let kubeconfig = read_kubeconfig(kubeconfig)?;
 
         let context = Context::try_from(&kubeconfig, context)?;
 
+        let port_forwards = Arc::new(PortForwards::new(&context));
+
         let mut store = KubeStore::try_from_kubeconfig(kubeconfig.clone()).await?;
 
         let KubeState {
@@ -137,6 +176,9 @@ impl KubeController {
             kubeconfig,
             context: context.to_string(),
             store,
+            port_forwards,
+            write_guard,
+            polling: Polling::new(poll_intervals),
         })
     }
 
@@ -148,6 +190,9 @@ impl KubeController {
             kubeconfig,
             mut context,
             mut store,
+            port_forwards,
+            write_guard,
+            polling,
         } = self;
 
         while !is_terminated.load(Ordering::Relaxed) {
@@ -170,11 +215,16 @@ impl KubeController {
             let shared_target_api_resources = Arc::new(RwLock::new(target_api_resources.to_vec()));
             let shared_api_resources = ApiResources::shared();
 
+            let health = ConnectionHealth::new(tx.clone());
+
             let poller_base = PollerBase {
                 shared_target_namespaces: shared_target_namespaces.clone(),
                 tx: tx.clone(),
                 is_terminated: is_terminated.clone(),
                 kube_client: client.clone(),
+                informers: TableInformers::new(client.clone(), health.clone()),
+                polling: polling.clone(),
+                health,
             };
 
             let event_controller_handle = EventController::new(
@@ -187,6 +237,10 @@ impl KubeController {
                     .collect(),
                 shared_target_api_resources.clone(),
                 shared_api_resources.clone(),
+                port_forwards.clone(),
+                write_guard
+                    .is_readonly(&context)
+                    .then(|| context.to_string()),
             )
             .spawn();
 
@@ -195,6 +249,10 @@ impl KubeController {
             let network_handle =
                 NetworkPoller::new(poller_base.clone(), shared_api_resources.clone()).spawn();
             let event_handle = EventPoller::new(poller_base.clone()).spawn();
+            let workload_handle = WorkloadPoller::new(poller_base.clone()).spawn();
+            let node_handle = NodePoller::new(poller_base.clone()).spawn();
+            let port_forward_handle =
+                PortForwardPoller::new(poller_base.clone(), port_forwards.clone()).spawn();
             let api_handle = ApiPoller::new(
                 poller_base.clone(),
                 shared_target_api_resources.clone(),
@@ -208,6 +266,9 @@ impl KubeController {
                 config_handle,
                 network_handle,
                 event_handle,
+                workload_handle,
+                node_handle,
+                port_forward_handle,
                 api_handle,
             ];
 
@@ -233,6 +294,8 @@ impl KubeController {
                                 ),
                             );
 
+                            port_forwards.switch_context(&ctx).await;
+
                             context = ctx;
                         }
                         WorkerResult::Terminated => {}
@@ -262,6 +325,9 @@ struct EventController {
     contexts: Vec<String>,
     shared_target_api_resources: SharedTargetApiResources,
     shared_api_resources: SharedApiResources,
+    port_forwards: SharedPortForwards,
+    /// 書き込み操作を拒否する読み取り専用のコンテキスト
+    readonly_context: Option<String>,
 }
 
 impl EventController {
@@ -271,6 +337,8 @@ impl EventController {
         contexts: Vec<String>,
         shared_target_api_resources: SharedTargetApiResources,
         shared_api_resources: SharedApiResources,
+        port_forwards: SharedPortForwards,
+        readonly_context: Option<String>,
     ) -> Self {
         Self {
             base,
@@ -278,6 +346,8 @@ impl EventController {
             contexts,
             shared_target_api_resources,
             shared_api_resources,
+            port_forwards,
+            readonly_context,
         }
     }
 }
@@ -288,10 +358,13 @@ impl Worker for EventController {
 
     async fn run(&self) -> Self::Output {
         let mut log_handler: Option<AbortHandle> = None;
+        let mut closed_log_panes: Option<watch::Sender<HashSet<String>>> = None;
         let mut config_handler: Option<AbortHandle> = None;
         let mut network_handler: Option<AbortHandle> = None;
         let mut yaml_handler: Option<AbortHandle> = None;
         let mut get_handler: Option<AbortHandle> = None;
+        let mut drain_handler: Option<AbortHandle> = None;
+        let mut debug_handler: Option<AbortHandle> = None;
 
         let EventController {
             base: poll_worker,
@@ -299,6 +372,8 @@ impl Worker for EventController {
             contexts,
             shared_target_api_resources,
             shared_api_resources,
+            port_forwards,
+            readonly_context,
         } = self;
 
         let PollerBase {
@@ -306,6 +381,8 @@ impl Worker for EventController {
             tx,
             is_terminated,
             kube_client,
+            polling,
+            ..
         } = poll_worker;
 
         while !is_terminated.load(Ordering::Relaxed) {
@@ -316,6 +393,17 @@ impl Worker for EventController {
 
             let Ok(recv) = task.await else { continue };
 
+            let recv = match (recv, readonly_context) {
+                (Ok(Message::Kube(ev)), Some(context)) => {
+                    let Some(ev) = reject_write_request(&tx, ev, context) else {
+                        continue;
+                    };
+
+                    Ok(Message::Kube(ev))
+                }
+                (recv, _) => recv,
+            };
+
             match recv {
                 Ok(Message::Kube(ev)) => match ev {
                     Kube::Namespace(NamespaceMessage::Request(req)) => match req {
@@ -365,7 +453,72 @@ impl Worker for EventController {
                             handler.abort();
                         }
 
-                        log_handler = Some(LogWorker::new(tx, kube_client.clone(), req).spawn());
+                        let (closed_panes_tx, closed_panes) = watch::channel(HashSet::default());
+
+                        log_handler = Some(
+                            LogWorker::new(tx, kube_client.clone(), req)
+                                .closed_panes(closed_panes)
+                                .spawn(),
+                        );
+
+                        closed_log_panes = Some(closed_panes_tx);
+
+                        task::yield_now().await;
+                    }
+
+                    Kube::Log(LogMessage::PaneClosed(pane)) => {
+                        if let Some(closed_log_panes) = &closed_log_panes {
+                            closed_log_panes.send_modify(|panes| {
+                                panes.insert(pane);
+                            });
+                        }
+                    }
+
+                    Kube::Log(LogMessage::SaveRequest(req)) => {
+                        let tx = tx.clone();
+
+                        task::spawn_blocking(move || {
+                            tx.send(LogMessage::SaveResponse(save_log(req)).into())
+                                .expect("Failed to send LogMessage::SaveResponse");
+                        });
+                    }
+
+                    Kube::Exec(ExecMessage::Request { namespace, pod }) => {
+                        let containers = fetch_containers(kube_client, &namespace, &pod).await;
+
+                        tx.send(
+                            ExecMessage::Response {
+                                namespace,
+                                pod,
+                                containers,
+                            }
+                            .into(),
+                        )
+                        .expect("Failed to send ExecMessage::Response");
+                    }
+
+                    Kube::Exec(ExecMessage::Session {
+                        target,
+                        input,
+                        done,
+                    }) => {
+                        let client = kube_client.to_client();
+
+                        tokio::spawn(async move {
+                            let result = exec_session(client, &target, input).await;
+
+                            // 描画スレッドが待機をやめていた場合は結果を捨てる
+                            let _ = done.send(result);
+                        });
+                    }
+
+                    Kube::Debug(DebugMessage::Request(req)) => {
+                        if let Some(handler) = debug_handler {
+                            handler.abort();
+                        }
+
+                        debug_handler =
+                            Some(DebugWorker::new(tx, kube_client.clone(), req).spawn());
 
                         task::yield_now().await;
                     }
@@ -381,6 +534,7 @@ impl Worker for EventController {
                                 tx,
                                 kube_client.clone(),
                                 req,
+                                polling.clone(),
                             )
                             .spawn(),
                         );
@@ -429,6 +583,14 @@ impl Worker for EventController {
                                 h.abort();
                             }
 
+                            if let Some(h) = drain_handler {
+                                h.abort();
+                            }
+
+                            if let Some(h) = debug_handler {
+                                h.abort();
+                            }
+
                             return WorkerResult::ChangedContext(req);
                         }
                     },
@@ -456,7 +618,10 @@ impl Worker for EventController {
                                     &api_resources,
                                     &target_namespaces,
                                 )
-                                .fetch()
+                                .fetch(|list| {
+                                    tx.send(YamlResponse::Resource(Ok(list)).into())
+                                        .expect("Failed to send YamlResponse::Resource");
+                                })
                                 .await;
 
                                 tx.send(YamlResponse::Resource(fetched_data).into())
@@ -474,6 +639,7 @@ impl Worker for EventController {
                                         kube_client.clone(),
                                         shared_api_resources.clone(),
                                         req,
+                                        polling.clone(),
                                     )
                                     .spawn(),
                                 );
@@ -494,6 +660,159 @@ impl Worker for EventController {
                         task::yield_now().await;
                     }
 
+                    // 書き込み操作とdry-runは時間がかかることがあるため、
+                    // 他のリクエストを待たせないようタスクで実行して結果をtxで返す
+                    Kube::Delete(DeleteMessage::Request(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let result = delete_resource(&client, &req).await;
+
+                            tx.send(
+                                DeleteResponse {
+                                    target: req.target,
+                                    result,
+                                }
+                                .into(),
+                            )
+                            .expect("Failed to send DeleteResponse");
+                        });
+                    }
+
+                    Kube::Delete(DeleteMessage::DryRun(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let result = dry_run_delete(&client, &req).await;
+
+                            tx.send(DeleteMessage::DryRunResponse(req, result).into())
+                                .expect("Failed to send DeleteMessage::DryRunResponse");
+                        });
+                    }
+
+                    Kube::Edit(EditMessage::Request(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let res = match req {
+                                EditRequest::Fetch(target) => {
+                                    let yaml = fetch_edit_yaml(&client, &target).await;
+
+                                    EditResponse::Fetched { target, yaml }
+                                }
+                                EditRequest::DryRun(session) => {
+                                    let result = dry_run_edit(&client, &session).await;
+
+                                    EditResponse::DryRun { session, result }
+                                }
+                                EditRequest::Apply(session) => {
+                                    let result = apply_edit(&client, &session).await;
+
+                                    EditResponse::Applied { session, result }
+                                }
+                            };
+
+                            tx.send(res.into()).expect("Failed to send EditResponse");
+                        });
+                    }
+
+                    Kube::PortForward(PortForwardMessage::Request(req)) => {
+                        match req {
+                            PortForwardRequest::Start { target, ports } => {
+                                port_forwards
+                                    .start(kube_client.to_client(), target, ports)
+                                    .await;
+                            }
+                            PortForwardRequest::Stop(id) => {
+                                port_forwards.stop(id).await;
+                            }
+                        }
+
+                        tx.send(
+                            PortForwardMessage::Response(port_forwards.to_table().await).into(),
+                        )
+                        .expect("Failed to send PortForwardMessage::Response");
+                    }
+
+                    Kube::Workload(WorkloadMessage::Request(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let res = execute_workload_request(&client, req).await;
+
+                            tx.send(res.into())
+                                .expect("Failed to send WorkloadResponse");
+                        });
+                    }
+
+                    Kube::Node(NodeMessage::Request(req)) => match req {
+                        NodeRequest::Cordon(node) => {
+                            let client = kube_client.clone();
+
+                            tokio::spawn(async move {
+                                let result = cordon_node(&client, &node, true).await;
+
+                                tx.send(NodeResponse::Action(result).into())
+                                    .expect("Failed to send NodeResponse::Action");
+                            });
+                        }
+                        NodeRequest::Uncordon(node) => {
+                            let client = kube_client.clone();
+
+                            tokio::spawn(async move {
+                                let result = cordon_node(&client, &node, false).await;
+
+                                tx.send(NodeResponse::Action(result).into())
+                                    .expect("Failed to send NodeResponse::Action");
+                            });
+                        }
+                        NodeRequest::Drain(node) => {
+                            if let Some(handler) = drain_handler {
+                                handler.abort();
+                            }
+
+                            drain_handler =
+                                Some(DrainWorker::new(tx, kube_client.clone(), node).spawn());
+
+                            task::yield_now().await;
+                        }
+                    },
+
+                    Kube::Node(NodeMessage::DryRun(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let result = dry_run_node_request(&client, &req).await;
+
+                            tx.send(NodeResponse::DryRun(req, result).into())
+                                .expect("Failed to send NodeResponse::DryRun");
+                        });
+                    }
+
+                    Kube::Debug(DebugMessage::DryRun(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let result = dry_run_debug(&client, &req).await;
+
+                            tx.send(DebugMessage::DryRunResponse(req, result).into())
+                                .expect("Failed to send DebugMessage::DryRunResponse");
+                        });
+                    }
+
+                    Kube::Workload(WorkloadMessage::DryRun(req)) => {
+                        let client = kube_client.clone();
+
+                        tokio::spawn(async move {
+                            let result = dry_run_workload_request(&client, &req).await;
+
+                            tx.send(WorkloadResponse::DryRun(req, result).into())
+                                .expect("Failed to send WorkloadResponse::DryRun");
+                        });
+                    }
+
+                    Kube::ActiveTab(id) => polling.activate(&id),
+
                     Kube::Network(NetworkMessage::Request(req)) => {
                         if let Some(handler) = network_handler {
                             handler.abort();
@@ -506,6 +825,7 @@ impl Worker for EventController {
                                 kube_client.clone(),
                                 req,
                                 shared_api_resources.clone(),
+                                polling.clone(),
                             )
                             .spawn(),
                         );
@@ -0,0 +1,193 @@
+use anyhow::anyhow;
+use crossbeam::channel::Sender;
+
+use crate::{
+    features::{
+        delete::message::{DeleteMessage, DeleteResponse},
+        edit::message::{ApplyError, EditMessage, EditRequest, EditResponse},
+        node::message::{DrainEvent, NodeMessage, NodeRequest, NodeResponse},
+        pod::message::{DebugMessage, ExecMessage},
+        port_forward::message::{PortForwardMessage, PortForwardRequest},
+        workload::message::{WorkloadMessage, WorkloadResponse},
+    },
+    logger,
+    message::Message,
+};
+
+use super::message::Kube;
+
+/// 読み取り専用のコンテキストでは書き込み操作を拒否する
+///
+/// 書き込み操作の場合は、実行せずに各機能のエラーレスポンスを送信してNoneを返す。
+/// 確認ポップアップで拒否されたことがわかるように、dry-runも拒否する。
+/// ポートフォワードの開始は`pods/portforward`への`create`が必要で、
+/// クラスタ内のワークロードへ接続できるようになるため書き込み操作として扱う。
+pub fn reject_write_request(tx: &Sender<Message>, ev: Kube, context: &str) -> Option<Kube> {
+    let err = || {
+        anyhow!(
+            "Context {} is read-only. Write operations are disabled.",
+            context
+        )
+    };
+
+    let res: Message = match ev {
+        Kube::Delete(DeleteMessage::Request(req)) => DeleteResponse {
+            target: req.target,
+            result: Err(err()),
+        }
+        .into(),
+        Kube::Delete(DeleteMessage::DryRun(req)) => {
+            DeleteMessage::DryRunResponse(req, Err(err())).into()
+        }
+
+        Kube::Edit(EditMessage::Request(req)) => match req {
+            EditRequest::Fetch(target) => EditResponse::Fetched {
+                target,
+                yaml: Err(err()),
+            }
+            .into(),
+            EditRequest::DryRun(session) => EditResponse::DryRun {
+                session,
+                result: Err(ApplyError::Other(err())),
+            }
+            .into(),
+            EditRequest::Apply(session) => EditResponse::Applied {
+                session,
+                result: Err(ApplyError::Other(err())),
+            }
+            .into(),
+        },
+
+        Kube::Workload(WorkloadMessage::Request(_)) => WorkloadResponse::Action(Err(err())).into(),
+        Kube::Workload(WorkloadMessage::DryRun(req)) => {
+            WorkloadResponse::DryRun(req, Err(err())).into()
+        }
+
+        Kube::Node(NodeMessage::Request(req)) => match req {
+            NodeRequest::Cordon(_) | NodeRequest::Uncordon(_) => {
+                NodeResponse::Action(Err(err())).into()
+            }
+            NodeRequest::Drain(_) => DrainEvent::Finished(Err(err())).into(),
+        },
+        Kube::Node(NodeMessage::DryRun(req)) => NodeResponse::DryRun(req, Err(err())).into(),
+
+        Kube::Exec(ExecMessage::Request { namespace, pod }) => ExecMessage::Response {
+            namespace,
+            pod,
+            containers: Err(err()),
+        }
+        .into(),
+        Kube::Debug(DebugMessage::Request(req)) => DebugMessage::Response(req, Err(err())).into(),
+        Kube::Debug(DebugMessage::DryRun(req)) => {
+            DebugMessage::DryRunResponse(req, Err(err())).into()
+        }
+
+        Kube::PortForward(PortForwardMessage::Request(PortForwardRequest::Start {
+            target,
+            ..
+        })) => PortForwardMessage::Rejected(target, err()).into(),
+
+        ev => return Some(ev),
+    };
+
+    logger!(
+        warn,
+        "Rejected write request in read-only context {}",
+        context
+    );
+
+    tx.send(res).expect("Failed to send rejected response");
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::{
+        port_forward::message::{PortForwardKind, PortForwardTarget, PortMapping},
+        workload::message::{WorkloadKind, WorkloadRequest, WorkloadTarget},
+    };
+
+    use crossbeam::channel::unbounded;
+
+    use super::*;
+
+    #[test]
+    fn 書き込み操作はエラーレスポンスを返す() {
+        let req = WorkloadRequest::Restart(WorkloadTarget {
+            kind: WorkloadKind::Deployment,
+            namespace: "default".to_string(),
+            name: "app".to_string(),
+        });
+
+        let (tx, rx) = unbounded();
+
+        assert!(
+            reject_write_request(&tx, Kube::Workload(WorkloadMessage::Request(req)), "prod")
+                .is_none()
+        );
+
+        let actual = rx.try_recv();
+
+        let Ok(Message::Kube(Kube::Workload(WorkloadMessage::Response(WorkloadResponse::Action(
+            Err(err),
+        ))))) = actual
+        else {
+            panic!("unexpected result: {:?}", actual);
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "Context prod is read-only. Write operations are disabled."
+        );
+    }
+
+    #[test]
+    fn 書き込み操作以外はそのまま返す() {
+        let (tx, rx) = unbounded();
+
+        let actual = reject_write_request(
+            &tx,
+            Kube::PortForward(PortForwardMessage::Request(PortForwardRequest::Stop(0))),
+            "prod",
+        );
+
+        assert!(matches!(actual, Some(Kube::PortForward(_))));
+        assert!(rx.is_empty());
+    }
+
+    #[test]
+    fn ポートフォワードの開始は拒否する() {
+        let target = PortForwardTarget {
+            kind: PortForwardKind::Pod,
+            namespace: "default".to_string(),
+            name: "app".to_string(),
+        };
+
+        let (tx, rx) = unbounded();
+
+        let actual = reject_write_request(
+            &tx,
+            Kube::PortForward(PortForwardMessage::Request(PortForwardRequest::Start {
+                target: target.clone(),
+                ports: PortMapping {
+                    local: 8080,
+                    remote: 80,
+                },
+            })),
+            "prod",
+        );
+
+        assert!(actual.is_none());
+
+        let actual = rx.try_recv();
+
+        let Ok(Message::Kube(Kube::PortForward(PortForwardMessage::Rejected(rejected, _)))) =
+            actual
+        else {
+            panic!("unexpected result: {:?}", actual);
+        };
+
+        assert_eq!(rejected, target);
+    }
+}
@@ -0,0 +1,251 @@
+//! APIサーバーとの接続状態
+//!
+//! informerのlist・watchや、キャッシュを使わないポーラーの取得結果から連続した接続エラーを数え、
+//! すべてのinformerとポーラーで共有する待ち時間を指数的に延ばす。
+//! キャッシュを読み取るだけのポーラーは結果を記録せず、待ち時間だけを参照する。
+//! 取得に成功すると自動的に接続済みに戻る。
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use crossbeam::channel::Sender;
+use tokio::time::Instant;
+
+use crate::{logger, message::Message};
+
+use super::message::Kube;
+
+/// 最初の再試行までの時間
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// 再試行までの最大の時間
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// この回数以上連続して失敗した場合はオフラインとする
+const OFFLINE_THRESHOLD: u32 = 3;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    #[default]
+    Connected,
+    Degraded,
+    Offline,
+}
+
+/// ヘッダーに表示する接続状態
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConnectionStatus {
+    pub state: ConnectionState,
+    /// 最後に取得に成功した時刻
+    pub last_success: Option<DateTime<Local>>,
+    /// 次に再試行するまでの時間
+    pub backoff: Option<Duration>,
+}
+
+/// コンテキストごとの接続状態をポーラー間で共有する
+#[derive(Clone)]
+pub struct ConnectionHealth {
+    tx: Sender<Message>,
+    state: Arc<Mutex<HealthState>>,
+}
+
+impl ConnectionHealth {
+    pub fn new(tx: Sender<Message>) -> Self {
+        Self {
+            tx,
+            state: Default::default(),
+        }
+    }
+
+    /// 取得結果を記録する
+    ///
+    /// APIサーバーがエラーを返した場合は接続できているため成功として扱う
+    pub fn record<T>(&self, result: &Result<T>) {
+        let status = {
+            let mut state = self.state.lock().expect("Failed to lock connection health");
+
+            let changed = match result {
+                Err(err) if is_connection_error(err) => state.on_failure(Instant::now()),
+                _ => state.on_success(Local::now()),
+            };
+
+            changed.then(|| state.status())
+        };
+
+        if let Some(status) = status {
+            logger!(info, "Connection status changed: {:?}", status);
+
+            self.tx
+                .send(Kube::Health(status).into())
+                .expect("Failed to send Kube::Health");
+        }
+    }
+
+    /// 接続エラーが続いている間、再試行できるまで待つ
+    pub async fn wait_backoff(&self) {
+        let next_attempt = self
+            .state
+            .lock()
+            .expect("Failed to lock connection health")
+            .next_attempt;
+
+        if let Some(next_attempt) = next_attempt {
+            tokio::time::sleep_until(next_attempt).await;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    /// 連続して失敗した回数
+    failures: u32,
+    last_success: Option<DateTime<Local>>,
+    /// 次に再試行できる時刻
+    next_attempt: Option<Instant>,
+}
+
+impl HealthState {
+    /// 成功を記録し、接続状態が変わったかを返す
+    fn on_success(&mut self, now: DateTime<Local>) -> bool {
+        let changed = self.failures != 0;
+
+        self.failures = 0;
+        self.next_attempt = None;
+        self.last_success = Some(now);
+
+        changed
+    }
+
+    /// 失敗を記録し、接続状態が変わったかを返す
+    ///
+    /// 同じ待ち時間の間に複数のポーラーが失敗しても1回として数える
+    fn on_failure(&mut self, now: Instant) -> bool {
+        if self.next_attempt.is_some_and(|next| now < next) {
+            return false;
+        }
+
+        self.failures += 1;
+        self.next_attempt = Some(now + backoff(self.failures));
+
+        true
+    }
+
+    fn state(&self) -> ConnectionState {
+        match self.failures {
+            0 => ConnectionState::Connected,
+            n if n < OFFLINE_THRESHOLD => ConnectionState::Degraded,
+            _ => ConnectionState::Offline,
+        }
+    }
+
+    fn status(&self) -> ConnectionStatus {
+        ConnectionStatus {
+            state: self.state(),
+            last_success: self.last_success,
+            backoff: (0 < self.failures).then(|| backoff(self.failures)),
+        }
+    }
+}
+
+fn backoff(failures: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2_u32.saturating_pow(failures.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+/// APIサーバーに接続できなかったことによるエラーかどうか
+fn is_connection_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<kube::Error>(),
+            Some(kube::Error::HyperError(_) | kube::Error::Service(_) | kube::Error::ReadEvents(_))
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use kube::core::ErrorResponse;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(3, 4)]
+    #[case(5, 16)]
+    #[case(6, 30)]
+    #[case(100, 30)]
+    fn 失敗が続くと待ち時間を指数的に延ばす(
+        #[case] failures: u32,
+        #[case] expected: u64,
+    ) {
+        assert_eq!(backoff(failures), Duration::from_secs(expected));
+    }
+
+    #[test]
+    fn 連続した失敗の回数に応じて接続状態を変える() {
+        let mut state = HealthState::default();
+        let now = Instant::now();
+
+        assert_eq!(state.state(), ConnectionState::Connected);
+
+        assert!(state.on_failure(now));
+        assert_eq!(state.state(), ConnectionState::Degraded);
+
+        assert!(state.on_failure(now + Duration::from_secs(1)));
+        assert!(state.on_failure(now + Duration::from_secs(3)));
+        assert_eq!(state.state(), ConnectionState::Offline);
+        assert_eq!(state.status().backoff, Some(Duration::from_secs(4)));
+
+        assert!(state.on_success(Local::now()));
+        assert_eq!(state.state(), ConnectionState::Connected);
+        assert_eq!(state.next_attempt, None);
+    }
+
+    #[test]
+    fn 待ち時間の間の失敗は数えない() {
+        let mut state = HealthState::default();
+        let now = Instant::now();
+
+        assert!(state.on_failure(now));
+        assert!(!state.on_failure(now + Duration::from_millis(500)));
+
+        assert_eq!(state.failures, 1);
+    }
+
+    #[test]
+    fn 接続できている間の成功は状態を変えない() {
+        let mut state = HealthState::default();
+
+        assert!(!state.on_success(Local::now()));
+        assert!(state.last_success.is_some());
+    }
+
+    #[test]
+    fn apiサーバーが返したエラーは接続エラーとしない() {
+        let err = anyhow::Error::from(kube::Error::Api(ErrorResponse {
+            status: "Failure".to_string(),
+            message: "forbidden".to_string(),
+            reason: "Forbidden".to_string(),
+            code: 403,
+        }));
+
+        assert!(!is_connection_error(&err));
+        assert!(!is_connection_error(&anyhow!("error")));
+    }
+
+    #[test]
+    fn 接続できないエラーを判定する() {
+        let err = anyhow::Error::from(kube::Error::Service("connection refused".into()))
+            .context("Failed to fetch table");
+
+        assert!(is_connection_error(&err));
+    }
+}
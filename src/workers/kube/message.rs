@@ -2,16 +2,28 @@ use anyhow::Result;
 
 use crate::{
     features::{
-        api_resources::message::ApiMessage, config::message::ConfigMessage,
-        context::message::ContextMessage, get::message::GetMessage,
-        namespace::message::NamespaceMessage, network::message::NetworkMessage,
-        pod::message::LogMessage, yaml::message::YamlMessage,
+        api_resources::message::ApiMessage,
+        config::message::ConfigMessage,
+        context::message::ContextMessage,
+        delete::message::DeleteMessage,
+        edit::message::EditMessage,
+        get::message::GetMessage,
+        namespace::message::NamespaceMessage,
+        network::message::NetworkMessage,
+        node::message::NodeMessage,
+        pod::message::{DebugMessage, ExecMessage, LogMessage},
+        port_forward::message::PortForwardMessage,
+        workload::message::WorkloadMessage,
+        yaml::message::YamlMessage,
     },
     kube::table::KubeTable,
     message::Message,
 };
 
-use super::controller::{TargetApiResources, TargetNamespaces};
+use super::{
+    controller::{TargetApiResources, TargetNamespaces},
+    health::ConnectionStatus,
+};
 
 #[derive(Debug)]
 pub enum Kube {
@@ -26,10 +38,20 @@ pub enum Kube {
     Namespace(NamespaceMessage),
     Pod(Result<KubeTable>),
     Log(LogMessage),
+    Exec(ExecMessage),
+    Debug(DebugMessage),
     Config(ConfigMessage),
     Network(NetworkMessage),
     Yaml(YamlMessage),
     Get(GetMessage),
+    Delete(DeleteMessage),
+    Edit(EditMessage),
+    Workload(WorkloadMessage),
+    PortForward(PortForwardMessage),
+    Node(NodeMessage),
+    /// 表示中のタブ（タブのID）
+    ActiveTab(String),
+    Health(ConnectionStatus),
 }
 
 impl From<Kube> for Message {
@@ -1,6 +1,12 @@
 mod action;
+mod config;
+mod edit;
+mod exec;
+mod state;
 mod window;
 
+pub use config::RenderConfig;
+
 use std::{
     cell::RefCell,
     io::{self},
@@ -13,26 +19,40 @@ use std::{
 
 use anyhow::Result;
 use crossbeam::channel::{Receiver, Sender};
-use ratatui::{backend::CrosstermBackend, layout::Direction, Terminal, TerminalOptions, Viewport};
+use ratatui::{backend::CrosstermBackend, Terminal, TerminalOptions, Viewport};
 
 use crate::{
+    features::{
+        edit::{
+            message::EditSession,
+            view::{request_dry_run, show_edit_error},
+        },
+        pod::message::ExecTarget,
+    },
     kube::context::{Context, Namespace},
     logger,
     message::Message,
     panic_set_hook,
     ui::WindowAction,
+    workers::kube::{health::ConnectionStatus, message::Kube},
 };
 
 use self::{
-    action::{update_contents, window_action},
+    action::{exec_error, update_contents, window_action},
+    edit::edit,
+    exec::exec,
+    state::ActionStates,
     window::WindowInit,
 };
 
+use super::InputPause;
+
 pub struct Render {
     tx: Sender<Message>,
     rx: Receiver<Message>,
     is_terminated: Arc<AtomicBool>,
-    direction: Direction,
+    input_pause: InputPause,
+    config: RenderConfig,
 }
 
 impl Render {
@@ -40,13 +60,15 @@ impl Render {
         tx: Sender<Message>,
         rx: Receiver<Message>,
         is_terminated: Arc<AtomicBool>,
-        direction: Direction,
+        input_pause: InputPause,
+        config: RenderConfig,
     ) -> Self {
         Self {
-            direction,
+            config,
             tx,
             rx,
             is_terminated,
+            input_pause,
         }
     }
 
@@ -73,12 +95,16 @@ impl Render {
     fn render(&self) -> Result<()> {
         let namespace = Rc::new(RefCell::new(Namespace::new()));
         let context = Rc::new(RefCell::new(Context::new()));
+        let health = Rc::new(RefCell::new(ConnectionStatus::default()));
+        let states = ActionStates::new(&self.config);
 
         let mut window = WindowInit::new(
-            self.direction,
+            self.config.clone(),
             self.tx.clone(),
             context.clone(),
             namespace.clone(),
+            health.clone(),
+            states.clone(),
         )
         .build();
 
@@ -91,25 +117,93 @@ impl Render {
 
         terminal.clear()?;
 
+        let mut active_tab = String::new();
+
         while !self.is_terminated.load(Ordering::Relaxed) {
+            // 表示していないタブのポーリングを遅くするため、タブの切り替えを通知する
+            if window.active_tab_id() != active_tab {
+                active_tab = window.active_tab_id().to_string();
+
+                self.tx
+                    .send(Kube::ActiveTab(active_tab.clone()).into())
+                    .expect("Failed to send Kube::ActiveTab");
+            }
+
             terminal.draw(|f| {
                 window.render(f);
             })?;
 
-            match window_action(&mut window, &self.rx) {
-                WindowAction::Continue => {}
+            let action = match window_action(&mut window, &self.rx) {
+                WindowAction::UpdateContents(ev) => update_contents(
+                    &mut window,
+                    &self.tx,
+                    ev,
+                    &mut context.borrow_mut(),
+                    &mut namespace.borrow_mut(),
+                    &mut health.borrow_mut(),
+                    &states,
+                ),
+                action => action,
+            };
+
+            match action {
+                WindowAction::Continue | WindowAction::UpdateContents(_) => {}
                 WindowAction::CloseWindow => {
                     self.is_terminated
                         .store(true, std::sync::atomic::Ordering::Relaxed);
                     // break
                 }
-                WindowAction::UpdateContents(ev) => {
-                    update_contents(
-                        &mut window,
-                        ev,
-                        &mut context.borrow_mut(),
-                        &mut namespace.borrow_mut(),
-                    );
+                WindowAction::Suspend(session) => {
+                    // 明け渡し先は各機能が定義した型で判別する
+                    let session = match session.downcast::<ExecTarget>() {
+                        Ok(target) => {
+                            let result = exec(
+                                &mut terminal,
+                                &self.input_pause,
+                                &self.tx,
+                                &self.rx,
+                                &target,
+                                |msg| {
+                                    // 接続中はキー入力を受け取らないため、リソースの更新だけを反映する
+                                    if let Message::Kube(ev) = msg {
+                                        update_contents(
+                                            &mut window,
+                                            &self.tx,
+                                            ev,
+                                            &mut context.borrow_mut(),
+                                            &mut namespace.borrow_mut(),
+                                            &mut health.borrow_mut(),
+                                            &states,
+                                        );
+                                    }
+                                },
+                            );
+
+                            if let Err(err) = result {
+                                logger!(error, "exec error: {:?}", err);
+
+                                exec_error(&mut window, &target, err);
+                            }
+
+                            continue;
+                        }
+                        Err(session) => session,
+                    };
+
+                    match session.downcast::<EditSession>() {
+                        Ok(session) => match edit(&mut terminal, &self.input_pause, &session) {
+                            Ok(edited) => request_dry_run(
+                                &mut window,
+                                &self.tx,
+                                &states.edit,
+                                EditSession { edited, ..*session },
+                            ),
+                            Err(err) => {
+                                show_edit_error(&mut window, &states.edit, &session.target, err)
+                            }
+                        },
+                        Err(_) => logger!(error, "Unknown terminal session"),
+                    }
                 }
             }
         }
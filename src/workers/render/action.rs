@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 
 use anyhow::Result;
-use crossbeam::channel::Receiver;
+use crossbeam::channel::{Receiver, Sender};
 
 use crate::{
     features::{
@@ -9,16 +9,41 @@ use crate::{
         component_id::{
             CONFIG_RAW_DATA_WIDGET_ID, CONFIG_WIDGET_ID, CONTEXT_POPUP_ID, EVENT_WIDGET_ID,
             LIST_POPUP_ID, LIST_WIDGET_ID, MULTIPLE_NAMESPACES_POPUP_ID,
-            NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID, POD_LOG_WIDGET_ID, POD_WIDGET_ID,
-            SINGLE_NAMESPACE_POPUP_ID, YAML_KIND_POPUP_ID, YAML_NAME_POPUP_ID,
-            YAML_NOT_FOUND_POPUP_ID, YAML_POPUP_ID, YAML_WIDGET_ID,
+            NETWORK_DESCRIPTION_WIDGET_ID, NETWORK_WIDGET_ID, NODE_WIDGET_ID, POD_WIDGET_ID,
+            PORT_FORWARD_WIDGET_ID, SINGLE_NAMESPACE_POPUP_ID, WORKLOAD_WIDGET_ID,
+            YAML_KIND_POPUP_ID, YAML_NAME_POPUP_ID, YAML_NOT_FOUND_POPUP_ID, YAML_POPUP_ID,
+            YAML_WIDGET_ID,
         },
         config::message::ConfigMessage,
         context::message::{ContextMessage, ContextResponse},
+        delete::{
+            message::DeleteMessage,
+            view::{show_delete_dry_run_result, show_delete_result},
+        },
+        edit::{
+            message::{EditMessage, EditResponse, EditSession},
+            view::{show_dry_run_result, show_edit_error, show_edit_result},
+        },
         get::message::{GetMessage, GetResponse},
         namespace::message::{NamespaceMessage, NamespaceResponse},
         network::message::{NetworkMessage, NetworkResponse},
-        pod::message::LogMessage,
+        node::{
+            message::{NodeMessage, NodeResponse},
+            view::{show_drain_event, show_node_action_result, show_node_dry_run_result},
+        },
+        pod::{
+            message::{DebugMessage, ExecMessage, ExecTarget, LogEntry, LogMessage},
+            view::{
+                add_log_pane, append_log, append_log_pane, attach_debug_container, open_exec_popup,
+                select_exec_container, show_debug_dry_run_result, show_log_save_result,
+                terminate_log_pane,
+            },
+        },
+        port_forward::{message::PortForwardMessage, view::show_port_forward_error},
+        workload::{
+            message::{WorkloadMessage, WorkloadResponse},
+            view::{show_action_dry_run_result, show_action_result, show_triggered_job},
+        },
         yaml::message::{YamlMessage, YamlResourceListItem, YamlResponse},
     },
     kube::{
@@ -29,13 +54,14 @@ use crate::{
     message::Message,
     ui::{
         event::{Callback, EventResult},
-        util::chars::convert_tabs_to_spaces,
         widget::{Item, LiteralItem, TableItem, WidgetTrait},
         Window, WindowAction,
     },
-    workers::kube::message::Kube,
+    workers::kube::{health::ConnectionStatus, message::Kube},
 };
 
+use super::state::ActionStates;
+
 macro_rules! error_format {
     ($fmt:literal, $($arg:tt)*) => {
         format!(concat!("\x1b[31m[kubetui] ", $fmt,"\x1b[39m"), $($arg)*)
@@ -63,7 +89,10 @@ fn exec_callback(cb: Callback, w: &mut Window) -> WindowAction {
         result = next_cb(w);
     }
 
-    WindowAction::Continue
+    match result {
+        EventResult::WindowAction(action) => action,
+        _ => WindowAction::Continue,
+    }
 }
 
 pub fn window_action(window: &mut Window, rx: &Receiver<Message>) -> WindowAction {
@@ -99,6 +128,9 @@ fn update_widget_item_for_table(window: &mut Window, id: &str, table: Result<Kub
     let widget = window.find_widget_mut(id);
     let w = widget.as_mut_table();
 
+    w.widget_config_mut()
+        .set_loading(table.as_ref().is_ok_and(|table| table.loading));
+
     match table {
         Ok(table) => {
             if w.equal_header(table.header()) {
@@ -180,38 +212,81 @@ fn update_widget_item_for_vec(window: &mut Window, id: &str, vec: Result<Vec<Str
     }
 }
 
+/// execに失敗したときにエラーを表示する
+pub fn exec_error(window: &mut Window, target: &ExecTarget, err: anyhow::Error) {
+    open_exec_popup(
+        window,
+        &target.namespace,
+        &target.pod,
+        Item::Array(error_lines!(err)),
+    );
+}
+
 pub fn update_contents(
     window: &mut Window,
+    tx: &Sender<Message>,
     ev: Kube,
     context: &mut Context,
     namespace: &mut Namespace,
-) {
+    health: &mut ConnectionStatus,
+    states: &ActionStates,
+) -> WindowAction {
     match ev {
         Kube::Pod(pods_table) => {
             update_widget_item_for_table(window, POD_WIDGET_ID, pods_table);
         }
 
         Kube::Log(LogMessage::Response(res)) => {
-            let widget = window.find_widget_mut(POD_LOG_WIDGET_ID);
+            let entries = match res {
+                Ok(entries) => entries,
+                Err(e) => error_lines!(e)
+                    .into_iter()
+                    .map(|line| LogEntry::new(line.item))
+                    .collect(),
+            };
 
-            match res {
-                Ok(i) => {
-                    let array = i
-                        .into_iter()
-                        .map(|i| LiteralItem {
-                            metadata: None,
-                            item: convert_tabs_to_spaces(i),
-                        })
-                        .collect();
+            append_log(window, &states.log, entries);
+        }
 
-                    widget.append_widget_item(Item::Array(array));
-                }
-                Err(e) => {
-                    widget.append_widget_item(Item::Array(error_lines!(e)));
-                }
+        Kube::Log(LogMessage::PaneResponse(panes)) => {
+            for (pane, entries) in panes {
+                append_log_pane(window, &states.log, &pane, entries);
             }
         }
 
+        Kube::Log(LogMessage::PaneAdded(pane)) => {
+            add_log_pane(window, tx, &states.log, &pane);
+        }
+
+        Kube::Log(LogMessage::SaveResponse(res)) => {
+            show_log_save_result(window, res);
+        }
+
+        Kube::Log(LogMessage::PaneRemoved(pane)) => {
+            terminate_log_pane(window, &pane);
+        }
+
+        Kube::Exec(ExecMessage::Response {
+            namespace,
+            pod,
+            containers,
+        }) => match containers {
+            Ok(containers) => {
+                return select_exec_container(window, namespace, pod, containers);
+            }
+            Err(e) => {
+                open_exec_popup(window, &namespace, &pod, Item::Array(error_lines!(e)));
+            }
+        },
+
+        Kube::Debug(DebugMessage::DryRunResponse(req, result)) => {
+            show_debug_dry_run_result(window, &states.debug, req, result);
+        }
+
+        Kube::Debug(DebugMessage::Response(req, result)) => {
+            return attach_debug_container(window, &states.debug, req, result);
+        }
+
         Kube::Config(ConfigMessage::Response(res)) => {
             use crate::features::config::message::ConfigResponse::*;
 
@@ -256,6 +331,8 @@ pub fn update_contents(
             },
             NamespaceResponse::Set(res) => {
                 namespace.update(res);
+
+                states.log.borrow_mut().reset();
             }
         },
 
@@ -271,6 +348,9 @@ pub fn update_contents(
         } => {
             context.update(ctx);
             namespace.update(ns.clone());
+            *health = ConnectionStatus::default();
+
+            states.log.borrow_mut().reset();
 
             window
                 .find_widget_mut(MULTIPLE_NAMESPACES_POPUP_ID)
@@ -381,13 +461,13 @@ pub fn update_contents(
 
                 Resource(res) => match res {
                     Ok(list) => {
-                        if list.items.is_empty() {
+                        if list.items.is_empty() && !list.loading {
                             window.open_popup(YAML_NOT_FOUND_POPUP_ID);
                         } else {
-                            window.open_popup(YAML_NAME_POPUP_ID);
-
                             let widget = window.find_widget_mut(YAML_NAME_POPUP_ID);
 
+                            widget.widget_config_mut().set_loading(list.loading);
+
                             let items = list
                                 .items
                                 .into_iter()
@@ -421,6 +501,7 @@ pub fn update_contents(
                     }
                     Err(e) => {
                         let widget = window.find_widget_mut(YAML_NAME_POPUP_ID);
+                        widget.widget_config_mut().set_loading(false);
                         widget.update_widget_item(Item::Array(error_lines!(e)));
                     }
                 },
@@ -437,6 +518,57 @@ pub fn update_contents(
             update_widget_item_for_vec(window, YAML_POPUP_ID, yaml);
         }
 
+        Kube::Delete(DeleteMessage::DryRunResponse(req, res)) => {
+            show_delete_dry_run_result(window, &states.delete, req, res);
+        }
+
+        Kube::Delete(DeleteMessage::Response(res)) => {
+            show_delete_result(window, res);
+        }
+
+        Kube::Edit(EditMessage::Response(res)) => match res {
+            EditResponse::Fetched { target, yaml } => match yaml {
+                Ok(yaml) => return EditSession::new(target, yaml).into(),
+                Err(err) => show_edit_error(window, &states.edit, &target, err),
+            },
+            EditResponse::DryRun { session, result } => {
+                show_dry_run_result(window, &states.edit, session, result);
+            }
+            EditResponse::Applied { session, result } => {
+                show_edit_result(window, &states.edit, session, result);
+            }
+        },
+
+        Kube::Node(NodeMessage::Response(res)) => match res {
+            NodeResponse::Table(res) => {
+                update_widget_item_for_table(window, NODE_WIDGET_ID, res);
+            }
+            NodeResponse::Action(res) => show_node_action_result(window, res),
+            NodeResponse::DryRun(req, res) => {
+                show_node_dry_run_result(window, &states.node, req, res);
+            }
+            NodeResponse::Drain(ev) => show_drain_event(window, ev),
+        },
+
+        Kube::Workload(WorkloadMessage::Response(res)) => match res {
+            WorkloadResponse::Table(res) => {
+                update_widget_item_for_table(window, WORKLOAD_WIDGET_ID, res);
+            }
+            WorkloadResponse::Action(res) => show_action_result(window, res),
+            WorkloadResponse::DryRun(req, res) => {
+                show_action_dry_run_result(window, &states.workload, req, res);
+            }
+            WorkloadResponse::Triggered(res) => show_triggered_job(window, res),
+        },
+
+        Kube::PortForward(PortForwardMessage::Response(table)) => {
+            update_widget_item_for_table(window, PORT_FORWARD_WIDGET_ID, Ok(table));
+        }
+
+        Kube::PortForward(PortForwardMessage::Rejected(target, err)) => {
+            show_port_forward_error(window, &target, &err);
+        }
+
         Kube::Network(NetworkMessage::Response(ev)) => {
             use NetworkResponse::*;
 
@@ -448,6 +580,12 @@ pub fn update_contents(
             }
         }
 
+        Kube::Health(status) => {
+            *health = status;
+        }
+
         _ => unreachable!(),
     }
+
+    WindowAction::Continue
 }
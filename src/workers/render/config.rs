@@ -0,0 +1,14 @@
+use ratatui::layout::Direction;
+
+use crate::kube::guard::WriteGuard;
+
+#[derive(Debug, Clone)]
+pub struct RenderConfig {
+    pub split_direction: Direction,
+    /// ログウィジェットに保持する最大行数（Noneの場合は無制限）
+    pub log_max_lines: Option<usize>,
+    /// デバッグ用のエフェメラルコンテナに使うイメージ
+    pub debug_image: String,
+    /// ヘッダーに表示するバッジの判定に使う
+    pub write_guard: WriteGuard,
+}
@@ -0,0 +1,132 @@
+use std::{
+    fs,
+    io::Stdout,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use anyhow::{bail, Context as _, Result};
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+use crate::{
+    features::edit::message::{EditSession, EditTarget},
+    logger,
+    workers::InputPause,
+};
+
+use super::exec::{resume_terminal, suspend_terminal};
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+/// 端末をエディタに明け渡して、一時ファイルに書き出したyamlを編集する
+///
+/// エディタはkubectl editと同じく `KUBE_EDITOR`、`EDITOR` の順に参照する
+pub fn edit(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    input_pause: &InputPause,
+    session: &EditSession,
+) -> Result<String> {
+    let _pause = input_pause.pause();
+
+    let path = temp_file_path(&session.target);
+
+    fs::write(&path, &session.edited)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let result = run_editor(terminal, &path);
+
+    let _ = fs::remove_file(&path);
+
+    result
+}
+
+fn run_editor(terminal: &mut Terminal<CrosstermBackend<Stdout>>, path: &Path) -> Result<String> {
+    let (program, args) = editor_command(
+        std::env::var("KUBE_EDITOR").ok(),
+        std::env::var("EDITOR").ok(),
+    );
+
+    logger!(
+        info,
+        "edit: {} {} {}",
+        program,
+        args.join(" "),
+        path.display()
+    );
+
+    suspend_terminal()?;
+
+    let status = Command::new(&program).args(&args).arg(path).status();
+
+    resume_terminal(terminal)?;
+
+    let status = status.with_context(|| format!("Failed to run editor {:?}", program))?;
+
+    if !status.success() {
+        bail!("Editor {:?} exited with {}", program, status);
+    }
+
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn temp_file_path(target: &EditTarget) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "kubetui-edit-{}-{}-{}.yaml",
+        target.kind,
+        target.name,
+        process::id()
+    ))
+}
+
+/// エディタのコマンドと引数を取得する
+///
+/// `code --wait` のように引数を含む指定にも対応する
+fn editor_command(kube_editor: Option<String>, editor: Option<String>) -> (String, Vec<String>) {
+    let editor = [kube_editor, editor]
+        .into_iter()
+        .flatten()
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    let mut words = editor.split_whitespace().map(ToString::to_string);
+
+    let program = words.next().unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    (program, words.collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Some("nvim"), Some("nano"), "nvim", vec![])]
+    #[case(None, Some("code --wait"), "code", vec!["--wait"])]
+    #[case(Some(" "), Some("nano"), "nano", vec![])]
+    #[case(None, None, DEFAULT_EDITOR, vec![])]
+    fn editor_command(
+        #[case] kube_editor: Option<&str>,
+        #[case] editor: Option<&str>,
+        #[case] program: &str,
+        #[case] args: Vec<&str>,
+    ) {
+        let actual = super::editor_command(
+            kube_editor.map(ToString::to_string),
+            editor.map(ToString::to_string),
+        );
+
+        assert_eq!(
+            actual,
+            (
+                program.to_string(),
+                args.into_iter().map(ToString::to_string).collect()
+            )
+        );
+    }
+}
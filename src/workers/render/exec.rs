@@ -0,0 +1,112 @@
+use std::io::{self, Stdout};
+
+use anyhow::{anyhow, Result};
+use crossbeam::channel::{bounded, Receiver, Sender};
+use ratatui::{
+    backend::CrosstermBackend,
+    crossterm::{
+        cursor::Show,
+        event::{
+            DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+        },
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    Terminal,
+};
+
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{
+    features::pod::message::{ExecMessage, ExecTarget},
+    message::Message,
+    workers::InputPause,
+};
+
+/// 端末をコンテナに明け渡して、セッションが終了するまで待つ
+///
+/// 入出力の中継はkubeワーカーが行う。
+/// 接続中のキー入力はUserInputスレッドからセッションに転送し、届いたメッセージはon_messageに渡す。
+pub fn exec(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    input_pause: &InputPause,
+    tx: &Sender<Message>,
+    rx: &Receiver<Message>,
+    target: &ExecTarget,
+    on_message: impl FnMut(Message),
+) -> Result<()> {
+    let (_forward, input) = input_pause.forward();
+
+    suspend_terminal()?;
+
+    // キー入力をそのままコンテナに送るため、rawモードのまま通常画面に切り替える
+    let result = enable_raw_mode()
+        .map_err(Into::into)
+        .and_then(|_| wait_session(tx, rx, target, input, on_message));
+
+    resume_terminal(terminal)?;
+
+    result
+}
+
+/// セッションの終了を待つ
+///
+/// 待機中もポーラーからメッセージが届くため、受信を続けてチャネルが詰まらないようにする
+fn wait_session(
+    tx: &Sender<Message>,
+    rx: &Receiver<Message>,
+    target: &ExecTarget,
+    input: UnboundedReceiver<Event>,
+    mut on_message: impl FnMut(Message),
+) -> Result<()> {
+    let (done_tx, done_rx) = bounded(1);
+
+    tx.send(
+        ExecMessage::Session {
+            target: target.clone(),
+            input,
+            done: done_tx,
+        }
+        .into(),
+    )?;
+
+    loop {
+        crossbeam::select! {
+            recv(done_rx) -> result => {
+                return result.unwrap_or_else(|_| Err(anyhow!("The exec session was aborted")));
+            }
+            recv(rx) -> msg => {
+                on_message(msg?);
+            }
+        }
+    }
+}
+
+pub(super) fn suspend_terminal() -> Result<()> {
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableFocusChange,
+        Show
+    )?;
+
+    disable_raw_mode()?;
+
+    Ok(())
+}
+
+pub(super) fn resume_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+    enable_raw_mode()?;
+
+    execute!(
+        io::stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
+
+    terminal.clear()?;
+
+    Ok(())
+}
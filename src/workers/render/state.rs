@@ -0,0 +1,37 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::features::{
+    delete::view::SharedDeleteState,
+    edit::view::SharedEditState,
+    node::view::SharedNodeActionState,
+    pod::view::{DebugState, LogState, SharedDebugState, SharedLogState},
+    workload::view::SharedWorkloadActionState,
+};
+
+use super::RenderConfig;
+
+/// kubeワーカーのレスポンスを待つ操作の状態
+///
+/// ウィジェットのコールバックと描画ループで共有し、レスポンスが待っている操作のものかを判定する
+#[derive(Clone)]
+pub struct ActionStates {
+    pub delete: SharedDeleteState,
+    pub edit: SharedEditState,
+    pub workload: SharedWorkloadActionState,
+    pub node: SharedNodeActionState,
+    pub debug: SharedDebugState,
+    pub log: SharedLogState,
+}
+
+impl ActionStates {
+    pub fn new(config: &RenderConfig) -> Self {
+        Self {
+            delete: SharedDeleteState::default(),
+            edit: SharedEditState::default(),
+            workload: SharedWorkloadActionState::default(),
+            node: SharedNodeActionState::default(),
+            debug: Rc::new(RefCell::new(DebugState::new(config.debug_image.clone()))),
+            log: Rc::new(RefCell::new(LogState::new(config.log_max_lines))),
+        }
+    }
+}
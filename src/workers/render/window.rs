@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 
 use crossbeam::channel::Sender;
 use k8s_openapi::{
@@ -10,8 +10,8 @@ use k8s_openapi::{
 };
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
-    layout::Direction,
-    text::Line,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::Paragraph,
 };
 
@@ -25,6 +25,11 @@ use crate::{
         },
         config::view::ConfigTab,
         context::{message::ContextRequest, view::ContextPopup},
+        delete::{
+            message::DeleteTarget,
+            view::{open_delete_popup, DeletePopup, SharedDeleteState},
+        },
+        edit::{message::EditTarget, view::EditPopup},
         event::view::EventTab,
         get::{
             message::{GetRequest, GetYamlKind},
@@ -39,12 +44,16 @@ use crate::{
             message::{GatewayVersion, HTTPRouteVersion},
             view::NetworkTab,
         },
+        node::view::NodeTab,
         pod::view::PodTab,
+        port_forward::view::{PortForwardPopup, PortForwardTab, SharedPortForwardState},
+        workload::view::WorkloadTab,
         yaml::view::YamlTab,
     },
     kube::{
         apis::networking::gateway::v1::{Gateway, HTTPRoute},
         context::{Context, Namespace},
+        guard::WriteGuard,
     },
     logger,
     message::{Message, UserEvent},
@@ -54,27 +63,41 @@ use crate::{
         widget::{SelectedItem, WidgetTrait},
         Header, Tab, Window, WindowAction,
     },
+    workers::kube::health::{ConnectionState, ConnectionStatus},
 };
 
+use super::{state::ActionStates, RenderConfig};
+
 pub struct WindowInit {
-    split_mode: Direction,
+    config: RenderConfig,
     tx: Sender<Message>,
     context: Rc<RefCell<Context>>,
     namespaces: Rc<RefCell<Namespace>>,
+    health: Rc<RefCell<ConnectionStatus>>,
+    states: ActionStates,
+    /// yamlポップアップで表示中のリソース
+    yaml_popup_target: Rc<RefCell<Option<EditTarget>>>,
+    port_forward_state: SharedPortForwardState,
 }
 
 impl WindowInit {
     pub fn new(
-        split_mode: Direction,
+        config: RenderConfig,
         tx: Sender<Message>,
         context: Rc<RefCell<Context>>,
         namespaces: Rc<RefCell<Namespace>>,
+        health: Rc<RefCell<ConnectionStatus>>,
+        states: ActionStates,
     ) -> Self {
         Self {
-            split_mode,
+            config,
             tx,
             context,
             namespaces,
+            health,
+            states,
+            yaml_popup_target: Default::default(),
+            port_forward_state: SharedPortForwardState::default(),
         }
     }
 
@@ -125,11 +148,15 @@ impl WindowInit {
             EventResult::Nop
         };
 
-        let open_yaml = open_yaml(self.tx.clone());
+        let open_yaml = open_yaml(self.tx.clone(), self.yaml_popup_target.clone());
 
         let builder = builder.action('h', open_help).action('?', open_help);
         let builder = builder.action('y', open_yaml);
 
+        let open_delete = open_delete(self.tx.clone(), self.states.delete.clone());
+
+        let builder = builder.action('d', open_delete);
+
         //　分割方向を変更する
         let toggle_split_direction = move |w: &mut Window| {
             logger!(info, "Toggle split direction");
@@ -148,12 +175,14 @@ impl WindowInit {
 
         let context = self.context.clone();
         let namespaces = self.namespaces.clone();
+        let write_guard = self.config.write_guard.clone();
+        let health = self.health.clone();
 
         let header = Header::new_callback(2, move || {
             let context = context.borrow();
             let namespaces = namespaces.borrow();
             Paragraph::new(vec![
-                Line::from(format!(" ctx: {}", context)),
+                context_line(&context.0, &write_guard, &health.borrow()),
                 Line::from(format!(" ns: {}", namespaces)),
             ])
         });
@@ -169,19 +198,33 @@ impl WindowInit {
         let PodTab {
             tab: pod_tab,
             log_query_help_popup,
+            log_save_popup,
+            log_save_status_popup,
+            exec_popup,
+            debug_popup,
+            debug_status_popup,
         } = PodTab::new(
             "Pod",
             &self.tx,
             &clipboard,
-            self.split_mode,
+            self.config.split_direction,
             self.namespaces.clone(),
+            self.config.log_max_lines,
+            &self.states.log,
+            &self.port_forward_state,
+            &self.states.debug,
         );
 
         let ConfigTab { tab: config_tab } =
-            ConfigTab::new("Config", &self.tx, &clipboard, self.split_mode);
+            ConfigTab::new("Config", &self.tx, &clipboard, self.config.split_direction);
 
-        let NetworkTab { tab: network_tab } =
-            NetworkTab::new("Network", &self.tx, &clipboard, self.split_mode);
+        let NetworkTab { tab: network_tab } = NetworkTab::new(
+            "Network",
+            &self.tx,
+            &clipboard,
+            self.config.split_direction,
+            &self.port_forward_state,
+        );
 
         let EventTab { tab: event_tab } = EventTab::new("Event", &clipboard);
 
@@ -195,7 +238,28 @@ impl WindowInit {
             kind_popup: yaml_kind_popup,
             name_popup: yaml_name_popup,
             not_found_popup: yaml_not_found_popup,
-        } = YamlTab::new("Yaml", &self.tx, &clipboard);
+        } = YamlTab::new("Yaml", &self.tx, &clipboard, &self.states.delete);
+
+        let EditPopup { popup: edit_popup } = EditPopup::new(&self.tx, self.states.edit.clone());
+
+        let WorkloadTab {
+            tab: workload_tab,
+            scale_popup: workload_scale_popup,
+            action_popup: workload_action_popup,
+        } = WorkloadTab::new("Workload", &self.tx, self.states.workload.clone());
+
+        let NodeTab {
+            tab: node_tab,
+            action_popup: node_action_popup,
+        } = NodeTab::new("Node", &self.tx, self.states.node.clone());
+
+        let PortForwardTab {
+            tab: port_forward_tab,
+        } = PortForwardTab::new("Port Forward", &self.tx);
+
+        let PortForwardPopup {
+            popup: port_forward_popup,
+        } = PortForwardPopup::new(&self.tx, self.port_forward_state.clone());
 
         let ContextPopup {
             popup: context_popup,
@@ -209,9 +273,14 @@ impl WindowInit {
             popup: multiple_namespaces_popup,
         } = MultipleNamespacesPopup::new(&self.tx);
 
+        let DeletePopup {
+            popup: delete_popup,
+        } = DeletePopup::new(&self.tx, self.states.delete.clone());
+
         let HelpPopup { popup: help_popup } = HelpPopup::new();
 
-        let YamlPopup { popup: yaml_popup } = YamlPopup::new(&clipboard);
+        let YamlPopup { popup: yaml_popup } =
+            YamlPopup::new(&self.tx, &clipboard, self.yaml_popup_target.clone());
 
         // Init Window
         let tabs = vec![
@@ -221,6 +290,9 @@ impl WindowInit {
             event_tab,
             list_tab,
             yaml_tab,
+            workload_tab,
+            port_forward_tab,
+            node_tab,
         ];
 
         let popups = vec![
@@ -233,14 +305,28 @@ impl WindowInit {
             Popup::new(yaml_not_found_popup),
             Popup::new(help_popup),
             Popup::new(log_query_help_popup),
+            Popup::new(log_save_popup),
+            Popup::new(log_save_status_popup),
+            Popup::new(exec_popup),
+            Popup::new(debug_popup).fixed_height(3),
+            Popup::new(debug_status_popup),
             Popup::new(yaml_popup),
+            Popup::new(delete_popup),
+            Popup::new(edit_popup),
+            Popup::new(workload_scale_popup).fixed_height(3),
+            Popup::new(workload_action_popup),
+            Popup::new(port_forward_popup).fixed_height(3),
+            Popup::new(node_action_popup),
         ];
 
         (tabs, popups)
     }
 }
 
-fn open_yaml(tx: Sender<Message>) -> impl CallbackFn {
+fn open_yaml(
+    tx: Sender<Message>,
+    yaml_popup_target: Rc<RefCell<Option<EditTarget>>>,
+) -> impl CallbackFn {
     move |w: &mut Window| {
         let widget = w.active_tab().active_widget();
 
@@ -267,29 +353,10 @@ fn open_yaml(tx: Sender<Message>) -> impl CallbackFn {
             return EventResult::Ignore;
         };
 
-        let version = metadata.get("version");
-
-        let kind = match metadata.get("kind").map(|v| v.as_str()) {
-            Some(Pod::KIND) => GetYamlKind::Pod,
-            Some(ConfigMap::KIND) => GetYamlKind::ConfigMap,
-            Some(Secret::KIND) => GetYamlKind::Secret,
-            Some(Ingress::KIND) => GetYamlKind::Ingress,
-            Some(Service::KIND) => GetYamlKind::Service,
-            Some(NetworkPolicy::KIND) => GetYamlKind::NetworkPolicy,
-            Some(Gateway::KIND) => match version.as_ref().map(|v| v.as_str()) {
-                Some("v1") => GetYamlKind::Gateway(GatewayVersion::V1),
-                Some("v1beta1") => GetYamlKind::Gateway(GatewayVersion::V1Beta1),
-                _ => unreachable!(),
-            },
-            Some(HTTPRoute::KIND) => match version.as_ref().map(|v| v.as_str()) {
-                Some("v1") => GetYamlKind::HTTPRoute(HTTPRouteVersion::V1),
-                Some("v1beta1") => GetYamlKind::HTTPRoute(HTTPRouteVersion::V1Beta1),
-                _ => unreachable!(),
-            },
-            _ => {
-                unreachable!();
-            }
-        };
+        let kind = table_row_kind(metadata);
+
+        *yaml_popup_target.borrow_mut() =
+            Some(EditTarget::from_get_yaml_kind(&kind, namespace, name));
 
         tx.send(
             GetRequest {
@@ -307,3 +374,132 @@ fn open_yaml(tx: Sender<Message>) -> impl CallbackFn {
         EventResult::Nop
     }
 }
+
+/// 選択中のリソースを削除する確認ポップアップを開く
+fn open_delete(tx: Sender<Message>, state: SharedDeleteState) -> impl CallbackFn {
+    move |w: &mut Window| {
+        let widget = w.active_tab().active_widget();
+
+        match widget.id() {
+            POD_WIDGET_ID | CONFIG_WIDGET_ID | NETWORK_WIDGET_ID => {}
+            _ => {
+                return EventResult::Ignore;
+            }
+        }
+
+        let Some(SelectedItem::TableRow {
+            metadata: Some(metadata),
+            ..
+        }) = widget.widget_item()
+        else {
+            return EventResult::Ignore;
+        };
+
+        let (Some(namespace), Some(name)) = (metadata.get("namespace"), metadata.get("name"))
+        else {
+            return EventResult::Ignore;
+        };
+
+        let target = DeleteTarget::from_get_yaml_kind(&table_row_kind(&metadata), namespace, name);
+
+        open_delete_popup(w, &tx, &state, target);
+
+        EventResult::Nop
+    }
+}
+
+/// コンテキスト名と、本番環境・読み取り専用のコンテキストを示すバッジ、接続状態を表示する
+fn context_line(
+    context: &str,
+    write_guard: &WriteGuard,
+    health: &ConnectionStatus,
+) -> Line<'static> {
+    let mut spans = vec![Span::raw(format!(" ctx: {}", context))];
+
+    if write_guard.is_production(context) {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            " PROD ",
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if write_guard.is_readonly(context) {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            " READ-ONLY ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    spans.push(Span::raw(" "));
+    spans.extend(connection_spans(health));
+
+    Line::from(spans)
+}
+
+/// 接続状態と、接続できない場合は最後に成功した時刻と再試行までの時間を表示する
+fn connection_spans(health: &ConnectionStatus) -> Vec<Span<'static>> {
+    let (label, color) = match health.state {
+        ConnectionState::Connected => ("● connected", Color::Green),
+        ConnectionState::Degraded => ("● degraded", Color::Yellow),
+        ConnectionState::Offline => ("● offline", Color::Red),
+    };
+
+    let mut spans = vec![Span::styled(
+        label,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )];
+
+    if health.state != ConnectionState::Connected {
+        let last_success = health
+            .last_success
+            .map(|time| time.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|| "never".to_string());
+
+        let backoff = health
+            .backoff
+            .map(|backoff| format!(", retry in {}s", backoff.as_secs()))
+            .unwrap_or_default();
+
+        spans.push(Span::styled(
+            format!(" (last success: {}{})", last_success, backoff),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    spans
+}
+
+/// テーブルの行のメタデータからリソースの種類を取得する
+fn table_row_kind(metadata: &BTreeMap<String, String>) -> GetYamlKind {
+    let version = metadata.get("version");
+
+    match metadata.get("kind").map(|v| v.as_str()) {
+        Some(Pod::KIND) => GetYamlKind::Pod,
+        Some(ConfigMap::KIND) => GetYamlKind::ConfigMap,
+        Some(Secret::KIND) => GetYamlKind::Secret,
+        Some(Ingress::KIND) => GetYamlKind::Ingress,
+        Some(Service::KIND) => GetYamlKind::Service,
+        Some(NetworkPolicy::KIND) => GetYamlKind::NetworkPolicy,
+        Some(Gateway::KIND) => match version.as_ref().map(|v| v.as_str()) {
+            Some("v1") => GetYamlKind::Gateway(GatewayVersion::V1),
+            Some("v1beta1") => GetYamlKind::Gateway(GatewayVersion::V1Beta1),
+            _ => unreachable!(),
+        },
+        Some(HTTPRoute::KIND) => match version.as_ref().map(|v| v.as_str()) {
+            Some("v1") => GetYamlKind::HTTPRoute(HTTPRouteVersion::V1),
+            Some("v1beta1") => GetYamlKind::HTTPRoute(HTTPRouteVersion::V1Beta1),
+            _ => unreachable!(),
+        },
+        _ => {
+            unreachable!();
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use std::{
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex, MutexGuard, PoisonError,
     },
+    thread,
     time::Duration,
 };
 
 use anyhow::Result;
 use crossbeam::channel::Sender;
 use ratatui::crossterm::event::{poll, read, Event as CEvent, KeyEvent, KeyEventKind};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
 use crate::{
     logger,
@@ -16,16 +18,133 @@ use crate::{
     panic_set_hook,
 };
 
+/// crosstermのイベントを待つ時間
+///
+/// 入力の一時停止を要求されてから停止するまでの最大待ち時間になる
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// ユーザー入力の読み取りを一時停止するためのハンドル
+///
+/// 外部コマンドに端末を明け渡している間、UserInputスレッドがイベントを読み取らないようにする。
+/// execセッションの間は読み取りを止めず、キー入力と貼り付けをセッションに転送する
+#[derive(Debug, Clone, Default)]
+pub struct InputPause {
+    requested: Arc<AtomicBool>,
+    lock: Arc<Mutex<()>>,
+    forward: Arc<Mutex<Option<UnboundedSender<CEvent>>>>,
+}
+
+impl InputPause {
+    /// 入力の読み取りを停止する
+    ///
+    /// UserInputスレッドが読み取りを終えるまでブロックし、戻り値をドロップすると再開する
+    pub fn pause(&self) -> InputPauseGuard<'_> {
+        self.requested.store(true, Ordering::SeqCst);
+
+        InputPauseGuard {
+            requested: &self.requested,
+            _guard: self.lock(),
+        }
+    }
+
+    /// キー入力と貼り付けをメインスレッドではなく戻り値のチャネルに送る
+    ///
+    /// 戻り値のガードをドロップすると通常の送信先に戻る
+    pub fn forward(&self) -> (InputForwardGuard<'_>, UnboundedReceiver<CEvent>) {
+        let (tx, rx) = unbounded_channel();
+
+        *self.forward_lock() = Some(tx);
+
+        (
+            InputForwardGuard {
+                forward: &self.forward,
+            },
+            rx,
+        )
+    }
+
+    /// 転送先に送った場合はtrueを返す
+    fn try_forward(&self, ev: &CEvent) -> bool {
+        let forward = self.forward_lock();
+
+        let Some(tx) = forward.as_ref() else {
+            return false;
+        };
+
+        if is_forwarded(ev) {
+            // セッションが先に終了した場合は入力を捨てる
+            let _ = tx.send(ev.clone());
+        }
+
+        true
+    }
+
+    fn forward_lock(&self) -> MutexGuard<'_, Option<UnboundedSender<CEvent>>> {
+        self.forward.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+pub struct InputPauseGuard<'a> {
+    requested: &'a AtomicBool,
+    _guard: MutexGuard<'a, ()>,
+}
+
+impl Drop for InputPauseGuard<'_> {
+    fn drop(&mut self) {
+        self.requested.store(false, Ordering::SeqCst);
+    }
+}
+
+pub struct InputForwardGuard<'a> {
+    forward: &'a Mutex<Option<UnboundedSender<CEvent>>>,
+}
+
+impl Drop for InputForwardGuard<'_> {
+    fn drop(&mut self) {
+        *self.forward.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+/// 転送するイベント
+///
+/// 転送中はマウスやフォーカスのイベントを捨てる
+fn is_forwarded(ev: &CEvent) -> bool {
+    match ev {
+        CEvent::Key(KeyEvent { kind, .. }) => {
+            matches!(kind, KeyEventKind::Press | KeyEventKind::Repeat)
+        }
+        CEvent::Paste(_) => true,
+        _ => false,
+    }
+}
+
 /// ユーザー入力を受け付けるワーカースレッドを生成する構造体
 /// イベントデータはチャネルを介してメインスレッドに送信される
 pub struct UserInput {
     tx: Sender<Message>,
     is_terminated: Arc<AtomicBool>,
+    input_pause: InputPause,
 }
 
 impl UserInput {
-    pub fn new(tx: Sender<Message>, is_terminated: Arc<AtomicBool>) -> Self {
-        Self { tx, is_terminated }
+    pub fn new(
+        tx: Sender<Message>,
+        is_terminated: Arc<AtomicBool>,
+        input_pause: InputPause,
+    ) -> Self {
+        Self {
+            tx,
+            is_terminated,
+            input_pause,
+        }
     }
 
     pub fn start(&self) -> Result<()> {
@@ -50,9 +169,26 @@ impl UserInput {
 
     fn poll(&self) -> Result<()> {
         while !self.is_terminated.load(Ordering::Relaxed) {
-            if let Ok(true) = poll(Duration::from_secs(1)) {
+            if self.input_pause.is_requested() {
+                thread::sleep(POLL_TIMEOUT);
+                continue;
+            }
+
+            let _guard = self.input_pause.lock();
+
+            // ロックを取得するまでの間に停止を要求された場合は読み取らない
+            if self.input_pause.is_requested() {
+                continue;
+            }
+
+            if let Ok(true) = poll(POLL_TIMEOUT) {
                 let ev = read()?;
 
+                // セッションへの入力はパスワードを含むことがあるため記録しない
+                if self.input_pause.try_forward(&ev) {
+                    continue;
+                }
+
                 logger!(debug, "{:?}", ev);
 
                 match ev {
@@ -77,3 +213,29 @@ impl UserInput {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+
+    #[test]
+    fn 転送中はキー入力をセッションに送る() {
+        let input_pause = InputPause::default();
+
+        let ev = CEvent::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+
+        {
+            let (_guard, mut rx) = input_pause.forward();
+
+            assert!(input_pause.try_forward(&ev));
+            assert!(input_pause.try_forward(&CEvent::FocusLost));
+
+            assert_eq!(rx.try_recv().ok(), Some(ev.clone()));
+            assert!(rx.try_recv().is_err());
+        }
+
+        assert!(!input_pause.try_forward(&ev));
+    }
+}